@@ -29,6 +29,12 @@ use tracing::{debug, trace};
 ///
 /// The manager owns the in-memory block graph and a cache of flattened state trie overlays keyed by
 /// `(anchor_hash, tip_hash)`.
+///
+/// Overlays are handed out as reference-counted, read-only [`TrieInputSorted`] values, so every
+/// caller requesting the same `(anchor_hash, tip_hash)` pair holds the same allocation. When
+/// building payloads for several sibling forks on top of one parent, the flattened trie nodes and
+/// hashed state are therefore kept in memory once, rather than once per state root task. Memory
+/// grows with the number of distinct parents being built on, not with the number of tasks.
 #[derive(Clone)]
 pub struct StateTrieOverlayManager<N: NodePrimitives = EthPrimitives> {
     blocks: Arc<DashMap<B256, ExecutedBlock<N>>>,
@@ -228,6 +234,20 @@ impl<N: NodePrimitives> StateTrieOverlayManager<N> {
         Ok((Arc::clone(&input.nodes), Arc::clone(&input.state)))
     }
 
+    /// Returns the shared flattened overlay from `anchor_hash` to `parent_hash`.
+    ///
+    /// Unlike [`Self::overlay_for_parent`], this returns the cached [`TrieInputSorted`] itself,
+    /// including its prefix sets. Concurrent and repeated requests for the same pair are
+    /// deduplicated: the overlay is computed at most once and all callers receive clones of the
+    /// same [`Arc`], which can be checked with [`Arc::ptr_eq`].
+    pub fn shared_overlay_for_parent(
+        &self,
+        parent_hash: B256,
+        anchor_hash: B256,
+    ) -> Result<Arc<TrieInputSorted>, StateTrieOverlayError> {
+        self.get_overlay(parent_hash, anchor_hash)
+    }
+
     #[tracing::instrument(
         level = "trace",
         target = "chain_state::state_trie_overlay",
@@ -679,6 +699,34 @@ mod tests {
         assert!(Arc::ptr_eq(&short, &cached_short));
     }
 
+    #[test]
+    fn sibling_requests_share_overlay() {
+        let manager = StateTrieOverlayManager::default();
+        let blocks = test_blocks();
+        for block in &blocks {
+            manager.insert_block(block.clone());
+        }
+
+        let parent_hash = blocks[2].recovered_block().hash();
+        let anchor_hash = blocks[0].recovered_block().parent_hash();
+
+        let siblings = thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|_| {
+                    scope.spawn(|| manager.shared_overlay_for_parent(parent_hash, anchor_hash))
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().map(|handle| handle.join().unwrap().unwrap()).collect::<Vec<_>>()
+        });
+        for overlay in &siblings[1..] {
+            assert!(Arc::ptr_eq(&siblings[0], overlay));
+        }
+
+        let (nodes, state) = manager.overlay_for_parent(parent_hash, anchor_hash).unwrap();
+        assert!(Arc::ptr_eq(&siblings[0].nodes, &nodes));
+        assert!(Arc::ptr_eq(&siblings[0].state, &state));
+    }
+
     #[test]
     fn returns_anchor_for_in_memory_parent() {
         let manager = StateTrieOverlayManager::default();
@@ -1,6 +1,10 @@
 //! Engine tree configuration.
 
+#[cfg(feature = "trie-debug")]
+use alloc::vec::Vec;
 use alloy_eips::merge::EPOCH_SLOTS;
+#[cfg(feature = "trie-debug")]
+use alloy_primitives::B256;
 use core::time::Duration;
 
 /// Triggers persistence when the number of canonical blocks in memory exceeds this threshold.
@@ -164,6 +168,9 @@ pub struct TreeConfig {
     slow_block_threshold: Option<Duration>,
     /// Whether to fully disable sparse trie cache pruning between blocks.
     disable_sparse_trie_cache_pruning: bool,
+    /// Whether the sparse trie task takes storage trie updates out of the sparse trie as soon as
+    /// the storage roots are calculated, instead of once after the state root.
+    sparse_trie_incremental_updates: bool,
     /// Timeout for the state root task before spawning a sequential fallback computation.
     /// If `Some`, after waiting this duration for the state root task, a sequential state root
     /// computation is spawned in parallel and whichever finishes first is used.
@@ -194,11 +201,44 @@ pub struct TreeConfig {
     /// This trusts the block header's state root. It is intended for experiments that measure
     /// execution without trie state-root work.
     skip_state_root: bool,
+    /// How long the sparse trie task buffers prefetch proof targets before dispatching them.
+    ///
+    /// Prefetch hints tend to arrive in bursts, so a short window trades latency for fewer,
+    /// larger multiproofs. A state update closes the window early. `Duration::ZERO` dispatches
+    /// prefetch targets immediately.
+    prefetch_coalesce_window: Duration,
+    /// Maximum number of state updates the sparse trie task merges before applying them.
+    ///
+    /// Consecutive updates often touch the same accounts, so merging them saves proof fetches at
+    /// the cost of latency. `0` disables coalescing by count.
+    state_update_coalesce_count: usize,
+    /// How long the sparse trie task merges state updates before applying them.
+    ///
+    /// `Duration::ZERO` disables coalescing by time. If both limits are set, whichever is reached
+    /// first applies the merged updates.
+    state_update_coalesce_window: Duration,
+    /// How long the sparse trie task waits for a message while proofs are in flight before
+    /// failing.
+    ///
+    /// A proof job that dies without reporting a result would otherwise leave the task waiting
+    /// forever. This should be generous, since slow proofs are not an error. `None` waits
+    /// indefinitely.
+    proof_result_timeout: Option<Duration>,
+    /// Maximum number of proof batches a single sparse trie task has in flight at once.
+    ///
+    /// Lets several concurrent state root computations share the proof worker pool without one
+    /// of them taking all workers. Clamped to the size of the task's account proof worker pool.
+    /// `None` only bounds a task by the pool size.
+    max_concurrent_multiproofs: Option<usize>,
     /// Maximum random jitter applied before each proof computation (trie-debug only).
     /// When set, each proof worker sleeps for a random duration up to this value
     /// before starting a proof calculation.
     #[cfg(feature = "trie-debug")]
     proof_jitter: Option<Duration>,
+    /// Hashed addresses of accounts whose subtree root is recomputed from a fresh multiproof
+    /// before each state root task and checked against the parent state root (trie-debug only).
+    #[cfg(feature = "trie-debug")]
+    subtree_check_accounts: Vec<B256>,
 }
 
 impl Default for TreeConfig {
@@ -232,6 +272,7 @@ impl Default for TreeConfig {
             sparse_trie_prune_depth: DEFAULT_SPARSE_TRIE_PRUNE_DEPTH,
             slow_block_threshold: None,
             disable_sparse_trie_cache_pruning: false,
+            sparse_trie_incremental_updates: false,
             state_root_task_timeout: Some(DEFAULT_STATE_ROOT_TASK_TIMEOUT),
             share_execution_cache_with_payload_builder: false,
             share_sparse_trie_with_payload_builder: false,
@@ -240,8 +281,15 @@ impl Default for TreeConfig {
             disable_bal_parallel_state_root: false,
             disable_bal_batch_io: false,
             skip_state_root: false,
+            prefetch_coalesce_window: Duration::ZERO,
+            state_update_coalesce_count: 0,
+            state_update_coalesce_window: Duration::ZERO,
+            proof_result_timeout: None,
+            max_concurrent_multiproofs: None,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
+            #[cfg(feature = "trie-debug")]
+            subtree_check_accounts: Vec::new(),
         }
     }
 }
@@ -305,6 +353,7 @@ impl TreeConfig {
             sparse_trie_prune_depth,
             slow_block_threshold,
             disable_sparse_trie_cache_pruning: false,
+            sparse_trie_incremental_updates: false,
             state_root_task_timeout,
             share_execution_cache_with_payload_builder,
             share_sparse_trie_with_payload_builder,
@@ -313,8 +362,15 @@ impl TreeConfig {
             disable_bal_parallel_state_root: false,
             disable_bal_batch_io: false,
             skip_state_root: false,
+            prefetch_coalesce_window: Duration::ZERO,
+            state_update_coalesce_count: 0,
+            state_update_coalesce_window: Duration::ZERO,
+            proof_result_timeout: None,
+            max_concurrent_multiproofs: None,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
+            #[cfg(feature = "trie-debug")]
+            subtree_check_accounts: Vec::new(),
         }
     }
 
@@ -638,6 +694,20 @@ impl TreeConfig {
         self
     }
 
+    /// Returns whether storage trie updates are taken incrementally by the sparse trie task.
+    pub const fn sparse_trie_incremental_updates(&self) -> bool {
+        self.sparse_trie_incremental_updates
+    }
+
+    /// Setter for whether the sparse trie task takes storage trie updates incrementally.
+    ///
+    /// Updates of a storage trie are taken once its root is calculated, which bounds the update
+    /// buffers of the sparse tries for blocks that touch many storage slots. Disabled by default.
+    pub const fn with_sparse_trie_incremental_updates(mut self, value: bool) -> Self {
+        self.sparse_trie_incremental_updates = value;
+        self
+    }
+
     /// Returns the state root task timeout.
     pub const fn state_root_task_timeout(&self) -> Option<Duration> {
         self.state_root_task_timeout
@@ -739,6 +809,66 @@ impl TreeConfig {
         self
     }
 
+    /// Returns how long prefetch proof targets are buffered before being dispatched.
+    pub const fn prefetch_coalesce_window(&self) -> Duration {
+        self.prefetch_coalesce_window
+    }
+
+    /// Setter for how long prefetch proof targets are buffered before being dispatched.
+    pub const fn with_prefetch_coalesce_window(mut self, window: Duration) -> Self {
+        self.prefetch_coalesce_window = window;
+        self
+    }
+
+    /// Returns the maximum number of state updates merged before they are applied.
+    pub const fn state_update_coalesce_count(&self) -> usize {
+        self.state_update_coalesce_count
+    }
+
+    /// Setter for the maximum number of state updates merged before they are applied.
+    pub const fn with_state_update_coalesce_count(mut self, count: usize) -> Self {
+        self.state_update_coalesce_count = count;
+        self
+    }
+
+    /// Returns how long state updates are merged before they are applied.
+    pub const fn state_update_coalesce_window(&self) -> Duration {
+        self.state_update_coalesce_window
+    }
+
+    /// Setter for how long state updates are merged before they are applied.
+    pub const fn with_state_update_coalesce_window(mut self, window: Duration) -> Self {
+        self.state_update_coalesce_window = window;
+        self
+    }
+
+    /// Returns how long the sparse trie task waits for a message while proofs are in flight.
+    pub const fn proof_result_timeout(&self) -> Option<Duration> {
+        self.proof_result_timeout
+    }
+
+    /// Setter for how long the sparse trie task waits for a message while proofs are in flight.
+    pub const fn with_proof_result_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.proof_result_timeout = timeout;
+        self
+    }
+
+    /// Returns the maximum number of proof batches a sparse trie task has in flight at once.
+    pub const fn max_concurrent_multiproofs(&self) -> Option<usize> {
+        self.max_concurrent_multiproofs
+    }
+
+    /// Setter for the maximum number of proof batches a sparse trie task has in flight at once.
+    ///
+    /// # Panics
+    ///
+    /// If the limit is zero.
+    pub const fn with_max_concurrent_multiproofs(mut self, max: Option<usize>) -> Self {
+        assert!(!matches!(max, Some(0)), "max_concurrent_multiproofs must be at least 1");
+        self.max_concurrent_multiproofs = max;
+        self
+    }
+
     /// Returns the proof jitter duration, if configured (trie-debug only).
     #[cfg(feature = "trie-debug")]
     pub const fn proof_jitter(&self) -> Option<Duration> {
@@ -751,6 +881,21 @@ impl TreeConfig {
         self.proof_jitter = proof_jitter;
         self
     }
+
+    /// Returns the hashed addresses of accounts whose subtree root is checked against the parent
+    /// state root before each state root task (trie-debug only).
+    #[cfg(feature = "trie-debug")]
+    pub fn subtree_check_accounts(&self) -> &[B256] {
+        &self.subtree_check_accounts
+    }
+
+    /// Setter for the accounts whose subtree root is checked before each state root task
+    /// (trie-debug only).
+    #[cfg(feature = "trie-debug")]
+    pub fn with_subtree_check_accounts(mut self, accounts: Vec<B256>) -> Self {
+        self.subtree_check_accounts = accounts;
+        self
+    }
 }
 
 #[cfg(test)]
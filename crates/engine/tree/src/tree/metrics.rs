@@ -11,6 +11,7 @@ use reth_metrics::{
 };
 use reth_primitives_traits::{constants::gas_units::MEGAGAS, FastInstant as Instant};
 use reth_trie::updates::TrieUpdates;
+use reth_trie_parallel::state_root_task::FetchedProofTargets;
 use std::time::Duration;
 
 /// Upper bounds for each gas bucket. The last bucket is a catch-all for
@@ -586,6 +587,10 @@ pub struct BlockValidationMetrics {
     pub state_root_duration: Gauge,
     /// Histogram for state root duration ie the time spent blocked waiting for the state root
     pub state_root_histogram: Histogram,
+    /// Histogram of the number of account proof targets fetched by the state root task
+    pub state_root_fetched_account_targets: Histogram,
+    /// Histogram of the number of storage proof targets fetched by the state root task
+    pub state_root_fetched_storage_targets: Histogram,
     /// Histogram of deferred trie computation duration.
     pub deferred_trie_compute_duration: Histogram,
     /// Payload conversion and validation latency
@@ -613,6 +618,13 @@ impl BlockValidationMetrics {
         self.state_root_histogram.record(elapsed_as_secs);
     }
 
+    /// Records the number of account and storage proof targets the state root task fetched
+    pub fn record_fetched_proof_targets(&self, targets: &FetchedProofTargets) {
+        self.state_root_fetched_account_targets.record(targets.accounts.len() as f64);
+        self.state_root_fetched_storage_targets
+            .record(targets.storages.values().map(|slots| slots.len()).sum::<usize>() as f64);
+    }
+
     /// Records a new payload validation time, updating both the histogram and the payload
     /// validation gauge
     pub fn record_payload_validation(&self, elapsed_as_secs: f64) {
@@ -633,10 +645,12 @@ pub(crate) struct BlockBufferMetrics {
 mod tests {
     use super::*;
     use alloy_eips::eip7685::Requests;
-    use metrics_util::debugging::{DebuggingRecorder, Snapshotter};
+    use alloy_primitives::B256;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
     use reth_ethereum_primitives::Receipt;
     use reth_execution_types::BlockExecutionResult;
     use reth_revm::db::BundleState;
+    use reth_trie_common::ProofV2TargetParent;
 
     fn setup_test_recorder() -> Snapshotter {
         let recorder = DebuggingRecorder::new();
@@ -695,4 +709,38 @@ mod tests {
         assert!(found_execution_metrics, "Expected to find sync.execution metrics");
         assert!(found_thread_resource_metrics, "Expected to find thread resource metrics");
     }
+
+    #[test]
+    fn test_record_fetched_proof_targets() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        let mut targets = FetchedProofTargets::default();
+        targets.accounts.insert(B256::repeat_byte(0x11), ProofV2TargetParent::NONE);
+        targets.accounts.insert(B256::repeat_byte(0x22), ProofV2TargetParent::NONE);
+        let slots = targets.storages.entry(B256::repeat_byte(0x11)).or_default();
+        slots.insert(B256::repeat_byte(0x01), ProofV2TargetParent::NONE);
+        slots.insert(B256::repeat_byte(0x02), ProofV2TargetParent::new(1));
+        slots.insert(B256::repeat_byte(0x03), ProofV2TargetParent::NONE);
+
+        reth_metrics::metrics::with_local_recorder(&recorder, || {
+            BlockValidationMetrics::default().record_fetched_proof_targets(&targets);
+        });
+
+        let recorded = |name: &str| {
+            snapshotter.snapshot().into_vec().into_iter().find_map(|(key, _, _, value)| {
+                let DebugValue::Histogram(values) = value else { return None };
+                (key.key().name() == name)
+                    .then(|| values.into_iter().map(|value| value.0).collect::<Vec<_>>())
+            })
+        };
+        assert_eq!(
+            recorded("sync.block_validation.state_root_fetched_account_targets"),
+            Some(vec![2.0])
+        );
+        assert_eq!(
+            recorded("sync.block_validation.state_root_fetched_storage_targets"),
+            Some(vec![3.0])
+        );
+    }
 }
@@ -54,6 +54,7 @@ use crate::tree::{
 };
 use alloy_primitives::B256;
 use crossbeam_channel::Receiver as CrossbeamReceiver;
+use parking_lot::Mutex;
 use reth_chain_state::{ExecutedBlock, PreservedSparseTrie, StateTrieOverlayManager};
 use reth_errors::ProviderResult;
 use reth_evm::{ConfigureEvm, OnStateHook};
@@ -68,15 +69,18 @@ use reth_provider::{
 use reth_tasks::utils::increase_thread_priority;
 use reth_trie::{
     hashed_cursor::HashedCursorFactory, trie_cursor::TrieCursorFactory, updates::TrieUpdates,
-    HashedPostState,
+    DecodedMultiProofV2, HashedPostState,
 };
+#[cfg(feature = "trie-debug")]
+use reth_trie_common::MultiProofTargetsV2;
 use reth_trie_parallel::proof_task::{ProofTaskCtx, ProofWorkerHandle};
 pub use reth_trie_parallel::{
     error::StateRootTaskError,
     state_root_task::{
-        evm_state_to_hashed_post_state, PayloadStateRootHandle, StateAccessHint,
-        StateRootComputeOutcome, StateRootHandle, StateRootHintStream, StateRootMessage,
-        StateRootSink, StateRootTaskCancelGuard, StateRootUpdateHook, StateRootUpdateStream,
+        evm_state_to_hashed_post_state, evm_state_to_hashed_post_state_with_hasher,
+        FetchedProofTargets, PayloadStateRootHandle, StateAccessHint, StateRootComputeOutcome,
+        StateRootHandle, StateRootHintStream, StateRootMessage, StateRootSink,
+        StateRootTaskCancelGuard, StateRootTaskLoad, StateRootUpdateHook, StateRootUpdateStream,
     },
 };
 #[cfg(feature = "trie-debug")]
@@ -487,6 +491,8 @@ type SerialFallbackRx = mpsc::Receiver<ProviderResult<(B256, TrieUpdates, Arc<Ha
 #[derive(Default)]
 pub struct DefaultStateRootStrategy {
     metrics: SparseTrieTaskMetrics,
+    unrevealed_proofs: UnrevealedProofs,
+    preserved_proof_targets: PreservedProofTargets,
 }
 
 impl fmt::Debug for DefaultStateRootStrategy {
@@ -537,10 +543,15 @@ impl DefaultStateRootStrategy {
         let halve_workers = transaction_count
             .is_some_and(|count| count <= Self::SMALL_BLOCK_PROOF_WORKER_TX_THRESHOLD);
         let proof_handle = ProofWorkerHandle::new(executor, task_ctx, halve_workers);
+        let pool_size = proof_handle.total_account_workers();
+        let max_concurrent_proof_batches =
+            config.max_concurrent_multiproofs().map(|max| max.clamp(1, pool_size.max(1)));
+        let load = StateRootTaskLoad::new(max_concurrent_proof_batches.unwrap_or(pool_size));
 
         let (state_root_tx, state_root_rx) = mpsc::channel();
         let (hashed_state_tx, hashed_state_rx) = mpsc::channel();
         let parent_state_root = parent_header.state_root();
+        let pre_revealed_nodes = self.unrevealed_proofs.take(parent_header.hash());
 
         self.spawn_sparse_trie_task(
             executor,
@@ -554,11 +565,22 @@ impl DefaultStateRootStrategy {
                 parent_header,
                 preserved_sparse_trie,
                 chunk_size: config.multiproof_chunk_size(),
+                expected_updates: transaction_count,
+                prefetch_coalesce_window: config.prefetch_coalesce_window(),
+                state_update_coalesce_count: config.state_update_coalesce_count(),
+                state_update_coalesce_window: config.state_update_coalesce_window(),
+                proof_result_timeout: config.proof_result_timeout(),
+                max_concurrent_proof_batches,
+                incremental_trie_updates: config.sparse_trie_incremental_updates(),
+                load: load.clone(),
                 pending_sparse_trie_prune_blocks: if config.disable_sparse_trie_cache_pruning() {
                     None
                 } else {
                     pending_sparse_trie_prune_blocks
                 },
+                pre_revealed_nodes,
+                #[cfg(feature = "trie-debug")]
+                subtree_check_accounts: config.subtree_check_accounts().to_vec(),
             },
         );
 
@@ -569,6 +591,7 @@ impl DefaultStateRootStrategy {
             state_root_rx,
             hashed_state_rx,
         )
+        .with_load(load)
     }
 
     /// Spawns the sparse-trie task and preserves its trie for the next state-root job.
@@ -588,10 +611,23 @@ impl DefaultStateRootStrategy {
             parent_header,
             preserved_sparse_trie,
             chunk_size,
+            expected_updates,
+            prefetch_coalesce_window,
+            state_update_coalesce_count,
+            state_update_coalesce_window,
+            proof_result_timeout,
+            max_concurrent_proof_batches,
+            incremental_trie_updates,
+            load,
             pending_sparse_trie_prune_blocks,
+            pre_revealed_nodes,
+            #[cfg(feature = "trie-debug")]
+            subtree_check_accounts,
         } = options;
         let state_trie_overlays = state_trie_overlays.clone();
         let trie_metrics = self.metrics.clone();
+        let unrevealed_proofs = self.unrevealed_proofs.clone();
+        let preserved_proof_targets = self.preserved_proof_targets.clone();
         let executor = executor.clone();
 
         let parent_span = Span::current();
@@ -651,7 +687,7 @@ impl DefaultStateRootStrategy {
                 }
                 None => new_sparse_state_trie(),
             };
-            let mut task = SparseTrieCacheTask::new_with_trie(
+            let task = SparseTrieCacheTask::new_with_trie(
                 &executor,
                 from_multi_proof,
                 cancel_rx,
@@ -662,7 +698,40 @@ impl DefaultStateRootStrategy {
                 parent_state_root,
                 new_epoch,
                 chunk_size,
-            );
+            )
+            .with_prefetch_coalesce_window(prefetch_coalesce_window)
+            .with_state_update_coalescing(state_update_coalesce_count, state_update_coalesce_window)
+            .with_proof_result_timeout(proof_result_timeout)
+            .with_max_concurrent_proof_batches(max_concurrent_proof_batches)
+            .with_incremental_trie_updates(incremental_trie_updates)
+            .with_load(load);
+            let task = match expected_updates {
+                Some(expected_updates) => task.with_expected_updates(expected_updates),
+                None => task,
+            };
+            // Carried-over targets stand for nodes revealed in the preserved trie, so they are
+            // only valid if that trie is reused.
+            let mut task = match preserved_proof_targets.take(parent_state_root) {
+                Some(targets) if reused_preserved_sparse_trie => {
+                    task.with_fetched_proof_targets(Arc::unwrap_or_clone(targets))
+                }
+                _ => task,
+            };
+            if let Some(nodes) = pre_revealed_nodes &&
+                let Err(err) = task.reveal_pre_revealed_nodes(nodes)
+            {
+                let (trie, deferred) = task.into_cleared_trie();
+                state_trie_overlays.clear_sparse_trie();
+                executor.spawn_drop(trie);
+                executor.spawn_drop(deferred);
+                let _ = state_root_tx.send(Err(err));
+                return;
+            }
+
+            #[cfg(feature = "trie-debug")]
+            if !subtree_check_accounts.is_empty() {
+                check_parent_subtree_root(&mut task, subtree_check_accounts, parent_state_root);
+            }
 
             let result = task.run();
             let task_result = result.as_ref().ok().cloned();
@@ -677,10 +746,18 @@ impl DefaultStateRootStrategy {
                 );
                 let (preserved, completer) =
                     PreservedSparseTrie::pending(result.state_root, preserved_anchor_hash);
+                // Set before publishing the trie, so a task taking it sees its targets. Pruning
+                // removes revealed nodes, so the fetched targets no longer describe a pruned trie.
+                preserved_proof_targets.set(
+                    prune_before
+                        .is_none()
+                        .then(|| (result.state_root, result.fetched_proof_targets.clone())),
+                );
                 state_trie_overlays.store_sparse_trie(preserved);
                 Some(completer)
             } else {
                 state_trie_overlays.clear_sparse_trie();
+                preserved_proof_targets.set(None);
                 None
             };
 
@@ -696,6 +773,7 @@ impl DefaultStateRootStrategy {
                 );
                 let (trie, deferred) = task.into_cleared_trie();
                 state_trie_overlays.clear_sparse_trie();
+                preserved_proof_targets.set(None);
                 executor.spawn_drop(trie);
                 executor.spawn_drop(deferred);
                 return;
@@ -727,10 +805,18 @@ impl DefaultStateRootStrategy {
                 }
                 deferred
             } else {
+                let pending_proofs = task.drain_pending_proofs();
                 debug!(
                     target: "engine::tree::payload_processor",
-                    "State root computation failed, dropping trie"
+                    pending_proofs = pending_proofs.len(),
+                    pending_proof_accounts = pending_proofs
+                        .iter()
+                        .map(|(state, _)| state.accounts.len())
+                        .sum::<usize>(),
+                    "State root computation failed, dropping trie and keeping unrevealed proofs"
                 );
+                unrevealed_proofs
+                    .store(parent_hash, pending_proofs.into_iter().map(|(_, proof)| proof));
                 let (trie, deferred) = task.into_cleared_trie();
                 trie_to_drop = Some(trie);
                 deferred
@@ -743,12 +829,87 @@ impl DefaultStateRootStrategy {
     }
 }
 
+/// Proofs that a failed sparse trie task fetched but never revealed.
+///
+/// They are revealed by the next task computing a state root on top of the same parent block,
+/// e.g. the next attempt of a payload build, so it does not fetch the same proofs again. Proofs
+/// are keyed by the parent block hash, so they are always consistent with the parent state.
+#[derive(Debug, Default, Clone)]
+struct UnrevealedProofs(Arc<Mutex<Option<(B256, DecodedMultiProofV2)>>>);
+
+impl UnrevealedProofs {
+    /// Stores proofs fetched for the state of the given parent block, dropping proofs stored for
+    /// any other parent.
+    fn store(&self, parent_hash: B256, proofs: impl IntoIterator<Item = DecodedMultiProofV2>) {
+        let mut stored = self.0.lock();
+        let mut nodes = match stored.take() {
+            Some((hash, nodes)) if hash == parent_hash => nodes,
+            _ => DecodedMultiProofV2::default(),
+        };
+        for proof in proofs {
+            nodes.extend(proof);
+        }
+        if !nodes.is_empty() {
+            *stored = Some((parent_hash, nodes));
+        }
+    }
+
+    /// Takes the proofs stored for the given parent block.
+    ///
+    /// Proofs stored for another parent are dropped, since the chain has moved on.
+    fn take(&self, parent_hash: B256) -> Option<DecodedMultiProofV2> {
+        self.0.lock().take().and_then(|(hash, nodes)| (hash == parent_hash).then_some(nodes))
+    }
+}
+
+/// Proof targets fetched by the task that produced the preserved sparse trie, keyed by the state
+/// root of that trie.
+///
+/// The next task reusing the preserved trie is seeded with them, see
+/// [`StateRootComputeOutcome::fetched_proof_targets`].
+#[derive(Debug, Default, Clone)]
+struct PreservedProofTargets(Arc<Mutex<Option<(B256, Arc<FetchedProofTargets>)>>>);
+
+impl PreservedProofTargets {
+    /// Replaces the targets of the preserved trie.
+    fn set(&self, targets: Option<(B256, Arc<FetchedProofTargets>)>) {
+        *self.0.lock() = targets;
+    }
+
+    /// Takes the targets if they belong to a trie with the given state root.
+    fn take(&self, state_root: B256) -> Option<Arc<FetchedProofTargets>> {
+        self.0.lock().take().and_then(|(root, targets)| (root == state_root).then_some(targets))
+    }
+}
+
 struct SparseTrieTaskOptions<N: NodePrimitives> {
     parent_header: SealedHeader<N::BlockHeader>,
     preserved_sparse_trie: Option<PreservedSparseTrie>,
     chunk_size: usize,
+    /// Expected number of state updates, used to pre-size the task's per-block maps.
+    expected_updates: Option<usize>,
+    /// How long prefetch proof targets are buffered before being dispatched.
+    prefetch_coalesce_window: Duration,
+    /// Maximum number of state updates merged before they are applied, `0` for no limit.
+    state_update_coalesce_count: usize,
+    /// How long state updates are merged before they are applied, zero for no limit.
+    state_update_coalesce_window: Duration,
+    /// How long to wait for a message while proofs are in flight, `None` to wait forever.
+    proof_result_timeout: Option<Duration>,
+    /// Maximum number of proof batches in flight at once, `None` for no limit.
+    max_concurrent_proof_batches: Option<usize>,
+    /// Whether storage trie updates are taken as soon as the storage roots are calculated.
+    incremental_trie_updates: bool,
+    /// Load counters the task publishes its proof queue depth to.
+    load: StateRootTaskLoad,
     /// `None` disables pruning. `Some(Vec::new())` prunes nodes older than the current block.
     pending_sparse_trie_prune_blocks: Option<Vec<ExecutedBlock<N>>>,
+    /// Trie nodes of the parent state revealed before the first state update, skipping their
+    /// proof fetches.
+    pre_revealed_nodes: Option<DecodedMultiProofV2>,
+    /// Hashed addresses of accounts whose subtree root is checked before the task runs.
+    #[cfg(feature = "trie-debug")]
+    subtree_check_accounts: Vec<B256>,
 }
 
 struct StateRootTaskOptions<'a, N: NodePrimitives> {
@@ -1084,9 +1245,21 @@ where
             state_root,
             trie_updates,
             hashed_state: _hashed_state,
+            total_time,
+            proof_time,
+            sparse_trie_time,
+            fetched_proof_targets,
             #[cfg(feature = "trie-debug")]
             debug_recorders,
         } = outcome;
+        debug!(
+            target: "engine::tree::state_root_strategy",
+            ?total_time,
+            ?proof_time,
+            ?sparse_trie_time,
+            "State root task finished"
+        );
+        self.metrics.record_fetched_proof_targets(&fetched_proof_targets);
 
         if self.compare_trie_updates {
             let _has_diff = compare_trie_updates_with_serial(
@@ -1269,6 +1442,35 @@ where
 
 /// Writes trie debug recorders to a JSON file for the given block number.
 ///
+/// Recomputes the root of the parent state trie from a single multiproof for the given accounts
+/// and warns if it does not match the parent state root.
+#[cfg(feature = "trie-debug")]
+fn check_parent_subtree_root(
+    task: &mut SparseTrieCacheTask,
+    accounts: Vec<B256>,
+    parent_state_root: B256,
+) {
+    let targets = MultiProofTargetsV2 {
+        account_targets: accounts.into_iter().map(Into::into).collect(),
+        ..Default::default()
+    };
+    match task.compute_subtree(targets) {
+        Ok((root, proof)) if root != parent_state_root => warn!(
+            target: "engine::tree::payload_processor",
+            ?root,
+            ?parent_state_root,
+            proof_nodes = proof.account_proofs.len(),
+            "Subtree root does not match the parent state root"
+        ),
+        Ok(_) => {}
+        Err(err) => warn!(
+            target: "engine::tree::payload_processor",
+            %err,
+            "Failed to compute subtree root"
+        ),
+    }
+}
+
 /// The file is written to the current working directory as `trie_debug_block_{block_number}.json`.
 #[cfg(feature = "trie-debug")]
 fn write_trie_debug_recorders(block_number: u64, recorders: &[(Option<B256>, TrieDebugRecorder)]) {
@@ -1324,6 +1526,50 @@ mod tests {
     use reth_trie_db::ChangesetCache;
     use revm::state::{AccountInfo, AccountStatus, EvmState, EvmStorageSlot, TransactionId};
 
+    #[test]
+    fn unrevealed_proofs_are_taken_only_for_their_parent() {
+        let proof = || DecodedMultiProofV2 {
+            account_proofs: vec![reth_trie::ProofTrieNodeV2::empty()],
+            ..Default::default()
+        };
+        let parent = B256::repeat_byte(0x01);
+        let unrevealed_proofs = UnrevealedProofs::default();
+
+        unrevealed_proofs.store(parent, [proof()]);
+        unrevealed_proofs.store(parent, [proof(), DecodedMultiProofV2::default()]);
+        assert_eq!(unrevealed_proofs.take(parent).map(|nodes| nodes.account_proofs.len()), Some(2));
+        assert!(unrevealed_proofs.take(parent).is_none());
+
+        unrevealed_proofs.store(parent, [proof()]);
+        unrevealed_proofs.store(B256::repeat_byte(0x02), [proof()]);
+        assert!(unrevealed_proofs.take(parent).is_none());
+
+        unrevealed_proofs.store(parent, [proof()]);
+        assert!(unrevealed_proofs.take(B256::repeat_byte(0x02)).is_none());
+        assert!(unrevealed_proofs.take(parent).is_none(), "proofs of a stale parent are dropped");
+    }
+
+    #[test]
+    fn preserved_proof_targets_are_taken_only_for_their_state_root() {
+        let state_root = B256::repeat_byte(0x01);
+        let preserved_proof_targets = PreservedProofTargets::default();
+
+        preserved_proof_targets.set(Some((state_root, Arc::default())));
+        assert!(preserved_proof_targets.take(B256::repeat_byte(0x02)).is_none());
+        assert!(
+            preserved_proof_targets.take(state_root).is_none(),
+            "mismatched targets are dropped"
+        );
+
+        preserved_proof_targets.set(Some((state_root, Arc::default())));
+        assert!(preserved_proof_targets.take(state_root).is_some());
+        assert!(preserved_proof_targets.take(state_root).is_none());
+
+        preserved_proof_targets.set(Some((state_root, Arc::default())));
+        preserved_proof_targets.set(None);
+        assert!(preserved_proof_targets.take(state_root).is_none());
+    }
+
     #[test]
     fn sparse_trie_prune_before_uses_requested_range() {
         let new_epoch = TrieNodeEpoch::new(10);
@@ -15,8 +15,8 @@ use reth_metrics::Metrics;
 use reth_primitives_traits::{Account, FastInstant as Instant};
 use reth_tasks::Runtime;
 use reth_trie::{
-    updates::TrieUpdates, DecodedMultiProofV2, HashedPostState, TrieAccount, EMPTY_ROOT_HASH,
-    TRIE_ACCOUNT_RLP_MAX_SIZE,
+    updates::{StorageTrieUpdates, TrieUpdates},
+    DecodedMultiProofV2, HashedPostState, TrieAccount, EMPTY_ROOT_HASH, TRIE_ACCOUNT_RLP_MAX_SIZE,
 };
 use reth_trie_common::{MultiProofTargetsV2, ProofV2Target, ProofV2TargetParent};
 use reth_trie_parallel::{
@@ -24,6 +24,7 @@ use reth_trie_parallel::{
     proof_task::{
         AccountMultiproofInput, ProofResultContext, ProofResultMessage, ProofWorkerHandle,
     },
+    state_root_task::{FetchedProofTargets, StateRootTaskLoad},
 };
 use reth_trie_sparse::{
     errors::{SparseStateTrieErrorKind, SparseTrieErrorKind, SparseTrieResult},
@@ -105,10 +106,39 @@ pub(super) struct SparseTrieCacheTask<A = ArenaParallelSparseTrie, S = ArenaPara
     storage_cache_hits: u64,
     /// Accumulated storage leaf update cache misses.
     storage_cache_misses: u64,
+    /// Accumulated dispatch-to-completion time of received proofs.
+    proof_time: std::time::Duration,
+    /// Accumulated time spent revealing proofs and updating the sparse trie.
+    sparse_trie_time: std::time::Duration,
     /// Pending proof targets queued for dispatch to proof workers.
     pending_targets: PendingTargets,
+    /// How long prefetch proof targets are held back before being dispatched.
+    prefetch_coalesce_window: std::time::Duration,
+    /// When the currently open prefetch coalescing window started, if any.
+    ///
+    /// While the window is open, pending targets are not dispatched so that bursts of prefetch
+    /// hints are merged into fewer multiproofs.
+    prefetch_window_start: Option<Instant>,
+    /// Maximum number of state updates merged before they are applied to the trie, `0` for no
+    /// limit.
+    state_update_coalesce_count: usize,
+    /// How long state updates are merged before they are applied to the trie,
+    /// `Duration::ZERO` for no limit.
+    state_update_coalesce_window: std::time::Duration,
+    /// When the first state update of the currently merged batch was received, if coalescing is
+    /// enabled and updates are being merged.
+    state_update_window_start: Option<Instant>,
     /// Proof batches dispatched to workers and not yet received.
     in_flight_proof_batches: usize,
+    /// Maximum number of proof batches in flight at once, `None` for no limit.
+    ///
+    /// Pending targets are held back while the limit is reached.
+    max_concurrent_proof_batches: Option<usize>,
+    /// How long to wait for any message while proof batches are in flight before failing, `None`
+    /// to wait forever.
+    proof_result_timeout: Option<std::time::Duration>,
+    /// Load counters shared with the [`StateRootHandle`](super::StateRootHandle).
+    load: StateRootTaskLoad,
     /// Number of pending execution/prewarming updates received but not yet passed to
     /// `update_leaves`.
     pending_updates: usize,
@@ -118,9 +148,20 @@ pub(super) struct SparseTrieCacheTask<A = ArenaParallelSparseTrie, S = ArenaPara
     /// final [`HashedPostState`] and share it with main engine thread without requiring any extra
     /// hashing work.
     final_hashed_state: HashedPostState,
+    /// Whether storage trie updates are taken from the trie as soon as the storage root is
+    /// calculated.
+    incremental_trie_updates: bool,
+    /// Storage trie updates taken from the trie before the state root was calculated.
+    ///
+    /// Only populated if `incremental_trie_updates` is enabled.
+    flushed_storage_trie_updates: B256Map<StorageTrieUpdates>,
 
     /// Metrics for the sparse trie.
     metrics: SparseTrieTaskMetrics,
+    /// Receives the results of dispatched proofs in place of the task, so tests can feed them
+    /// back in a chosen order.
+    #[cfg(test)]
+    proof_result_interceptor: Option<CrossbeamSender<ProofResultMessage>>,
 }
 
 impl<A, S> SparseTrieCacheTask<A, S>
@@ -177,14 +218,138 @@ where
             account_cache_misses: 0,
             storage_cache_hits: 0,
             storage_cache_misses: 0,
+            proof_time: std::time::Duration::ZERO,
+            sparse_trie_time: std::time::Duration::ZERO,
             pending_targets: Default::default(),
+            prefetch_coalesce_window: std::time::Duration::ZERO,
+            prefetch_window_start: None,
+            state_update_coalesce_count: 0,
+            state_update_coalesce_window: std::time::Duration::ZERO,
+            state_update_window_start: None,
             in_flight_proof_batches: 0,
+            max_concurrent_proof_batches: None,
+            proof_result_timeout: None,
+            load: StateRootTaskLoad::default(),
             pending_updates: Default::default(),
             final_hashed_state: Default::default(),
+            incremental_trie_updates: false,
+            flushed_storage_trie_updates: Default::default(),
             metrics,
+            #[cfg(test)]
+            proof_result_interceptor: None,
         }
     }
 
+    /// Pre-sizes the per-block account maps for the expected number of state updates.
+    ///
+    /// Every transaction touches at least its sender, so the block's transaction count is a
+    /// cheap lower bound that avoids repeated rehashing while the first updates stream in.
+    pub(super) fn with_expected_updates(mut self, expected_updates: usize) -> Self {
+        self.account_updates.reserve(expected_updates);
+        self.new_account_updates.reserve(expected_updates);
+        self.fetched_account_targets.reserve(expected_updates);
+        self.final_hashed_state.accounts.reserve(expected_updates);
+        self.pending_targets.reserve_accounts(expected_updates);
+        self
+    }
+
+    /// Seeds the task with proof targets fetched by a previous task, so they are not requested
+    /// again.
+    ///
+    /// The previous task's proofs must already be revealed in the trie this task was created
+    /// with, see [`FetchedProofTargets`] for when a carried-over set is valid.
+    pub(super) fn with_fetched_proof_targets(mut self, targets: FetchedProofTargets) -> Self {
+        let FetchedProofTargets { accounts, storages } = targets;
+        self.fetched_account_targets.extend(accounts);
+        for (account, slots) in storages {
+            self.fetched_storage_targets.entry(account).or_default().extend(slots);
+        }
+        self
+    }
+
+    /// Sets how long prefetch proof targets are buffered before being dispatched.
+    ///
+    /// A state update or the end of the update stream flushes the buffered targets early.
+    pub(super) const fn with_prefetch_coalesce_window(
+        mut self,
+        window: std::time::Duration,
+    ) -> Self {
+        self.prefetch_coalesce_window = window;
+        self
+    }
+
+    /// Sets how many state updates are merged, and for how long, before they are applied to the
+    /// trie.
+    ///
+    /// Consecutive updates are merged into the same leaf update maps in the order they were
+    /// received, so the result is the same as applying them one by one, but overlapping accounts
+    /// and slots only need one proof fetch. This trades latency for throughput and is disabled if
+    /// both limits are zero. The end of the update stream applies the merged updates early.
+    pub(super) const fn with_state_update_coalescing(
+        mut self,
+        count: usize,
+        window: std::time::Duration,
+    ) -> Self {
+        self.state_update_coalesce_count = count;
+        self.state_update_coalesce_window = window;
+        self
+    }
+
+    /// Sets whether storage trie updates are taken from the trie as soon as the storage root is
+    /// calculated.
+    ///
+    /// This keeps the update buffers of the sparse storage tries small for blocks with a lot of
+    /// storage churn. The updates are merged into the [`TrieUpdates`] of the outcome, so the
+    /// result is the same as with a single extraction after the state root.
+    pub(super) const fn with_incremental_trie_updates(mut self, enabled: bool) -> Self {
+        self.incremental_trie_updates = enabled;
+        self
+    }
+
+    /// Sets how long the task waits for any message while proof batches are in flight.
+    ///
+    /// A proof job that dies without sending its result would otherwise leave the task waiting
+    /// forever, this turns the hang into an error.
+    pub(super) const fn with_proof_result_timeout(
+        mut self,
+        timeout: Option<std::time::Duration>,
+    ) -> Self {
+        self.proof_result_timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum number of proof batches this task has in flight at once.
+    ///
+    /// This bounds the task's share of the proof worker pool, so several tasks can run
+    /// concurrently without starving each other. A limit of `0` is treated as `1`.
+    pub(super) fn with_max_concurrent_proof_batches(mut self, max: Option<usize>) -> Self {
+        self.max_concurrent_proof_batches = max.map(|max| max.max(1));
+        self
+    }
+
+    /// Sets the load counters this task publishes its proof queue depth to.
+    pub(super) fn with_load(mut self, load: StateRootTaskLoad) -> Self {
+        self.load = load;
+        self
+    }
+
+    /// Reveals nodes that were fetched ahead of time before any state update is processed.
+    ///
+    /// Leaf updates that land on these nodes no longer need a multiproof, so the task skips the
+    /// corresponding proof fetches. The nodes must belong to the trie of the parent state the task
+    /// computes the root on top of, otherwise the resulting state root is wrong.
+    pub(super) fn reveal_pre_revealed_nodes(
+        &mut self,
+        nodes: DecodedMultiProofV2,
+    ) -> Result<(), StateRootTaskError> {
+        self.on_proof_result(nodes)
+    }
+
+    /// Publishes the current number of in-flight proof batches and pending proof targets.
+    fn publish_load(&self) {
+        self.load.update(self.in_flight_proof_batches, self.pending_targets.len());
+    }
+
     /// Runs the hashing task that drains updates from the channel and converts them to
     /// `HashedPostState` in parallel.
     fn run_hashing_task(
@@ -244,6 +409,63 @@ where
         (trie, deferred)
     }
 
+    /// Returns the proofs that were computed but not yet revealed in the trie, in the order they
+    /// were received, together with the state that triggered them.
+    ///
+    /// Use this after the task was cancelled or failed to inspect or re-feed finished proofs.
+    /// Proofs that are still being computed and failed proofs are not included.
+    pub(super) fn drain_pending_proofs(&mut self) -> Vec<(HashedPostState, DecodedMultiProofV2)> {
+        self.proof_result_rx
+            .try_iter()
+            .filter_map(|message| Some((message.state, message.result.ok()?)))
+            .collect()
+    }
+
+    /// Fetches a single multiproof for the given targets and reveals it in the trie, bypassing the
+    /// state update loop of [`Self::run`].
+    ///
+    /// Returns the root of the partially revealed trie together with the revealed proof nodes.
+    /// This is a diagnostic for inspecting trie inconsistencies around a subset of accounts and
+    /// slots. Proofs are fetched for the parent state, so it must be called before [`Self::run`]
+    /// has applied any state updates.
+    #[cfg(feature = "trie-debug")]
+    pub(super) fn compute_subtree(
+        &mut self,
+        targets: MultiProofTargetsV2,
+    ) -> Result<(B256, DecodedMultiProofV2), StateRootTaskError> {
+        if self.in_flight_proof_batches > 0 {
+            return Err(StateRootTaskError::Other(
+                "can't compute a subtree while proofs are in flight".to_string(),
+            ))
+        }
+
+        let mut proof = DecodedMultiProofV2::default();
+        if !targets.is_empty() {
+            self.proof_worker_handle
+                .dispatch_account_multiproof(AccountMultiproofInput {
+                    targets,
+                    proof_result_sender: ProofResultContext::new(
+                        self.proof_result_tx.clone(),
+                        HashedPostState::default(),
+                        Instant::now(),
+                    ),
+                })
+                .map_err(StateRootTaskError::ProofDispatch)?;
+            self.in_flight_proof_batches += 1;
+
+            let Ok(message) = self.proof_result_rx.recv() else {
+                unreachable!("we own the sender half")
+            };
+            proof = self.on_proof_result_message(message)?;
+            self.on_proof_result(proof.clone())?;
+        }
+
+        let root = self.trie.root(self.new_epoch).map_err(|err| {
+            StateRootTaskError::Other(format!("could not calculate subtree root: {err:?}"))
+        })?;
+        Ok((root, proof))
+    }
+
     /// Runs the sparse trie task to completion.
     ///
     /// This waits for new incoming [`SparseTrieTaskMessage`]s, applies updates
@@ -299,9 +521,17 @@ where
                     self.on_proof_results(result, &mut t)?;
                 },
                 recv(self.cancel_rx) -> _ => return Err(StateRootTaskError::Canceled),
+                recv(self.prefetch_window_timer()) -> _ => {
+                    total_idle_time += idle_start.elapsed();
+                },
+                recv(self.state_update_window_timer()) -> _ => {
+                    total_idle_time += idle_start.elapsed();
+                },
+                recv(self.proof_result_timer()) -> _ => return Err(self.proof_result_timed_out()),
             }
 
             done = self.make_progress()?;
+            self.publish_load();
             idle_start = Instant::now();
         }
 
@@ -326,18 +556,87 @@ where
                     self.on_proof_results(result, &mut t)?;
                 },
                 recv(self.cancel_rx) -> _ => return Err(StateRootTaskError::Canceled),
+                recv(self.proof_result_timer()) -> _ => return Err(self.proof_result_timed_out()),
             }
 
             done = self.make_progress()?;
+            self.publish_load();
             idle_start = Instant::now();
         }
 
         self.metrics.sparse_trie_idle_time_seconds.record(total_idle_time.as_secs_f64());
 
+        self.finish(
+            now,
+            finalized_hashed_state.expect("finished state updates publish the hashed post state"),
+        )
+    }
+
+    /// Computes the state root for a fixed set of state updates on the current thread, bypassing
+    /// the update channel and the hashing task.
+    ///
+    /// Every update is hashed inline and applied together with all proofs it requires before the
+    /// next one is processed, so the trie operations happen in a deterministic order. Proofs are
+    /// still computed by the proof workers. Meant for testing the update sequencing and the end
+    /// condition of [`Self::run`], it must not be combined with messages sent on the task's
+    /// update channel or with a proof result interceptor.
+    #[cfg(test)]
+    pub(super) fn run_blocking(
+        &mut self,
+        updates: Vec<revm::state::EvmState>,
+    ) -> Result<StateRootComputeOutcome, StateRootTaskError> {
+        let now = Instant::now();
+
+        for update in updates {
+            let hashed_state = evm_state_to_hashed_post_state(update);
+            self.on_message(SparseTrieTaskMessage::HashedState(hashed_state));
+            self.pending_updates += 1;
+            self.progress_blocking()?;
+        }
+
+        let hashed_state = self
+            .on_message(SparseTrieTaskMessage::FinishedStateUpdates)
+            .expect("finished state updates publish the hashed post state");
+        self.pending_updates += 1;
+        if !self.progress_blocking()? {
+            return Err(StateRootTaskError::Stalled)
+        }
+
+        self.finish(now, hashed_state)
+    }
+
+    /// Makes progress on the buffered updates, waiting for every dispatched proof batch, until no
+    /// proof batch is in flight anymore.
+    ///
+    /// Returns `true` once the finish marker was received and all pending trie work is done.
+    #[cfg(test)]
+    fn progress_blocking(&mut self) -> Result<bool, StateRootTaskError> {
+        loop {
+            if self.make_progress()? {
+                return Ok(true)
+            }
+            if self.in_flight_proof_batches == 0 && self.proof_result_rx.is_empty() {
+                return Ok(false)
+            }
+
+            let Ok(message) = self.proof_result_rx.recv() else {
+                unreachable!("we own the sender half")
+            };
+            self.on_proof_results(message, &mut Instant::now())?;
+        }
+    }
+
+    /// Calculates the state root once all updates and proofs were processed and assembles the
+    /// outcome of the task that started at `started_at`.
+    fn finish(
+        &mut self,
+        started_at: Instant,
+        hashed_state: Arc<HashedPostState>,
+    ) -> Result<StateRootComputeOutcome, StateRootTaskError> {
         debug!(target: "engine::root", "All proofs processed, ending calculation");
 
         let start = Instant::now();
-        let (state_root, trie_updates) = match self.trie.root_with_updates(self.new_epoch) {
+        let (state_root, mut trie_updates) = match self.trie.root_with_updates(self.new_epoch) {
             Ok(result) => result,
             Err(err)
                 if matches!(
@@ -357,12 +656,22 @@ where
             }
         };
 
+        if !self.flushed_storage_trie_updates.is_empty() {
+            let mut flushed = TrieUpdates {
+                storage_tries: core::mem::take(&mut self.flushed_storage_trie_updates),
+                ..Default::default()
+            };
+            flushed.extend(trie_updates);
+            trie_updates = flushed;
+        }
+
         #[cfg(feature = "trie-debug")]
         let debug_recorders = self.trie.take_debug_recorders();
 
         let end = Instant::now();
         self.metrics.sparse_trie_final_update_duration_histogram.record(end.duration_since(start));
-        self.metrics.sparse_trie_total_duration_histogram.record(end.duration_since(now));
+        self.sparse_trie_time += end.duration_since(start);
+        self.metrics.sparse_trie_total_duration_histogram.record(end.duration_since(started_at));
 
         self.metrics.sparse_trie_account_cache_hits.record(self.account_cache_hits as f64);
         self.metrics.sparse_trie_account_cache_misses.record(self.account_cache_misses as f64);
@@ -376,8 +685,14 @@ where
         Ok(StateRootComputeOutcome {
             state_root,
             trie_updates: Arc::new(trie_updates),
-            hashed_state: finalized_hashed_state
-                .expect("finished state updates publish the hashed post state"),
+            hashed_state,
+            total_time: end.duration_since(started_at),
+            proof_time: core::mem::take(&mut self.proof_time),
+            sparse_trie_time: core::mem::take(&mut self.sparse_trie_time),
+            fetched_proof_targets: Arc::new(FetchedProofTargets {
+                accounts: core::mem::take(&mut self.fetched_account_targets),
+                storages: core::mem::take(&mut self.fetched_storage_targets),
+            }),
             #[cfg(feature = "trie-debug")]
             debug_recorders,
         })
@@ -403,7 +718,9 @@ where
         *t = phase_end;
 
         self.on_proof_result(result)?;
-        self.metrics.sparse_trie_reveal_multiproof_duration_histogram.record(t.elapsed());
+        let elapsed = t.elapsed();
+        self.metrics.sparse_trie_reveal_multiproof_duration_histogram.record(elapsed);
+        self.sparse_trie_time += elapsed;
         Ok(())
     }
 
@@ -421,7 +738,7 @@ where
             let t = Instant::now();
             self.process_new_updates()?;
             self.promote_pending_account_updates()?;
-            self.metrics.sparse_trie_process_updates_duration_histogram.record(t.elapsed());
+            self.record_process_updates_duration(t.elapsed());
 
             if self.finished_state_updates && !self.has_pending_sparse_trie_updates() {
                 return Ok(true);
@@ -439,7 +756,7 @@ where
             // If we don't have any pending updates, apply them to the trie,
             let t = Instant::now();
             self.process_new_updates()?;
-            self.metrics.sparse_trie_process_updates_duration_histogram.record(t.elapsed());
+            self.record_process_updates_duration(t.elapsed());
             self.dispatch_pending_targets()?;
         } else if self.pending_targets.len() > self.chunk_size {
             // Make sure to dispatch targets if we've accumulated a lot of them.
@@ -448,18 +765,33 @@ where
         Ok(false)
     }
 
+    fn record_process_updates_duration(&mut self, elapsed: std::time::Duration) {
+        self.metrics.sparse_trie_process_updates_duration_histogram.record(elapsed);
+        self.sparse_trie_time += elapsed;
+    }
+
     /// Processes a [`SparseTrieTaskMessage`] from the hashing task.
     fn on_message(&mut self, message: SparseTrieTaskMessage) -> Option<Arc<HashedPostState>> {
         match message {
             SparseTrieTaskMessage::PrefetchProofs(targets) => {
+                if !self.prefetch_coalesce_window.is_zero() {
+                    self.prefetch_window_start.get_or_insert_with(Instant::now);
+                }
                 self.on_prewarm_targets(targets);
                 None
             }
             SparseTrieTaskMessage::HashedState(hashed_state) => {
+                // Real state changes should not wait for the coalescing window.
+                self.prefetch_window_start = None;
+                if self.is_state_update_coalescing_enabled() {
+                    self.state_update_window_start.get_or_insert_with(Instant::now);
+                }
                 self.on_hashed_state_update(hashed_state);
                 None
             }
             SparseTrieTaskMessage::FinishedStateUpdates => {
+                self.prefetch_window_start = None;
+                self.state_update_window_start = None;
                 let hashed_state = Arc::new(core::mem::take(&mut self.final_hashed_state));
                 let _ = self.final_hashed_state_tx.take().unwrap().send(Arc::clone(&hashed_state));
                 self.finished_state_updates = true;
@@ -562,13 +894,15 @@ where
             "received proof result without an in-flight proof batch"
         );
         self.in_flight_proof_batches = self.in_flight_proof_batches.saturating_sub(1);
+        self.proof_time += message.elapsed;
         message.result
     }
 
     fn process_new_updates(&mut self) -> SparseTrieResult<()> {
-        if self.pending_updates == 0 {
+        if self.pending_updates == 0 || self.is_coalescing_state_updates() {
             return Ok(());
         }
+        self.state_update_window_start = None;
 
         let _span = debug_span!("process_new_updates").entered();
         self.pending_updates = 0;
@@ -714,7 +1048,10 @@ where
     /// 3. but the storage root hasn't been updated yet,
     ///
     /// we trigger state root computation on a rayon pool.
-    fn compute_drained_storage_roots(&mut self) {
+    ///
+    /// Returns [`StateRootTaskError::BlindStorageTrie`] with the hashed address of a storage trie
+    /// that is still blind even though all of its updates were drained.
+    fn compute_drained_storage_roots(&mut self) -> Result<(), StateRootTaskError> {
         let addresses_to_compute_roots: Vec<_> = self
             .storage_updates
             .iter()
@@ -737,40 +1074,53 @@ where
         }
 
         if tries_to_compute_roots.is_empty() {
-            return;
+            return Ok(());
         }
 
+        let computed_addresses = self.incremental_trie_updates.then(|| {
+            tries_to_compute_roots.iter().map(|(address, _)| *address).collect::<Vec<_>>()
+        });
+
         let parent_span =
             debug_span!("compute_drained_storage_roots", n = tries_to_compute_roots.len());
         let new_epoch = self.new_epoch;
-        tries_to_compute_roots.into_par_iter().for_each(|(address, SendStorageTriePtr(trie))| {
-            let span = if tracing::enabled!(tracing::Level::TRACE) {
-                debug_span!(
-                    target: "engine::tree::payload_processor::sparse_trie",
-                    parent: &parent_span,
-                    "storage_root",
-                    ?address
-                )
-            } else {
-                debug_span!(
-                    target: "engine::tree::payload_processor::sparse_trie",
-                    parent: &parent_span,
-                    "storage_root",
-                )
-            };
-            let _enter = span.entered();
-            // SAFETY:
-            // - pointers are created from `storage_tries_mut().get_mut(address)` above;
-            // - `addresses_to_compute_roots` comes from map iteration, so addresses are unique;
-            // - we do not insert/remove entries between pointer collection and use, so pointers
-            //   stay valid and map reallocation cannot occur;
-            // - each pointer is consumed by at most one rayon task, so no aliasing mutable access.
-            unsafe {
-                (*trie)
-                    .root(new_epoch)
-                    .expect("updates are drained, trie should be revealed by now")
-            };
-        });
+        tries_to_compute_roots.into_par_iter().try_for_each(
+            |(address, SendStorageTriePtr(trie))| {
+                let span = if tracing::enabled!(tracing::Level::TRACE) {
+                    debug_span!(
+                        target: "engine::tree::payload_processor::sparse_trie",
+                        parent: &parent_span,
+                        "storage_root",
+                        ?address
+                    )
+                } else {
+                    debug_span!(
+                        target: "engine::tree::payload_processor::sparse_trie",
+                        parent: &parent_span,
+                        "storage_root",
+                    )
+                };
+                let _enter = span.entered();
+                // SAFETY:
+                // - pointers are created from `storage_tries_mut().get_mut(address)` above;
+                // - `addresses_to_compute_roots` comes from map iteration, so addresses are unique;
+                // - we do not insert/remove entries between pointer collection and use, so pointers
+                //   stay valid and map reallocation cannot occur;
+                // - each pointer is consumed by at most one rayon task, so no aliasing mutable
+                //   access.
+                unsafe { (*trie).root(new_epoch) }
+                    .map(|_| ())
+                    .ok_or(StateRootTaskError::BlindStorageTrie(address))
+            },
+        )?;
+
+        if let Some(addresses) = computed_addresses {
+            for (address, updates) in self.trie.take_finalized_storage_trie_updates(addresses) {
+                self.flushed_storage_trie_updates.entry(address).or_default().extend(updates);
+            }
+        }
+
+        Ok(())
     }
 
     /// Iterates through all storage tries for which all updates were processed, computes their
@@ -781,14 +1131,14 @@ where
         target = "engine::tree::payload_processor::sparse_trie",
         skip_all
     )]
-    fn promote_pending_account_updates(&mut self) -> SparseTrieResult<()> {
+    fn promote_pending_account_updates(&mut self) -> Result<(), StateRootTaskError> {
         self.process_leaf_updates(false)?;
 
         if self.pending_account_updates.is_empty() {
             return Ok(());
         }
 
-        self.compute_drained_storage_roots();
+        self.compute_drained_storage_roots()?;
 
         loop {
             let span = trace_span!("promote_updates", promoted = tracing::field::Empty).entered();
@@ -854,18 +1204,106 @@ where
         Ok(())
     }
 
+    /// Returns `true` while the prefetch coalescing window is open, closing it once it elapsed.
+    fn is_coalescing_prefetch(&mut self) -> bool {
+        match self.prefetch_window_start {
+            Some(start) if start.elapsed() < self.prefetch_coalesce_window => true,
+            Some(_) => {
+                self.prefetch_window_start = None;
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if state updates are merged before being applied to the trie.
+    const fn is_state_update_coalescing_enabled(&self) -> bool {
+        self.state_update_coalesce_count > 0 || !self.state_update_coalesce_window.is_zero()
+    }
+
+    /// Returns `true` while received state updates are held back to be merged with the next
+    /// ones, i.e. neither the count nor the time limit of the current batch was reached.
+    fn is_coalescing_state_updates(&self) -> bool {
+        let Some(start) = self.state_update_window_start else { return false };
+        !self.finished_state_updates &&
+            (self.state_update_coalesce_count == 0 ||
+                self.pending_updates < self.state_update_coalesce_count) &&
+            (self.state_update_coalesce_window.is_zero() ||
+                start.elapsed() < self.state_update_coalesce_window)
+    }
+
+    /// Returns a channel that fires once the current state update batch must be applied, or
+    /// never if no batch is open or it has no time limit.
+    fn state_update_window_timer(&self) -> CrossbeamReceiver<std::time::Instant> {
+        match self.state_update_window_start {
+            Some(start) if !self.state_update_coalesce_window.is_zero() => {
+                crossbeam_channel::after(
+                    self.state_update_coalesce_window.saturating_sub(start.elapsed()),
+                )
+            }
+            _ => crossbeam_channel::never(),
+        }
+    }
+
+    /// Returns the sender dispatched proofs report their results to.
+    fn proof_result_sender(&self) -> CrossbeamSender<ProofResultMessage> {
+        #[cfg(test)]
+        if let Some(interceptor) = &self.proof_result_interceptor {
+            return interceptor.clone()
+        }
+        self.proof_result_tx.clone()
+    }
+
+    /// Returns a channel that fires once the proof result timeout elapses, or never if no proof
+    /// batch is in flight or the timeout is disabled.
+    fn proof_result_timer(&self) -> CrossbeamReceiver<std::time::Instant> {
+        match self.proof_result_timeout {
+            Some(timeout) if self.in_flight_proof_batches > 0 => crossbeam_channel::after(timeout),
+            _ => crossbeam_channel::never(),
+        }
+    }
+
+    /// Returns the error for a proof result that did not arrive within the timeout.
+    fn proof_result_timed_out(&self) -> StateRootTaskError {
+        StateRootTaskError::Other(format!(
+            "sparse trie task stalled waiting for {} in-flight proof batches for {:?}",
+            self.in_flight_proof_batches,
+            self.proof_result_timeout.unwrap_or_default(),
+        ))
+    }
+
+    /// Returns a channel that fires once the open prefetch coalescing window elapses, or never
+    /// if no window is open.
+    fn prefetch_window_timer(&self) -> CrossbeamReceiver<std::time::Instant> {
+        match self.prefetch_window_start {
+            Some(start) => crossbeam_channel::after(
+                self.prefetch_coalesce_window.saturating_sub(start.elapsed()),
+            ),
+            None => crossbeam_channel::never(),
+        }
+    }
+
     fn dispatch_pending_targets(&mut self) -> Result<(), StateRootTaskError> {
-        if self.pending_targets.is_empty() {
+        if self.pending_targets.is_empty() || self.is_coalescing_prefetch() {
             return Ok(())
         }
 
+        // with a concurrency limit, only as many chunks as there are free slots are dispatched
+        let chunk_size = match self.max_concurrent_proof_batches {
+            Some(max) if self.in_flight_proof_batches >= max => return Ok(()),
+            Some(max) => self
+                .chunk_size
+                .max(self.pending_targets.len().div_ceil(max - self.in_flight_proof_batches)),
+            None => self.chunk_size,
+        };
+
         let _span = trace_span!("dispatch_pending_targets").entered();
         let (targets, chunking_length) = self.pending_targets.take();
         let mut dispatch_error = None;
         dispatch_with_chunking(
             targets,
             chunking_length,
-            self.chunk_size,
+            chunk_size,
             self.max_targets_for_chunking,
             self.proof_worker_handle.has_multiple_idle_account_workers(),
             self.proof_worker_handle.has_multiple_idle_storage_workers(),
@@ -878,7 +1316,7 @@ where
                 match self.proof_worker_handle.dispatch_account_multiproof(AccountMultiproofInput {
                     targets: proof_targets,
                     proof_result_sender: ProofResultContext::new(
-                        self.proof_result_tx.clone(),
+                        self.proof_result_sender(),
                         HashedPostState::default(),
                         Instant::now(),
                     ),
@@ -1080,6 +1518,11 @@ impl PendingTargets {
         self.len == 0
     }
 
+    /// Reserves capacity for at least `additional` more account targets.
+    fn reserve_accounts(&mut self, additional: usize) {
+        self.targets.account_targets.reserve(additional);
+    }
+
     /// Takes the pending targets, replacing with empty defaults.
     fn take(&mut self) -> (MultiProofTargetsV2, usize) {
         (std::mem::take(&mut self.targets), std::mem::take(&mut self.len))
@@ -1121,6 +1564,46 @@ mod tests {
     use reth_trie_parallel::proof_task::ProofTaskCtx;
     use reth_trie_sparse::ArenaParallelSparseTrie;
 
+    /// Creates a task over an empty database with blind tries that retain trie updates.
+    fn test_task(
+        runtime: &Runtime,
+        updates_rx: CrossbeamReceiver<StateRootMessage>,
+        cancel_rx: CrossbeamReceiver<()>,
+        parent_state_root: B256,
+        chunk_size: usize,
+    ) -> SparseTrieCacheTask<ArenaParallelSparseTrie, ArenaParallelSparseTrie> {
+        let provider_factory = create_test_provider_factory();
+        let anchor_hash = provider_factory.chain_spec().genesis_hash();
+        let overlay_factory = OverlayStateProviderFactory::new(
+            provider_factory,
+            OverlayBuilder::<reth_chain_state::EthPrimitives>::new(
+                anchor_hash,
+                ChangesetCache::new(),
+            ),
+        );
+        let proof_worker_handle =
+            ProofWorkerHandle::new(runtime, ProofTaskCtx::new(overlay_factory), false);
+
+        let default_trie = RevealableSparseTrie::blind_from(ArenaParallelSparseTrie::default());
+        let trie = SparseStateTrie::default()
+            .with_accounts_trie(default_trie.clone())
+            .with_default_storage_trie(default_trie)
+            .with_updates(true);
+
+        SparseTrieCacheTask::new_with_trie(
+            runtime,
+            updates_rx,
+            cancel_rx,
+            std::sync::mpsc::channel().0,
+            proof_worker_handle,
+            SparseTrieTaskMetrics::default(),
+            trie,
+            parent_state_root,
+            TrieNodeEpoch::UNMODIFIED,
+            chunk_size,
+        )
+    }
+
     #[test]
     fn test_run_hashing_task_hashed_state_update_forwards() {
         let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
@@ -1203,39 +1686,80 @@ mod tests {
     #[test]
     fn run_returns_parent_root_without_revealing_blind_trie_when_no_state_updates() {
         let runtime = reth_tasks::Runtime::test();
-        let provider_factory = create_test_provider_factory();
-        let anchor_hash = provider_factory.chain_spec().genesis_hash();
-        let overlay_factory = OverlayStateProviderFactory::new(
-            provider_factory,
-            OverlayBuilder::<reth_chain_state::EthPrimitives>::new(
-                anchor_hash,
-                ChangesetCache::new(),
-            ),
-        );
-        let proof_worker_handle =
-            ProofWorkerHandle::new(&runtime, ProofTaskCtx::new(overlay_factory), false);
+        let parent_state_root = B256::from([0x55; 32]);
+        let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, parent_state_root, 1);
 
-        let default_trie = RevealableSparseTrie::blind_from(ArenaParallelSparseTrie::default());
-        let trie = SparseStateTrie::default()
-            .with_accounts_trie(default_trie.clone())
-            .with_default_storage_trie(default_trie)
-            .with_updates(true);
+        updates_tx.send(StateRootMessage::FinishedStateUpdates).unwrap();
+        drop(updates_tx);
+
+        let outcome = task.run().expect("state root computation should succeed");
+
+        assert_eq!(outcome.state_root, parent_state_root);
+        assert!(outcome.trie_updates.is_empty());
+        // no proofs were requested, and the trie work can never exceed the task's lifetime
+        assert!(outcome.proof_time.is_zero());
+        assert!(outcome.sparse_trie_time <= outcome.total_time);
+        assert!(task.trie.state_trie_ref().is_none(), "blind trie should not be revealed");
+    }
+
+    #[test]
+    fn run_returns_seeded_fetched_proof_targets() {
+        let runtime = reth_tasks::Runtime::test();
+
+        let mut seeded = FetchedProofTargets::default();
+        seeded.accounts.insert(B256::from([0x11; 32]), ProofV2TargetParent::NONE);
+        seeded
+            .storages
+            .entry(B256::from([0x11; 32]))
+            .or_default()
+            .insert(B256::from([0x22; 32]), ProofV2TargetParent::new(3));
+
+        let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::from([0x55; 32]), 1)
+            .with_fetched_proof_targets(seeded.clone());
+
+        updates_tx.send(StateRootMessage::FinishedStateUpdates).unwrap();
+        drop(updates_tx);
+
+        let outcome = task.run().expect("state root computation should succeed");
+
+        assert_eq!(*outcome.fetched_proof_targets, seeded);
+        assert!(task.fetched_account_targets.is_empty());
+        assert!(task.fetched_storage_targets.is_empty());
+    }
+
+    #[test]
+    fn blind_drained_storage_trie_reports_address() {
+        let runtime = reth_tasks::Runtime::test();
+        let (_updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::ZERO, 1);
 
+        let address = B256::from([0x42; 32]);
+        task.trie.get_or_create_storage_trie_mut(address);
+        task.storage_updates.insert(address, Default::default());
+
+        let error = task.compute_drained_storage_roots().unwrap_err();
+        assert!(matches!(error, StateRootTaskError::BlindStorageTrie(blind) if blind == address));
+    }
+
+    #[test]
+    fn run_with_expected_updates_returns_same_outcome() {
+        let runtime = reth_tasks::Runtime::test();
         let parent_state_root = B256::from([0x55; 32]);
         let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
         let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
-        let mut task = SparseTrieCacheTask::new_with_trie(
-            &runtime,
-            updates_rx,
-            cancel_rx,
-            std::sync::mpsc::channel().0,
-            proof_worker_handle,
-            SparseTrieTaskMetrics::default(),
-            trie,
-            parent_state_root,
-            TrieNodeEpoch::UNMODIFIED,
-            1,
-        );
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, parent_state_root, 1)
+            .with_expected_updates(128);
+
+        assert!(task.account_updates.capacity() >= 128);
+        assert!(task.fetched_account_targets.capacity() >= 128);
+        assert!(task.final_hashed_state.accounts.capacity() >= 128);
+        assert!(task.pending_targets.targets.account_targets.capacity() >= 128);
+        assert!(task.pending_targets.is_empty());
 
         updates_tx.send(StateRootMessage::FinishedStateUpdates).unwrap();
         drop(updates_tx);
@@ -1244,44 +1768,108 @@ mod tests {
 
         assert_eq!(outcome.state_root, parent_state_root);
         assert!(outcome.trie_updates.is_empty());
-        assert!(task.trie.state_trie_ref().is_none(), "blind trie should not be revealed");
     }
 
     #[test]
-    fn stall_check_waits_for_in_flight_proofs_then_reports_pending_updates() {
+    fn prefetch_targets_within_coalesce_window_are_dispatched_together() {
         let runtime = reth_tasks::Runtime::test();
-        let provider_factory = create_test_provider_factory();
-        let anchor_hash = provider_factory.chain_spec().genesis_hash();
-        let overlay_factory = OverlayStateProviderFactory::new(
-            provider_factory,
-            OverlayBuilder::<reth_chain_state::EthPrimitives>::new(
-                anchor_hash,
-                ChangesetCache::new(),
-            ),
-        );
-        let proof_worker_handle =
-            ProofWorkerHandle::new(&runtime, ProofTaskCtx::new(overlay_factory), false);
+        let (_updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::from([0x55; 32]), 1000)
+            .with_prefetch_coalesce_window(std::time::Duration::from_secs(3600));
 
-        let default_trie = RevealableSparseTrie::blind_from(ArenaParallelSparseTrie::default());
-        let trie = SparseStateTrie::default()
-            .with_accounts_trie(default_trie.clone())
-            .with_default_storage_trie(default_trie)
-            .with_updates(true);
+        for key in [0x11, 0x22, 0x33] {
+            let targets = MultiProofTargetsV2 {
+                account_targets: vec![ProofV2Target::new(B256::repeat_byte(key))],
+                ..Default::default()
+            };
+            task.on_message(SparseTrieTaskMessage::PrefetchProofs(targets));
+            task.pending_updates += 1;
+            task.make_progress().expect("progress should succeed");
+        }
+
+        // All prefetch targets are held back while the window is open.
+        assert_eq!(task.in_flight_proof_batches, 0);
+        assert!(!task.pending_targets.is_empty());
+
+        // A state update closes the window and flushes the buffered targets at once.
+        task.on_message(SparseTrieTaskMessage::HashedState(HashedPostState::default()));
+        task.pending_updates += 1;
+        task.make_progress().expect("progress should succeed");
 
+        assert_eq!(task.in_flight_proof_batches, 1);
+        assert!(task.pending_targets.is_empty());
+    }
+
+    #[test]
+    fn pending_targets_wait_for_free_proof_batch_slot() {
+        let runtime = reth_tasks::Runtime::test();
+        let (_updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::from([0x55; 32]), 1)
+            .with_max_concurrent_proof_batches(Some(1));
+        let (interceptor_tx, interceptor_rx) = crossbeam_channel::unbounded();
+        task.proof_result_interceptor = Some(interceptor_tx);
+
+        for key in [0x11, 0x22, 0x33] {
+            task.pending_targets.push_account_target(ProofV2Target::new(B256::repeat_byte(key)));
+        }
+        task.dispatch_pending_targets().expect("dispatch should succeed");
+        // all targets fit into the single free slot, even though they exceed the chunk size
+        assert_eq!(task.in_flight_proof_batches, 1);
+        assert!(task.pending_targets.is_empty());
+
+        task.pending_targets.push_account_target(ProofV2Target::new(B256::repeat_byte(0x44)));
+        task.dispatch_pending_targets().expect("dispatch should succeed");
+        assert_eq!(task.in_flight_proof_batches, 1);
+        assert_eq!(task.pending_targets.len(), 1);
+
+        let result = interceptor_rx
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .expect("proof should complete");
+        task.on_proof_result_message(result).expect("proof result should be ok");
+        task.dispatch_pending_targets().expect("dispatch should succeed");
+        assert_eq!(task.in_flight_proof_batches, 1);
+        assert!(task.pending_targets.is_empty());
+    }
+
+    #[test]
+    fn state_updates_are_coalesced_up_to_count() {
+        let runtime = reth_tasks::Runtime::test();
+        let (_updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::from([0x55; 32]), 1000)
+            .with_state_update_coalescing(3, std::time::Duration::ZERO);
+
+        for (i, key) in [0x11, 0x22, 0x33].into_iter().enumerate() {
+            let mut hashed_state = HashedPostState::default();
+            hashed_state.accounts.insert(
+                B256::repeat_byte(key),
+                Some(Account { balance: U256::from(key), nonce: 1, bytecode_hash: None }),
+            );
+            task.on_message(SparseTrieTaskMessage::HashedState(hashed_state));
+            task.pending_updates += 1;
+            task.make_progress().expect("progress should succeed");
+
+            if i < 2 {
+                // The first updates are merged instead of being applied one by one.
+                assert_eq!(task.pending_updates, i + 1);
+                assert_eq!(task.new_account_updates.len(), i + 1);
+            }
+        }
+
+        // Reaching the count applies the merged updates at once.
+        assert_eq!(task.pending_updates, 0);
+        assert!(task.new_account_updates.is_empty());
+        assert!(task.state_update_window_start.is_none());
+    }
+
+    #[test]
+    fn stall_check_waits_for_in_flight_proofs_then_reports_pending_updates() {
+        let runtime = reth_tasks::Runtime::test();
         let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
         let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
-        let mut task = SparseTrieCacheTask::new_with_trie(
-            &runtime,
-            updates_rx,
-            cancel_rx,
-            std::sync::mpsc::channel().0,
-            proof_worker_handle,
-            SparseTrieTaskMetrics::default(),
-            trie,
-            B256::from([0x55; 32]),
-            TrieNodeEpoch::UNMODIFIED,
-            1,
-        );
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::from([0x55; 32]), 1);
 
         drop(updates_tx);
 
@@ -1328,40 +1916,27 @@ mod tests {
     }
 
     #[test]
-    fn run_errors_when_cancel_guard_drops_before_updates_finish() {
+    fn run_errors_when_proof_result_times_out() {
         let runtime = reth_tasks::Runtime::test();
-        let provider_factory = create_test_provider_factory();
-        let anchor_hash = provider_factory.chain_spec().genesis_hash();
-        let overlay_factory = OverlayStateProviderFactory::new(
-            provider_factory,
-            OverlayBuilder::<reth_chain_state::EthPrimitives>::new(
-                anchor_hash,
-                ChangesetCache::new(),
-            ),
-        );
-        let proof_worker_handle =
-            ProofWorkerHandle::new(&runtime, ProofTaskCtx::new(overlay_factory), false);
+        let (_updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::from([0x55; 32]), 1)
+            .with_proof_result_timeout(Some(std::time::Duration::from_millis(10)));
 
-        let default_trie = RevealableSparseTrie::blind_from(ArenaParallelSparseTrie::default());
-        let trie = SparseStateTrie::default()
-            .with_accounts_trie(default_trie.clone())
-            .with_default_storage_trie(default_trie)
-            .with_updates(true);
+        // a proof batch that never reports back
+        task.finished_state_updates = true;
+        task.in_flight_proof_batches = 1;
+
+        let error = task.run().expect_err("lost proof should time out");
+        assert!(error.to_string().contains("stalled waiting for 1 in-flight proof batches"));
+    }
 
+    #[test]
+    fn run_errors_when_cancel_guard_drops_before_updates_finish() {
+        let runtime = reth_tasks::Runtime::test();
         let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
         let (cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
-        let mut task = SparseTrieCacheTask::new_with_trie(
-            &runtime,
-            updates_rx,
-            cancel_rx,
-            std::sync::mpsc::channel().0,
-            proof_worker_handle,
-            SparseTrieTaskMetrics::default(),
-            trie,
-            B256::from([0x55; 32]),
-            TrieNodeEpoch::UNMODIFIED,
-            1,
-        );
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::from([0x55; 32]), 1);
 
         // The consumer abandons the computation. The updates channel is still open (no finish
         // marker was sent), so without the cancel signal the task would wait forever.
@@ -1376,38 +1951,9 @@ mod tests {
     #[test]
     fn run_ignores_hints_queued_after_updates_finish() {
         let runtime = reth_tasks::Runtime::test();
-        let provider_factory = create_test_provider_factory();
-        let anchor_hash = provider_factory.chain_spec().genesis_hash();
-        let overlay_factory = OverlayStateProviderFactory::new(
-            provider_factory,
-            OverlayBuilder::<reth_chain_state::EthPrimitives>::new(
-                anchor_hash,
-                ChangesetCache::new(),
-            ),
-        );
-        let proof_worker_handle =
-            ProofWorkerHandle::new(&runtime, ProofTaskCtx::new(overlay_factory), false);
-
-        let default_trie = RevealableSparseTrie::blind_from(ArenaParallelSparseTrie::default());
-        let trie = SparseStateTrie::default()
-            .with_accounts_trie(default_trie.clone())
-            .with_default_storage_trie(default_trie)
-            .with_updates(true);
-
         let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
         let (cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
-        let mut task = SparseTrieCacheTask::new_with_trie(
-            &runtime,
-            updates_rx,
-            cancel_rx,
-            std::sync::mpsc::channel().0,
-            proof_worker_handle,
-            SparseTrieTaskMetrics::default(),
-            trie,
-            B256::from([0x55; 32]),
-            TrieNodeEpoch::UNMODIFIED,
-            1,
-        );
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::from([0x55; 32]), 1);
 
         updates_tx.send(StateRootMessage::FinishedStateUpdates).unwrap();
         updates_tx.send(StateRootMessage::PrefetchProofs(Default::default())).unwrap();
@@ -1432,4 +1978,212 @@ mod tests {
 
         assert!(result.expect("state root task stalled on a late hint").is_ok());
     }
+
+    #[test]
+    fn out_of_order_proof_results_yield_same_root() {
+        let runtime = reth_tasks::Runtime::test();
+
+        let keys = [0x11, 0x22, 0x33].map(B256::repeat_byte);
+        let mut state = HashedPostState::default();
+        for (i, key) in keys.into_iter().enumerate() {
+            state.accounts.insert(
+                key,
+                Some(Account { balance: U256::from(i + 1), nonce: 1, bytecode_hash: None }),
+            );
+        }
+
+        // Prefetches every account in its own proof batch, then completes the batches in the
+        // order given by `order` before the state update is applied.
+        let state_root_with_completion_order = |order: fn(&mut Vec<ProofResultMessage>)| {
+            let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
+            let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+            let mut task = test_task(&runtime, updates_rx, cancel_rx, EMPTY_ROOT_HASH, 1);
+            let (interceptor_tx, interceptor_rx) = crossbeam_channel::unbounded();
+            task.proof_result_interceptor = Some(interceptor_tx);
+
+            for key in keys {
+                task.on_message(SparseTrieTaskMessage::PrefetchProofs(MultiProofTargetsV2 {
+                    account_targets: vec![ProofV2Target::new(key)],
+                    ..Default::default()
+                }));
+                task.make_progress().expect("progress should succeed");
+            }
+            let batches = task.in_flight_proof_batches;
+            assert!(batches > 1, "prefetches should be dispatched separately");
+
+            let mut results = (0..batches)
+                .map(|_| {
+                    interceptor_rx
+                        .recv_timeout(std::time::Duration::from_secs(5))
+                        .expect("proof should complete")
+                })
+                .collect::<Vec<_>>();
+            order(&mut results);
+            for result in results {
+                task.proof_result_tx.send(result).unwrap();
+            }
+            task.proof_result_interceptor = None;
+
+            updates_tx.send(StateRootMessage::HashedStateUpdate(state.clone())).unwrap();
+            updates_tx.send(StateRootMessage::FinishedStateUpdates).unwrap();
+            task.run().expect("state root computation should succeed").state_root
+        };
+
+        let in_order = state_root_with_completion_order(|_| {});
+        let reversed = state_root_with_completion_order(|results| results.reverse());
+        let rotated = state_root_with_completion_order(|results| results.rotate_left(1));
+
+        assert_ne!(in_order, EMPTY_ROOT_HASH);
+        assert_eq!(in_order, reversed);
+        assert_eq!(in_order, rotated);
+    }
+
+    #[test]
+    fn incremental_trie_updates_match_full_updates() {
+        let runtime = reth_tasks::Runtime::test();
+
+        // Both updates touch the same storage tries, so the second one changes nodes whose
+        // updates may already have been taken after the first one.
+        let updates = [0..32u8, 16..48].map(|slots| {
+            let mut state = HashedPostState::default();
+            for key in [0x11, 0x22] {
+                let address = B256::repeat_byte(key);
+                state.accounts.insert(
+                    address,
+                    Some(Account { balance: U256::from(key), nonce: 1, bytecode_hash: None }),
+                );
+                state.storages.insert(
+                    address,
+                    reth_trie::HashedStorage::from_iter(
+                        false,
+                        slots.clone().map(|slot| {
+                            (
+                                keccak256(B256::with_last_byte(slot)),
+                                U256::from(slot) + U256::from(key),
+                            )
+                        }),
+                    ),
+                );
+            }
+            state
+        });
+
+        let run = |incremental| {
+            let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
+            let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+            let mut task = test_task(&runtime, updates_rx, cancel_rx, EMPTY_ROOT_HASH, 1)
+                .with_incremental_trie_updates(incremental);
+            for update in updates.clone() {
+                updates_tx.send(StateRootMessage::HashedStateUpdate(update)).unwrap();
+            }
+            updates_tx.send(StateRootMessage::FinishedStateUpdates).unwrap();
+            let outcome = task.run().expect("state root computation should succeed");
+            (outcome.state_root, outcome.trie_updates)
+        };
+
+        let (full_root, full_updates) = run(false);
+        let (incremental_root, incremental_updates) = run(true);
+
+        assert_ne!(full_root, EMPTY_ROOT_HASH);
+        assert!(!full_updates.storage_tries.is_empty());
+        assert_eq!(incremental_root, full_root);
+        assert_eq!(incremental_updates, full_updates);
+    }
+
+    #[test]
+    fn run_blocking_matches_run() {
+        let runtime = reth_tasks::Runtime::test();
+
+        let updates = [0x11u8, 0x22, 0x33]
+            .into_iter()
+            .enumerate()
+            .map(|(i, byte)| {
+                let mut account = revm::state::Account::default();
+                account.info.balance = U256::from(i + 1);
+                account.info.nonce = 1;
+                account.status = revm::state::AccountStatus::Touched;
+                let mut update = revm::state::EvmState::default();
+                update.insert(Address::repeat_byte(byte), account);
+                update
+            })
+            .collect::<Vec<_>>();
+
+        let new_task = |updates_rx| {
+            test_task(&runtime, updates_rx, crossbeam_channel::never(), EMPTY_ROOT_HASH, 1)
+        };
+
+        let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let mut task = new_task(updates_rx);
+        for update in updates.clone() {
+            updates_tx.send(StateRootMessage::StateUpdate(update)).unwrap();
+        }
+        updates_tx.send(StateRootMessage::FinishedStateUpdates).unwrap();
+        let expected = task.run().expect("state root computation should succeed");
+
+        let (_updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let mut task = new_task(updates_rx);
+        let outcome = task.run_blocking(updates).expect("state root computation should succeed");
+
+        assert_ne!(outcome.state_root, EMPTY_ROOT_HASH);
+        assert_eq!(outcome.state_root, expected.state_root);
+        assert_eq!(outcome.hashed_state, expected.hashed_state);
+        assert_eq!(task.in_flight_proof_batches, 0);
+    }
+
+    #[test]
+    fn drain_pending_proofs_returns_received_proofs_in_order() {
+        let runtime = reth_tasks::Runtime::test();
+        let (_updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, B256::from([0x55; 32]), 1);
+
+        let state_for = |byte| {
+            let mut state = HashedPostState::default();
+            state.accounts.insert(B256::repeat_byte(byte), None);
+            state
+        };
+        for (byte, result) in [
+            (0x01, Ok(DecodedMultiProofV2::default())),
+            (0x02, Err(StateRootTaskError::Other("proof failed".to_string()))),
+            (0x03, Ok(DecodedMultiProofV2::default())),
+        ] {
+            task.proof_result_tx
+                .send(ProofResultMessage {
+                    result,
+                    elapsed: std::time::Duration::ZERO,
+                    state: state_for(byte),
+                })
+                .unwrap();
+        }
+
+        let drained = task.drain_pending_proofs();
+
+        assert_eq!(
+            drained.into_iter().map(|(state, _)| state).collect::<Vec<_>>(),
+            vec![state_for(0x01), state_for(0x03)]
+        );
+        assert!(task.drain_pending_proofs().is_empty());
+    }
+
+    #[cfg(feature = "trie-debug")]
+    #[test]
+    fn compute_subtree_returns_parent_root_of_revealed_accounts() {
+        let runtime = reth_tasks::Runtime::test();
+        let (_updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (_cancel_guard, cancel_rx) = crossbeam_channel::bounded::<()>(0);
+        let mut task = test_task(&runtime, updates_rx, cancel_rx, EMPTY_ROOT_HASH, 1);
+        let targets = || MultiProofTargetsV2 {
+            account_targets: vec![B256::from([0x11; 32]).into()],
+            ..Default::default()
+        };
+
+        task.in_flight_proof_batches = 1;
+        assert!(task.compute_subtree(targets()).is_err());
+        task.in_flight_proof_batches = 0;
+
+        let (root, _proof) = task.compute_subtree(targets()).expect("subtree should be computed");
+
+        assert_eq!(root, EMPTY_ROOT_HASH);
+        assert_eq!(task.in_flight_proof_batches, 0);
+    }
 }
@@ -91,6 +91,15 @@ pub trait NewBlockPayload:
 
     /// Returns a reference to the block.
     fn block(&self) -> &Self::Block;
+
+    /// Decodes the payload of a `NewBlock` message received on a session with the given `version`.
+    ///
+    /// Payloads whose encoding depends on the protocol version, e.g. whether the total difficulty
+    /// is included, can override this. Defaults to [`Decodable::decode`].
+    fn decode_with_version(version: EthVersion, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let _ = version;
+        Self::decode(buf)
+    }
 }
 
 /// A new block with the current total difficulty, which includes the difficulty of the returned
@@ -111,6 +120,33 @@ impl<B: Block + 'static> NewBlockPayload for NewBlock<B> {
     fn block(&self) -> &Self::Block {
         &self.block
     }
+
+    /// Sessions before `eth/69` require the total difficulty. Since `eth/69` removed it
+    /// (EIP-7642), it may be omitted and then decodes as zero.
+    fn decode_with_version(version: EthVersion, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if version < EthVersion::Eth69 {
+            return Self::decode(buf)
+        }
+
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let (mut payload, rest) =
+            buf.split_at_checked(header.payload_length).ok_or(alloy_rlp::Error::InputTooShort)?;
+
+        let block = B::decode(&mut payload)?;
+        let td = if payload.is_empty() { U128::ZERO } else { U128::decode(&mut payload)? };
+        if !payload.is_empty() {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: header.payload_length - payload.len(),
+            })
+        }
+
+        *buf = rest;
+        Ok(Self { block, td })
+    }
 }
 
 generate_tests!(#[rlp, 25] NewBlock<reth_ethereum_primitives::Block>, EthNewBlockTests);
@@ -666,6 +702,14 @@ impl NewPooledTransactionHashes68 {
         }
     }
 
+    /// Returns `true` if there is exactly one type and one size for every hash.
+    ///
+    /// Decoding already rejects misaligned announcements, but locally constructed ones are not
+    /// checked.
+    pub const fn has_aligned_metadata(&self) -> bool {
+        self.types.len() == self.hashes.len() && self.sizes.len() == self.hashes.len()
+    }
+
     /// Returns an iterator over tx hashes zipped with corresponding metadata.
     pub fn metadata_iter(&self) -> impl Iterator<Item = (&B256, (u8, usize))> {
         self.hashes.iter().zip(self.types.iter().copied().zip(self.sizes.iter().copied()))
@@ -841,6 +885,14 @@ impl NewPooledTransactionHashes72 {
         }
     }
 
+    /// Returns `true` if there is exactly one type and one size for every hash.
+    ///
+    /// Decoding already rejects misaligned announcements, but locally constructed ones are not
+    /// checked.
+    pub const fn has_aligned_metadata(&self) -> bool {
+        self.types.len() == self.hashes.len() && self.sizes.len() == self.hashes.len()
+    }
+
     /// Returns an iterator over tx hashes zipped with corresponding metadata.
     pub fn metadata_iter(&self) -> impl Iterator<Item = (&B256, (u8, usize))> {
         self.hashes.iter().zip(self.types.iter().copied().zip(self.sizes.iter().copied()))
@@ -1809,4 +1861,39 @@ mod tests {
         assert_eq!(tx_hashes.sizes[1], tx.encode_2718_len());
         assert_eq!(tx_hashes.hashes[1], *tx.tx_hash());
     }
+
+    #[test]
+    fn test_new_block_decode_with_version() {
+        let new_block =
+            NewBlock { block: reth_ethereum_primitives::Block::default(), td: U128::from(5) };
+        let mut with_td = Vec::new();
+        new_block.encode(&mut with_td);
+
+        let mut without_td = Vec::new();
+        Header { list: true, payload_length: new_block.block.length() }.encode(&mut without_td);
+        new_block.block.encode(&mut without_td);
+
+        // the total difficulty is required before eth/69
+        assert_eq!(
+            NewBlock::decode_with_version(EthVersion::Eth68, &mut with_td.as_slice()).unwrap(),
+            new_block
+        );
+        assert!(NewBlock::<reth_ethereum_primitives::Block>::decode_with_version(
+            EthVersion::Eth68,
+            &mut without_td.as_slice()
+        )
+        .is_err());
+
+        // and optional since
+        assert_eq!(
+            NewBlock::decode_with_version(EthVersion::Eth69, &mut with_td.as_slice()).unwrap(),
+            new_block
+        );
+        let mut buf = without_td.as_slice();
+        assert_eq!(
+            NewBlock::decode_with_version(EthVersion::Eth69, &mut buf).unwrap(),
+            NewBlock { block: new_block.block.clone(), td: U128::ZERO }
+        );
+        assert!(buf.is_empty());
+    }
 }
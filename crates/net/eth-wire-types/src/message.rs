@@ -20,6 +20,7 @@ use crate::{
 use alloc::{boxed::Box, string::String, sync::Arc};
 use alloy_primitives::{
     bytes::{Buf, BufMut},
+    map::HashMap,
     Bytes,
 };
 use alloy_rlp::{length_of_length, Decodable, Encodable, Header};
@@ -38,6 +39,22 @@ pub const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
 /// `max_message_size * TX_MEMORY_BUDGET_MULTIPLIER`. Remaining transactions are silently dropped.
 pub const TX_MEMORY_BUDGET_MULTIPLIER: usize = 2;
 
+/// Maximum number of entries accepted in a received `NewBlockHashes` announcement.
+///
+/// Block announcements only cover a handful of recent blocks, anything beyond this can't be a
+/// legitimate announcement.
+pub const MAX_NEW_BLOCK_HASHES_ANNOUNCEMENT: usize = 1024;
+
+/// Maximum number of hashes accepted in a received `NewPooledTransactionHashes` announcement.
+///
+/// This leaves ample headroom over the
+/// [`SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE`](crate::broadcast::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE)
+/// that well behaved peers respect when announcing transactions.
+pub const MAX_NEW_POOLED_TRANSACTION_HASHES_ANNOUNCEMENT: usize = 4 * 4096;
+
+/// Maximum number of input bytes captured by [`ProtocolMessage::decode_message_debug`].
+pub const MAX_DECODE_ERROR_INPUT: usize = 64;
+
 /// Error when sending/receiving a message
 #[derive(thiserror::Error, Debug)]
 pub enum MessageError {
@@ -50,9 +67,40 @@ pub enum MessageError {
     /// Thrown when rlp decoding a message failed.
     #[error("RLP error: {0}")]
     RlpError(#[from] alloy_rlp::Error),
+    /// A [`RequestPair`] payload did not consume exactly the length declared by its header.
+    ///
+    /// Unlike a truncated message, this can only be caused by a malformed encoding.
+    #[error("request pair payload length mismatch")]
+    PayloadLengthMismatch,
+    /// An eth/69 or later receipt was encoded with a logs bloom, which these versions omit.
+    #[error("unexpected logs bloom in receipt")]
+    UnexpectedReceiptBloom,
+    /// The RLP header of a message declared a payload larger than the maximum message size,
+    /// [`MAX_MESSAGE_SIZE`] by default.
+    #[error("{id:?} message declares a payload of {len} bytes, exceeding the limit of {max}")]
+    PayloadTooLarge {
+        /// The id of the rejected message.
+        id: EthMessageID,
+        /// The declared payload length.
+        len: usize,
+        /// The maximum allowed payload length.
+        max: usize,
+    },
     /// Other message error with custom message
     #[error("{0}")]
     Other(String),
+    /// Decoding failed, with the input at the failure point attached for diagnostics.
+    ///
+    /// Returned by [`ProtocolMessage::decode_message_debug`].
+    #[error("{error} at offset {offset}, input: {input}")]
+    WithInput {
+        /// The decoding error.
+        error: Box<Self>,
+        /// Offset of the failure point from the start of the message.
+        offset: usize,
+        /// The input starting at the failure point, capped at [`MAX_DECODE_ERROR_INPUT`] bytes.
+        input: Bytes,
+    },
 }
 
 /// An `eth` protocol message, containing a message ID and payload.
@@ -93,11 +141,90 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
         Ok(status)
     }
 
+    /// Returns the encoded length of the message id prefix.
+    ///
+    /// Together with [`Self::payload_length`] this adds up to [`Encodable::length`].
+    pub fn header_length(&self) -> usize {
+        self.message_type.length()
+    }
+
+    /// Returns the encoded length of the message payload, excluding the message id prefix.
+    ///
+    /// This computes the length without encoding the message.
+    pub fn payload_length(&self) -> usize {
+        self.message.length()
+    }
+
     /// Create a new `ProtocolMessage` from a message type and message rlp bytes.
     ///
     /// This will enforce decoding according to the given [`EthVersion`] of the connection.
+    ///
+    /// With the `debug-roundtrip-check` feature, debug builds re-encode the decoded message and
+    /// assert that it matches the consumed input, to catch encode/decode asymmetries.
     pub fn decode_message(version: EthVersion, buf: &mut &[u8]) -> Result<Self, MessageError> {
-        Self::decode_message_with_tx_memory_budget(version, buf, usize::MAX)
+        #[cfg(all(debug_assertions, feature = "debug-roundtrip-check"))]
+        let input = *buf;
+        let message = Self::decode_message_with_tx_memory_budget(version, buf, usize::MAX)?;
+        #[cfg(all(debug_assertions, feature = "debug-roundtrip-check"))]
+        if !matches!(message.message_type, EthMessageID::Other(_)) {
+            let consumed = &input[..input.len() - buf.len()];
+            let mut encoded = alloc::vec::Vec::with_capacity(consumed.len());
+            message.encode(&mut encoded);
+            assert_eq!(
+                encoded.as_slice(),
+                consumed,
+                "{:?} message does not round-trip for {version:?}",
+                message.message_type
+            );
+        }
+        Ok(message)
+    }
+
+    /// Like [`Self::decode_message`], but attaches the input at the failure point to the error.
+    ///
+    /// At most [`MAX_DECODE_ERROR_INPUT`] bytes are captured, so the error can be logged when a
+    /// peer sends a malformed message without dumping the whole payload.
+    pub fn decode_message_debug(
+        version: EthVersion,
+        buf: &mut &[u8],
+    ) -> Result<Self, MessageError> {
+        let len = buf.len();
+        Self::decode_message(version, buf).map_err(|error| MessageError::WithInput {
+            error: Box::new(error),
+            offset: len - buf.len(),
+            input: Bytes::copy_from_slice(&buf[..buf.len().min(MAX_DECODE_ERROR_INPUT)]),
+        })
+    }
+
+    /// Decodes all messages from a buffer holding several consecutive messages.
+    ///
+    /// Each message is framed by its id and the RLP header that follows it, so a message that
+    /// fails to decode is yielded as an error and decoding continues with the next message. This
+    /// allows callers to detect violations that span messages, like a repeated `Status`.
+    ///
+    /// The iterator ends after the first framing error, because the start of the next message
+    /// can't be determined.
+    pub fn decode_many(
+        version: EthVersion,
+        mut buf: &[u8],
+    ) -> impl Iterator<Item = Result<Self, MessageError>> + '_ {
+        let mut framing_failed = false;
+        core::iter::from_fn(move || {
+            if framing_failed || buf.is_empty() {
+                return None
+            }
+
+            let frame_len = match message_frame_length(buf) {
+                Ok(frame_len) => frame_len,
+                Err(err) => {
+                    framing_failed = true;
+                    return Some(Err(err))
+                }
+            };
+            let (mut frame, rest) = buf.split_at(frame_len);
+            buf = rest;
+            Some(Self::decode_message(version, &mut frame))
+        })
     }
 
     /// Like [`Self::decode_message`], but caps the cumulative in-memory size of decoded
@@ -109,8 +236,27 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
         version: EthVersion,
         buf: &mut &[u8],
         tx_memory_budget: usize,
+    ) -> Result<Self, MessageError> {
+        Self::decode_message_with_limits(version, buf, MAX_MESSAGE_SIZE, tx_memory_budget)
+    }
+
+    /// Like [`Self::decode_message_with_tx_memory_budget`], but rejects messages whose payload
+    /// declares more than `max_message_size` bytes instead of [`MAX_MESSAGE_SIZE`].
+    ///
+    /// This should be the message size limit of the stream the message was read from.
+    pub fn decode_message_with_limits(
+        version: EthVersion,
+        buf: &mut &[u8],
+        max_message_size: usize,
+        tx_memory_budget: usize,
     ) -> Result<Self, MessageError> {
         let message_type = EthMessageID::decode(buf)?;
+        if !matches!(message_type, EthMessageID::Other(_)) {
+            ensure_payload_len(message_type, buf, max_message_size)?;
+            if !version.supports(message_type) {
+                return Err(MessageError::Invalid(version, message_type))
+            }
+        }
 
         // For EIP-7642 (https://github.com/ethereum/EIPs/blob/master/EIPS/eip-7642.md):
         // pre-merge (legacy) status messages include total difficulty, whereas eth/69 omits it.
@@ -121,15 +267,32 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
                 StatusMessage::Eth69(StatusEth69::decode(buf)?)
             }),
             EthMessageID::NewBlockHashes => {
-                EthMessage::NewBlockHashes(NewBlockHashes::decode(buf)?)
-            }
-            EthMessageID::NewBlock => {
-                EthMessage::NewBlock(Box::new(N::NewBlockPayload::decode(buf)?))
+                ensure_announcement_len(
+                    message_type,
+                    announcement_len_lower_bound(version, message_type, buf)?,
+                    MAX_NEW_BLOCK_HASHES_ANNOUNCEMENT,
+                )?;
+                let hashes = NewBlockHashes::decode(buf)?;
+                ensure_announcement_len(
+                    message_type,
+                    hashes.len(),
+                    MAX_NEW_BLOCK_HASHES_ANNOUNCEMENT,
+                )?;
+                EthMessage::NewBlockHashes(hashes)
             }
+            EthMessageID::NewBlock => EthMessage::NewBlock(Box::new(
+                N::NewBlockPayload::decode_with_version(version, buf)?,
+            )),
             EthMessageID::Transactions => EthMessage::Transactions(
                 Transactions::decode_with_memory_budget(buf, tx_memory_budget)?,
             ),
             EthMessageID::NewPooledTransactionHashes => {
+                // the bound is exact for pooled transaction announcements
+                ensure_announcement_len(
+                    message_type,
+                    announcement_len_lower_bound(version, message_type, buf)?,
+                    MAX_NEW_POOLED_TRANSACTION_HASHES_ANNOUNCEMENT,
+                )?;
                 if version >= EthVersion::Eth72 {
                     EthMessage::NewPooledTransactionHashes72(NewPooledTransactionHashes72::decode(
                         buf,
@@ -144,35 +307,31 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
                     )?)
                 }
             }
-            EthMessageID::GetBlockHeaders => EthMessage::GetBlockHeaders(RequestPair::decode(buf)?),
-            EthMessageID::BlockHeaders => EthMessage::BlockHeaders(RequestPair::decode(buf)?),
-            EthMessageID::GetBlockBodies => EthMessage::GetBlockBodies(RequestPair::decode(buf)?),
-            EthMessageID::BlockBodies => EthMessage::BlockBodies(RequestPair::decode(buf)?),
+            EthMessageID::GetBlockHeaders => {
+                EthMessage::GetBlockHeaders(RequestPair::decode_message(buf)?)
+            }
+            EthMessageID::BlockHeaders => {
+                EthMessage::BlockHeaders(RequestPair::decode_message(buf)?)
+            }
+            EthMessageID::GetBlockBodies => {
+                EthMessage::GetBlockBodies(RequestPair::decode_message(buf)?)
+            }
+            EthMessageID::BlockBodies => EthMessage::BlockBodies(RequestPair::decode_message(buf)?),
             EthMessageID::GetPooledTransactions => {
-                EthMessage::GetPooledTransactions(RequestPair::decode(buf)?)
+                EthMessage::GetPooledTransactions(RequestPair::decode_message(buf)?)
             }
             EthMessageID::PooledTransactions => {
-                EthMessage::PooledTransactions(RequestPair::decode_with(buf, |buf| {
-                    PooledTransactions::decode_with_memory_budget(buf, tx_memory_budget)
+                EthMessage::PooledTransactions(RequestPair::decode_message_with(buf, |buf| {
+                    Ok(PooledTransactions::decode_with_memory_budget(buf, tx_memory_budget)?)
                 })?)
             }
-            EthMessageID::GetNodeData => {
-                if version >= EthVersion::Eth67 {
-                    return Err(MessageError::Invalid(version, EthMessageID::GetNodeData))
-                }
-                EthMessage::GetNodeData(RequestPair::decode(buf)?)
-            }
-            EthMessageID::NodeData => {
-                if version >= EthVersion::Eth67 {
-                    return Err(MessageError::Invalid(version, EthMessageID::NodeData))
-                }
-                EthMessage::NodeData(RequestPair::decode(buf)?)
-            }
+            EthMessageID::GetNodeData => EthMessage::GetNodeData(RequestPair::decode_message(buf)?),
+            EthMessageID::NodeData => EthMessage::NodeData(RequestPair::decode_message(buf)?),
             EthMessageID::GetReceipts => {
                 if version >= EthVersion::Eth70 {
-                    EthMessage::GetReceipts70(RequestPair::decode(buf)?)
+                    EthMessage::GetReceipts70(RequestPair::decode_message(buf)?)
                 } else {
-                    EthMessage::GetReceipts(RequestPair::decode(buf)?)
+                    EthMessage::GetReceipts(RequestPair::decode_message(buf)?)
                 }
             }
             EthMessageID::Receipts => {
@@ -181,47 +340,36 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
                         // eth/70 continues to omit bloom filters and adds the
                         // `lastBlockIncomplete` flag, encoded as
                         // `[request-id, lastBlockIncomplete, [[receipt₁, receipt₂], ...]]`.
-                        EthMessage::Receipts70(RequestPair::decode(buf)?)
+                        EthMessage::Receipts70(RequestPair::decode_message(buf)?)
                     }
                     EthVersion::Eth69 => {
                         // with eth69, receipts no longer include the bloom
-                        EthMessage::Receipts69(RequestPair::decode(buf)?)
+                        EthMessage::Receipts69(RequestPair::decode_message_with(
+                            buf,
+                            Receipts69::decode_message,
+                        )?)
                     }
                     _ => {
                         // before eth69 we need to decode the bloom  as well
-                        EthMessage::Receipts(RequestPair::decode(buf)?)
+                        EthMessage::Receipts(RequestPair::decode_message(buf)?)
                     }
                 }
             }
             EthMessageID::BlockRangeUpdate => {
-                if version < EthVersion::Eth69 {
-                    return Err(MessageError::Invalid(version, EthMessageID::BlockRangeUpdate))
-                }
                 EthMessage::BlockRangeUpdate(BlockRangeUpdate::decode(buf)?)
             }
             EthMessageID::GetBlockAccessLists => {
-                if version < EthVersion::Eth71 {
-                    return Err(MessageError::Invalid(version, EthMessageID::GetBlockAccessLists))
-                }
-                EthMessage::GetBlockAccessLists(RequestPair::decode(buf)?)
+                EthMessage::GetBlockAccessLists(RequestPair::decode_message(buf)?)
             }
             EthMessageID::BlockAccessLists => {
-                if version < EthVersion::Eth71 {
-                    return Err(MessageError::Invalid(version, EthMessageID::BlockAccessLists))
-                }
-                EthMessage::BlockAccessLists(RequestPair::decode(buf)?)
+                EthMessage::BlockAccessLists(RequestPair::decode_message(buf)?)
             }
-            EthMessageID::Cells => {
-                if version < EthVersion::Eth72 {
-                    return Err(MessageError::Invalid(version, EthMessageID::Cells))
-                }
-                EthMessage::Cells(RequestPair::decode(buf)?)
-            }
-            EthMessageID::GetCells => {
-                if version < EthVersion::Eth72 {
-                    return Err(MessageError::Invalid(version, EthMessageID::GetCells))
-                }
-                EthMessage::GetCells(RequestPair::decode(buf)?)
+            EthMessageID::Cells => EthMessage::Cells(RequestPair::decode_message(buf)?),
+            EthMessageID::GetCells => EthMessage::GetCells(RequestPair::decode_message(buf)?),
+            EthMessageID::Other(id) if id <= EthMessageID::max(version) => {
+                // ids within the eth message space of the negotiated version are reserved for the
+                // eth protocol itself and can't belong to another capability
+                return Err(MessageError::Invalid(version, message_type))
             }
             EthMessageID::Other(_) => {
                 let raw_payload = Bytes::copy_from_slice(buf);
@@ -236,6 +384,86 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
     }
 }
 
+/// Checks the length declared by the RLP header of the message payload before anything is
+/// decoded.
+///
+/// Decoding list messages allocates based on their contents, so a message whose header declares
+/// more than `max_message_size` bytes, or more bytes than are actually left in the buffer, is
+/// rejected upfront.
+fn ensure_payload_len(
+    message_type: EthMessageID,
+    buf: &[u8],
+    max_message_size: usize,
+) -> Result<(), MessageError> {
+    let mut payload = buf;
+    let header = Header::decode(&mut payload)?;
+    if header.payload_length > max_message_size {
+        return Err(MessageError::PayloadTooLarge {
+            id: message_type,
+            len: header.payload_length,
+            max: max_message_size,
+        })
+    }
+    if header.payload_length > payload.len() {
+        return Err(alloy_rlp::Error::InputTooShort.into())
+    }
+    Ok(())
+}
+
+/// Returns a lower bound for the number of entries of the announcement at the start of `buf`,
+/// derived from its RLP list headers without decoding any entry.
+///
+/// Pooled transaction hashes are fixed size and eth/68+ announcements start with a string of one
+/// type byte per transaction, so the bound is exact for them. `NewBlockHashes` entries vary with
+/// the encoded block number, dividing by the largest possible entry never exceeds the real count.
+fn announcement_len_lower_bound(
+    version: EthVersion,
+    message_type: EthMessageID,
+    buf: &[u8],
+) -> Result<usize, MessageError> {
+    /// Encoded length of a hash: a one byte string header and 32 bytes.
+    const HASH_LEN: usize = 1 + 32;
+    /// Largest encoded `[hash, number]` entry: a one byte list header, the hash and a `u64`.
+    const MAX_BLOCK_HASH_NUMBER_LEN: usize = 1 + HASH_LEN + 1 + 8;
+
+    let mut payload = buf;
+    let header = Header::decode(&mut payload)?;
+    let len = match message_type {
+        EthMessageID::NewBlockHashes => header.payload_length.div_ceil(MAX_BLOCK_HASH_NUMBER_LEN),
+        EthMessageID::NewPooledTransactionHashes if version >= EthVersion::Eth68 => {
+            Header::decode(&mut payload)?.payload_length
+        }
+        _ => header.payload_length / HASH_LEN,
+    };
+    Ok(len)
+}
+
+/// Rejects announcements with more than `max` entries.
+fn ensure_announcement_len(
+    message_type: EthMessageID,
+    len: usize,
+    max: usize,
+) -> Result<(), MessageError> {
+    if len > max {
+        return Err(MessageError::Other(format!(
+            "{message_type:?} announcement with {len} entries exceeds the limit of {max}"
+        )))
+    }
+    Ok(())
+}
+
+/// Returns the length of the message at the start of the buffer, consisting of the message id and
+/// a single RLP item.
+fn message_frame_length(buf: &[u8]) -> Result<usize, MessageError> {
+    let mut payload = &buf[1..];
+    let header = Header::decode(&mut payload)?;
+    let frame_len = buf.len() - payload.len() + header.payload_length;
+    if frame_len > buf.len() {
+        return Err(alloy_rlp::Error::InputTooShort.into())
+    }
+    Ok(frame_len)
+}
+
 impl<N: NetworkPrimitives> Encodable for ProtocolMessage<N> {
     /// Encodes the protocol message into bytes. The message type is encoded as a single byte and
     /// prepended to the message.
@@ -244,7 +472,7 @@ impl<N: NetworkPrimitives> Encodable for ProtocolMessage<N> {
         self.message.encode(out);
     }
     fn length(&self) -> usize {
-        self.message_type.length() + self.message.length()
+        self.header_length() + self.payload_length()
     }
 }
 
@@ -254,6 +482,70 @@ impl<N: NetworkPrimitives> From<EthMessage<N>> for ProtocolMessage<N> {
     }
 }
 
+/// An [`EthMessage`] tagged with the [`EthVersion`] it was decoded under.
+///
+/// Decoding is version dependent, so recorded wire traffic must retain the version to be
+/// faithfully re-decoded. The RLP encoding is a list of the version followed by the message id and
+/// payload, and decoding applies the same version gating as [`ProtocolMessage::decode_message`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionedMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
+    /// The version the message was decoded under.
+    pub version: EthVersion,
+    /// The decoded message.
+    pub message: EthMessage<N>,
+}
+
+impl<N: NetworkPrimitives> VersionedMessage<N> {
+    /// Creates a new message tagged with the given version.
+    pub const fn new(version: EthVersion, message: EthMessage<N>) -> Self {
+        Self { version, message }
+    }
+
+    fn rlp_payload_length(&self) -> usize {
+        self.version.length() + self.message.message_id().length() + self.message.length()
+    }
+}
+
+impl<N: NetworkPrimitives> Encodable for VersionedMessage<N> {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.rlp_payload_length() }.encode(out);
+        self.version.encode(out);
+        self.message.message_id().encode(out);
+        self.message.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.rlp_payload_length();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl<N: NetworkPrimitives> Decodable for VersionedMessage<N> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let (mut payload, rest) = buf.split_at(header.payload_length);
+
+        let version = EthVersion::decode(&mut payload)?;
+        let ProtocolMessage { message, .. } =
+            ProtocolMessage::decode_message(version, &mut payload).map_err(|err| match err {
+                MessageError::RlpError(err) => err,
+                _ => alloy_rlp::Error::Custom("message is invalid for the recorded eth version"),
+            })?;
+        if !payload.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength)
+        }
+
+        *buf = rest;
+        Ok(Self { version, message })
+    }
+}
+
 /// Represents messages that can be sent to multiple peers.
 #[derive(Clone, Debug)]
 pub struct ProtocolBroadcastMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
@@ -471,6 +763,41 @@ impl<N: NetworkPrimitives> EthMessage<N> {
         )
     }
 
+    /// Returns true if the message variant is a broadcast that isn't part of a request-response
+    /// exchange.
+    pub const fn is_broadcast(&self) -> bool {
+        matches!(
+            self,
+            Self::NewBlockHashes(_) |
+                Self::NewBlock(_) |
+                Self::Transactions(_) |
+                Self::NewPooledTransactionHashes66(_) |
+                Self::NewPooledTransactionHashes68(_) |
+                Self::NewPooledTransactionHashes72(_) |
+                Self::BlockRangeUpdate(_)
+        )
+    }
+
+    /// Checks message invariants that the message types can't enforce on their own.
+    ///
+    /// This rejects `eth/68` and `eth/72` transaction announcements whose `types`, `sizes` and
+    /// `hashes` lists are not of equal length.
+    pub fn validate(&self) -> Result<(), MessageError> {
+        let (types, sizes, hashes) = match self {
+            Self::NewPooledTransactionHashes68(msg) if !msg.has_aligned_metadata() => {
+                (msg.types.len(), msg.sizes.len(), msg.hashes.len())
+            }
+            Self::NewPooledTransactionHashes72(msg) if !msg.has_aligned_metadata() => {
+                (msg.types.len(), msg.sizes.len(), msg.hashes.len())
+            }
+            _ => return Ok(()),
+        };
+
+        Err(MessageError::Other(format!(
+            "misaligned transaction announcement: {types} types, {sizes} sizes, {hashes} hashes"
+        )))
+    }
+
     /// Converts the message types where applicable.
     ///
     /// This handles up/downcasting where appropriate, for example for different receipt request
@@ -599,6 +926,28 @@ impl<N: NetworkPrimitives> EthBroadcastMessage<N> {
     }
 }
 
+/// Converts a received message into a broadcast message for relaying it.
+///
+/// Only [`EthMessage::NewBlock`] and [`EthMessage::Transactions`] are broadcastable, any other
+/// message is returned unchanged as the error.
+///
+/// Note: this is not a plain move. The block payload is moved out of its [`Box`] into a new
+/// [`Arc`] allocation, and every transaction is wrapped in its own [`Arc`] so the message can be
+/// shared with multiple peers.
+impl<N: NetworkPrimitives> TryFrom<EthMessage<N>> for EthBroadcastMessage<N> {
+    type Error = EthMessage<N>;
+
+    fn try_from(message: EthMessage<N>) -> Result<Self, Self::Error> {
+        match message {
+            EthMessage::NewBlock(block) => Ok(Self::NewBlock(Arc::from(block))),
+            EthMessage::Transactions(transactions) => Ok(Self::Transactions(SharedTransactions(
+                transactions.0.into_iter().map(Arc::new).collect(),
+            ))),
+            message => Err(message),
+        }
+    }
+}
+
 impl<N: NetworkPrimitives> Encodable for EthBroadcastMessage<N> {
     fn encode(&self, out: &mut dyn BufMut) {
         match self {
@@ -619,7 +968,7 @@ impl<N: NetworkPrimitives> Encodable for EthBroadcastMessage<N> {
 
 /// Represents message IDs for eth protocol messages.
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EthMessageID {
     /// Status message.
@@ -705,6 +1054,64 @@ impl EthMessageID {
         }
     }
 
+    /// Returns true if this is the id of a request message.
+    pub const fn is_request(&self) -> bool {
+        matches!(
+            self,
+            Self::GetBlockHeaders |
+                Self::GetBlockBodies |
+                Self::GetPooledTransactions |
+                Self::GetNodeData |
+                Self::GetReceipts |
+                Self::GetBlockAccessLists |
+                Self::GetCells
+        )
+    }
+
+    /// Returns true if this is the id of a response message.
+    pub const fn is_response(&self) -> bool {
+        matches!(
+            self,
+            Self::BlockHeaders |
+                Self::BlockBodies |
+                Self::PooledTransactions |
+                Self::NodeData |
+                Self::Receipts |
+                Self::BlockAccessLists |
+                Self::Cells
+        )
+    }
+
+    /// Returns true if this is the id of a broadcast message.
+    pub const fn is_broadcast(&self) -> bool {
+        matches!(
+            self,
+            Self::NewBlockHashes |
+                Self::NewBlock |
+                Self::Transactions |
+                Self::NewPooledTransactionHashes |
+                Self::BlockRangeUpdate
+        )
+    }
+
+    /// Returns true if this is the id of the response message that answers the given request.
+    ///
+    /// A response with a matching request id but a different message type, e.g. a
+    /// [`GetBlockHeaders`](Self::GetBlockHeaders) request answered with
+    /// [`BlockBodies`](Self::BlockBodies), is a protocol violation.
+    pub const fn is_valid_response_to(&self, request: Self) -> bool {
+        matches!(
+            (request, self),
+            (Self::GetBlockHeaders, Self::BlockHeaders) |
+                (Self::GetBlockBodies, Self::BlockBodies) |
+                (Self::GetPooledTransactions, Self::PooledTransactions) |
+                (Self::GetNodeData, Self::NodeData) |
+                (Self::GetReceipts, Self::Receipts) |
+                (Self::GetBlockAccessLists, Self::BlockAccessLists) |
+                (Self::GetCells, Self::Cells)
+        )
+    }
+
     /// Returns the max value for the given version.
     pub const fn max(version: EthVersion) -> u8 {
         if version.is_eth72() {
@@ -797,6 +1204,76 @@ impl TryFrom<usize> for EthMessageID {
     }
 }
 
+/// Number of message ids up to the highest known [`EthMessageID`].
+const KNOWN_MESSAGE_IDS: usize = EthMessageID::Cells.to_u8() as usize + 1;
+
+/// Counts [`EthMessageID`]s, e.g. of the messages exchanged with a peer.
+///
+/// Known ids are counted in a fixed-size array indexed by the id, so recording a message is cheap.
+/// [`EthMessageID::Other`] ids are counted in a separate overflow map.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MessageIdCounter {
+    /// Counts of the known ids, indexed by the id.
+    known: [u64; KNOWN_MESSAGE_IDS],
+    /// Counts of [`EthMessageID::Other`] ids.
+    other: HashMap<u8, u64>,
+}
+
+impl MessageIdCounter {
+    /// Creates a counter without any recorded ids.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one occurrence of the given id.
+    pub fn record(&mut self, id: EthMessageID) {
+        match id {
+            EthMessageID::Other(id) => {
+                let count = self.other.entry(id).or_default();
+                *count = count.saturating_add(1);
+            }
+            known => {
+                let count = &mut self.known[known.to_u8() as usize];
+                *count = count.saturating_add(1);
+            }
+        }
+    }
+
+    /// Returns how often the given id was recorded.
+    pub fn count(&self, id: EthMessageID) -> u64 {
+        match id {
+            EthMessageID::Other(id) => self.other.get(&id).copied().unwrap_or_default(),
+            known => self.known[known.to_u8() as usize],
+        }
+    }
+
+    /// Returns the total number of recorded ids.
+    pub fn total(&self) -> u64 {
+        self.known
+            .iter()
+            .chain(self.other.values())
+            .fold(0u64, |total, count| total.saturating_add(*count))
+    }
+
+    /// Returns the counts of all ids that were recorded at least once.
+    pub fn snapshot(&self) -> HashMap<EthMessageID, u64> {
+        let known = self
+            .known
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .filter_map(|(id, count)| EthMessageID::try_from(id).ok().map(|id| (id, *count)));
+        let other = self.other.iter().map(|(id, count)| (EthMessageID::Other(*id), *count));
+        known.chain(other).collect()
+    }
+
+    /// Resets all counts to zero.
+    pub fn clear(&mut self) {
+        self.known = [0; KNOWN_MESSAGE_IDS];
+        self.other.clear();
+    }
+}
+
 /// This is used for all request-response style `eth` protocol messages.
 /// This can represent either a request or a response, since both include a message payload and
 /// request id.
@@ -821,10 +1298,61 @@ impl<T> RequestPair<T> {
         RequestPair { request_id, message: f(message) }
     }
 
+    /// Decodes the header and request id of an RLP encoded request pair, leaving `buf` at the
+    /// start of the message.
+    ///
+    /// This makes the request id available before the message is decoded, e.g. to correlate a
+    /// response that is then decoded incrementally with
+    /// [`PooledTransactions::decode_iter`](crate::PooledTransactions::decode_iter). `buf` is
+    /// truncated to the request pair, so the message is expected to end with it.
+    pub fn decode_request_id(buf: &mut &[u8]) -> alloy_rlp::Result<u64> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+
+        let mut payload = &buf[..header.payload_length];
+        let request_id = u64::decode(&mut payload)?;
+        *buf = payload;
+        Ok(request_id)
+    }
+
     /// Decodes the request id and then decodes the message payload using `decode_msg`.
     pub fn decode_with<F>(buf: &mut &[u8], decode_msg: F) -> alloy_rlp::Result<Self>
     where
         F: FnOnce(&mut &[u8]) -> alloy_rlp::Result<T>,
+    {
+        Self::decode_fields(buf, decode_msg, alloy_rlp::Error::UnexpectedLength)
+    }
+
+    /// Like [`Decodable::decode`], but reports a payload that doesn't match the length declared
+    /// by the list header as [`MessageError::PayloadLengthMismatch`].
+    pub fn decode_message(buf: &mut &[u8]) -> Result<Self, MessageError>
+    where
+        T: Decodable,
+    {
+        Self::decode_message_with(buf, |buf| Ok(T::decode(buf)?))
+    }
+
+    /// Like [`Self::decode_with`], but returns a [`MessageError`], so `decode_msg` can report
+    /// typed errors and a payload length mismatch is reported as
+    /// [`MessageError::PayloadLengthMismatch`].
+    pub fn decode_message_with<F>(buf: &mut &[u8], decode_msg: F) -> Result<Self, MessageError>
+    where
+        F: FnOnce(&mut &[u8]) -> Result<T, MessageError>,
+    {
+        Self::decode_fields(buf, decode_msg, MessageError::PayloadLengthMismatch)
+    }
+
+    /// Decodes the request id and the message with `decode_msg`, returning `length_mismatch` if
+    /// they don't consume exactly the payload length declared by the list header.
+    fn decode_fields<F, E>(buf: &mut &[u8], decode_msg: F, length_mismatch: E) -> Result<Self, E>
+    where
+        F: FnOnce(&mut &[u8]) -> Result<T, E>,
+        E: From<alloy_rlp::Error>,
     {
         let header = Header::decode(buf)?;
 
@@ -832,9 +1360,11 @@ impl<T> RequestPair<T> {
         let request_id = u64::decode(buf)?;
         let message = decode_msg(buf)?;
 
+        // Check that the buffer consumed exactly payload_length bytes after decoding the
+        // RequestPair
         let consumed_len = initial_length - buf.len();
         if consumed_len != header.payload_length {
-            return Err(alloy_rlp::Error::UnexpectedLength)
+            return Err(length_mismatch)
         }
 
         Ok(Self { request_id, message })
@@ -870,33 +1400,24 @@ where
     T: Decodable,
 {
     fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
-        let header = Header::decode(buf)?;
-
-        let initial_length = buf.len();
-        let request_id = u64::decode(buf)?;
-        let message = T::decode(buf)?;
-
-        // Check that the buffer consumed exactly payload_length bytes after decoding the
-        // RequestPair
-        let consumed_len = initial_length - buf.len();
-        if consumed_len != header.payload_length {
-            return Err(alloy_rlp::Error::UnexpectedLength)
-        }
-
-        Ok(Self { request_id, message })
+        Self::decode_with(buf, T::decode)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MessageError;
+    use super::{
+        MessageError, MessageIdCounter, MAX_DECODE_ERROR_INPUT, MAX_MESSAGE_SIZE,
+        MAX_NEW_BLOCK_HASHES_ANNOUNCEMENT, MAX_NEW_POOLED_TRANSACTION_HASHES_ANNOUNCEMENT,
+    };
     use crate::{
-        message::RequestPair, BlockAccessLists, EthMessage, EthMessageID, EthNetworkPrimitives,
-        EthVersion, GetBlockAccessLists, GetNodeData, NodeData, ProtocolMessage,
-        RawCapabilityMessage,
+        message::RequestPair, BlockAccessLists, BlockHashNumber, BlockRangeUpdate, EthMessage,
+        EthMessageID, EthNetworkPrimitives, EthVersion, GetBlockAccessLists, GetNodeData,
+        NewBlockHashes, NewPooledTransactionHashes66, NewPooledTransactionHashes68, NodeData,
+        ProtocolMessage, RawCapabilityMessage, Receipts, VersionedMessage,
     };
-    use alloy_primitives::hex;
-    use alloy_rlp::{Decodable, Encodable, Error};
+    use alloy_primitives::{hex, B256};
+    use alloy_rlp::{Decodable, Encodable, Error, Header};
     use reth_ethereum_primitives::BlockBody;
 
     fn encode<T: Encodable>(value: T) -> Vec<u8> {
@@ -905,6 +1426,43 @@ mod tests {
         buf
     }
 
+    #[test]
+    fn broadcastable_messages_convert_to_broadcast_message() {
+        use crate::{EthBroadcastMessage, NewBlock, Transactions};
+        use alloy_primitives::Signature;
+        use reth_ethereum_primitives::{Transaction, TransactionSigned};
+
+        let tx = TransactionSigned::new_unhashed(
+            Transaction::Legacy(Default::default()),
+            Signature::test_signature(),
+        );
+        let message =
+            EthMessage::<EthNetworkPrimitives>::Transactions(Transactions(vec![tx.clone()]));
+        let Ok(EthBroadcastMessage::Transactions(shared)) = EthBroadcastMessage::try_from(message)
+        else {
+            panic!("expected transactions broadcast")
+        };
+        assert_eq!(shared.0.len(), 1);
+        assert_eq!(*shared.0[0], tx);
+
+        let block = NewBlock::default();
+        let message = EthMessage::<EthNetworkPrimitives>::NewBlock(Box::new(block.clone()));
+        let Ok(EthBroadcastMessage::NewBlock(shared)) = EthBroadcastMessage::try_from(message)
+        else {
+            panic!("expected new block broadcast")
+        };
+        assert_eq!(*shared, block);
+
+        let message = EthMessage::<EthNetworkPrimitives>::GetNodeData(RequestPair {
+            request_id: 1,
+            message: GetNodeData(vec![]),
+        });
+        let Err(EthMessage::GetNodeData(returned)) = EthBroadcastMessage::try_from(message) else {
+            panic!("expected the message to be returned")
+        };
+        assert_eq!(returned.request_id, 1);
+    }
+
     #[test]
     fn test_removed_message_at_eth67() {
         let get_node_data = EthMessage::<EthNetworkPrimitives>::GetNodeData(RequestPair {
@@ -934,6 +1492,52 @@ mod tests {
         assert!(matches!(msg, Err(MessageError::Invalid(..))));
     }
 
+    #[test]
+    fn eth69_receipts_with_bloom_rejected() {
+        let receipts = EthMessage::<EthNetworkPrimitives>::Receipts(RequestPair {
+            request_id: 1337,
+            message: Receipts(vec![vec![Default::default()]]),
+        });
+        let buf =
+            encode(ProtocolMessage { message_type: EthMessageID::Receipts, message: receipts });
+
+        let msg = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            crate::EthVersion::Eth69,
+            &mut &buf[..],
+        );
+        assert!(matches!(msg, Err(MessageError::UnexpectedReceiptBloom)));
+
+        // the same payload is valid for eth/68
+        let msg = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            crate::EthVersion::Eth68,
+            &mut &buf[..],
+        );
+        assert!(msg.is_ok());
+    }
+
+    #[test]
+    fn test_misaligned_eth68_announcement_rejected() {
+        let announcement = NewPooledTransactionHashes68 {
+            types: vec![0x02],
+            sizes: vec![100, 200],
+            hashes: vec![B256::repeat_byte(1), B256::repeat_byte(2)],
+        };
+        assert!(!announcement.has_aligned_metadata());
+
+        let msg = EthMessage::<EthNetworkPrimitives>::NewPooledTransactionHashes68(announcement);
+        assert!(matches!(msg.validate(), Err(MessageError::Other(_))));
+
+        let buf = encode(ProtocolMessage {
+            message_type: EthMessageID::NewPooledTransactionHashes,
+            message: msg,
+        });
+        let msg = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &buf[..],
+        );
+        assert!(msg.is_err());
+    }
+
     #[test]
     fn test_bal_message_version_gating() {
         let get_block_access_lists =
@@ -1029,6 +1633,24 @@ mod tests {
 
         let result = RequestPair::<Vec<u8>>::decode(&mut &*raw_pair);
         assert!(matches!(result, Err(Error::UnexpectedLength)));
+
+        // surfaced as a distinct error, not as a truncated message
+        let err = RequestPair::<Vec<u8>>::decode_message(&mut &*raw_pair).unwrap_err();
+        assert!(matches!(err, MessageError::PayloadLengthMismatch));
+
+        let mut raw_message = vec![EthMessageID::NodeData.to_u8()];
+        raw_message.extend_from_slice(raw_pair);
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth66,
+            &mut &raw_message[..],
+        )
+        .unwrap_err();
+        assert!(matches!(err, MessageError::PayloadLengthMismatch));
+
+        // a truncated request pair is still reported as an RLP error
+        let err = RequestPair::<Vec<u8>>::decode_message(&mut &raw_pair[..raw_pair.len() - 1])
+            .unwrap_err();
+        assert!(matches!(err, MessageError::RlpError(_)));
     }
 
     #[test]
@@ -1075,6 +1697,377 @@ mod tests {
         assert!(matches!(msg, MessageError::RlpError(alloy_rlp::Error::InputTooShort)));
     }
 
+    #[test]
+    fn message_id_classification_is_exclusive() {
+        for id in 0..=EthMessageID::max(EthVersion::Eth72) as usize {
+            let Ok(id) = EthMessageID::try_from(id) else { continue };
+            let classes = [id.is_request(), id.is_response(), id.is_broadcast()];
+            let expected = if id == EthMessageID::Status { 0 } else { 1 };
+            assert_eq!(classes.iter().filter(|c| **c).count(), expected, "{id:?}");
+        }
+
+        let other = EthMessageID::Other(0x20);
+        assert!(!other.is_request() && !other.is_response() && !other.is_broadcast());
+    }
+
+    #[test]
+    fn message_id_response_matches_request() {
+        let valid = [
+            (EthMessageID::GetBlockHeaders, EthMessageID::BlockHeaders),
+            (EthMessageID::GetBlockBodies, EthMessageID::BlockBodies),
+            (EthMessageID::GetPooledTransactions, EthMessageID::PooledTransactions),
+            (EthMessageID::GetNodeData, EthMessageID::NodeData),
+            (EthMessageID::GetReceipts, EthMessageID::Receipts),
+            (EthMessageID::GetBlockAccessLists, EthMessageID::BlockAccessLists),
+            (EthMessageID::GetCells, EthMessageID::Cells),
+        ];
+        for (request, response) in valid {
+            assert!(response.is_valid_response_to(request), "{request:?} -> {response:?}");
+        }
+
+        // every pair of known ids outside the table is invalid
+        let ids = (0..=EthMessageID::max(EthVersion::Eth72) as usize)
+            .filter_map(|id| EthMessageID::try_from(id).ok())
+            .collect::<Vec<_>>();
+        for request in &ids {
+            for response in &ids {
+                assert_eq!(
+                    response.is_valid_response_to(*request),
+                    valid.contains(&(*request, *response)),
+                    "{request:?} -> {response:?}"
+                );
+            }
+        }
+
+        assert!(!EthMessageID::BlockBodies.is_valid_response_to(EthMessageID::GetBlockHeaders));
+        assert!(!EthMessageID::GetBlockHeaders.is_valid_response_to(EthMessageID::BlockHeaders));
+        assert!(!EthMessageID::Other(0x20).is_valid_response_to(EthMessageID::Other(0x1f)));
+    }
+
+    #[test]
+    fn message_id_counter_counts_known_and_other_ids() {
+        let mut counter = MessageIdCounter::new();
+        counter.record(EthMessageID::GetBlockHeaders);
+        counter.record(EthMessageID::GetBlockHeaders);
+        counter.record(EthMessageID::Cells);
+        counter.record(EthMessageID::Other(0x0b));
+        counter.record(EthMessageID::Other(0x42));
+        counter.record(EthMessageID::Other(0x42));
+
+        assert_eq!(counter.count(EthMessageID::GetBlockHeaders), 2);
+        assert_eq!(counter.count(EthMessageID::Cells), 1);
+        assert_eq!(counter.count(EthMessageID::Status), 0);
+        assert_eq!(counter.count(EthMessageID::Other(0x42)), 2);
+        assert_eq!(counter.total(), 6);
+
+        let snapshot = counter.snapshot();
+        assert_eq!(snapshot.len(), 4);
+        assert_eq!(snapshot[&EthMessageID::GetBlockHeaders], 2);
+        assert_eq!(snapshot[&EthMessageID::Cells], 1);
+        assert_eq!(snapshot[&EthMessageID::Other(0x0b)], 1);
+        assert_eq!(snapshot[&EthMessageID::Other(0x42)], 2);
+
+        counter.clear();
+        assert_eq!(counter.total(), 0);
+        assert!(counter.snapshot().is_empty());
+    }
+
+    #[test]
+    fn header_and_payload_lengths_add_up() {
+        let message =
+            ProtocolMessage::<EthNetworkPrimitives>::from(EthMessage::GetNodeData(RequestPair {
+                request_id: 1,
+                message: GetNodeData(vec![B256::ZERO; 4]),
+            }));
+        let encoded = encode(message.clone());
+
+        assert_eq!(message.header_length(), 1);
+        assert_eq!(message.header_length() + message.payload_length(), encoded.len());
+    }
+
+    #[test]
+    fn decode_error_captures_bounded_input() {
+        // a NodeData message whose payload is a truncated list
+        let mut encoded = vec![0x0e, 0xf9, 0x01, 0x00];
+        encoded.extend(std::iter::repeat_n(0xaa, 100));
+
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message_debug(
+            EthVersion::Eth66,
+            &mut &encoded[..],
+        )
+        .unwrap_err();
+        let MessageError::WithInput { error, offset, input } = err else {
+            panic!("expected error with input, got {err:?}")
+        };
+        assert!(matches!(*error, MessageError::RlpError(_)));
+        assert!(offset <= encoded.len());
+        assert!(input.len() <= MAX_DECODE_ERROR_INPUT);
+        assert_eq!(&input[..], &encoded[offset..][..input.len()]);
+    }
+
+    #[test]
+    fn versioned_message_roundtrip() {
+        let messages = [
+            VersionedMessage::<EthNetworkPrimitives>::new(
+                EthVersion::Eth68,
+                EthMessage::NewPooledTransactionHashes68(NewPooledTransactionHashes68 {
+                    types: vec![2],
+                    sizes: vec![100],
+                    hashes: vec![B256::repeat_byte(1)],
+                }),
+            ),
+            VersionedMessage::new(
+                EthVersion::Eth69,
+                EthMessage::BlockRangeUpdate(BlockRangeUpdate {
+                    earliest: 1,
+                    latest: 10,
+                    latest_hash: B256::repeat_byte(2),
+                }),
+            ),
+            VersionedMessage::new(
+                EthVersion::Eth68,
+                EthMessage::GetNodeData(RequestPair {
+                    request_id: 7,
+                    message: GetNodeData(vec![B256::ZERO]),
+                }),
+            ),
+        ];
+
+        let mut stream = Vec::new();
+        for message in &messages {
+            let len = stream.len();
+            message.encode(&mut stream);
+            assert_eq!(stream.len() - len, message.length());
+        }
+
+        let mut buf = &stream[..];
+        for message in &messages {
+            assert_eq!(&VersionedMessage::decode(&mut buf).unwrap(), message);
+        }
+        assert!(buf.is_empty());
+
+        // the version gating is applied when re-decoding
+        let mut downgraded = messages[1].clone();
+        downgraded.version = EthVersion::Eth68;
+        let encoded = encode(downgraded);
+        assert!(VersionedMessage::<EthNetworkPrimitives>::decode(&mut &encoded[..]).is_err());
+    }
+
+    #[test]
+    fn reject_lying_payload_length_prefix() {
+        // a list header declaring 16 MiB, followed by a few bytes of payload
+        let declared = 16 * 1024 * 1024;
+        let mut buf = vec![EthMessageID::BlockBodies.to_u8()];
+        alloy_rlp::Header { list: true, payload_length: declared }.encode(&mut buf);
+        buf.extend_from_slice(&[0xc0; 8]);
+
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &buf[..],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            MessageError::PayloadTooLarge { id: EthMessageID::BlockBodies, len, max }
+                if len == declared && max == MAX_MESSAGE_SIZE
+        ));
+
+        // within the limit, but longer than the actual input
+        let mut buf = vec![EthMessageID::PooledTransactions.to_u8()];
+        alloy_rlp::Header { list: true, payload_length: 1024 * 1024 }.encode(&mut buf);
+        buf.extend_from_slice(&[0xc0; 8]);
+
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &buf[..],
+        )
+        .unwrap_err();
+        assert!(matches!(err, MessageError::RlpError(Error::InputTooShort)));
+    }
+
+    #[test]
+    fn payload_len_check_uses_configured_limit() {
+        let declared = 16 * 1024 * 1024;
+        let mut buf = vec![EthMessageID::BlockBodies.to_u8()];
+        alloy_rlp::Header { list: true, payload_length: declared }.encode(&mut buf);
+        buf.extend_from_slice(&[0xc0; 8]);
+
+        // a raised limit admits the declared length, only the missing input is reported
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message_with_limits(
+            EthVersion::Eth68,
+            &mut &buf[..],
+            2 * declared,
+            usize::MAX,
+        )
+        .unwrap_err();
+        assert!(matches!(err, MessageError::RlpError(Error::InputTooShort)));
+
+        // a lowered limit rejects smaller payloads
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message_with_limits(
+            EthVersion::Eth68,
+            &mut &buf[..],
+            1024,
+            usize::MAX,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            MessageError::PayloadTooLarge { id: EthMessageID::BlockBodies, len, max: 1024 }
+                if len == declared
+        ));
+    }
+
+    #[test]
+    fn reject_oversized_announcements() {
+        let block_hashes = |count| {
+            encode(ProtocolMessage::<EthNetworkPrimitives>::from(EthMessage::NewBlockHashes(
+                NewBlockHashes(vec![BlockHashNumber::default(); count]),
+            )))
+        };
+        let encoded = block_hashes(MAX_NEW_BLOCK_HASHES_ANNOUNCEMENT);
+        assert!(ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &encoded[..]
+        )
+        .is_ok());
+        let encoded = block_hashes(MAX_NEW_BLOCK_HASHES_ANNOUNCEMENT + 1);
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &encoded[..],
+        )
+        .unwrap_err();
+        assert!(matches!(err, MessageError::Other(_)));
+
+        let count = MAX_NEW_POOLED_TRANSACTION_HASHES_ANNOUNCEMENT + 1;
+        let hashes66 =
+            EthMessage::NewPooledTransactionHashes66(NewPooledTransactionHashes66(vec![
+                B256::ZERO;
+                count
+            ]));
+        let hashes68 = EthMessage::NewPooledTransactionHashes68(NewPooledTransactionHashes68 {
+            types: vec![0; count],
+            sizes: vec![0; count],
+            hashes: vec![B256::ZERO; count],
+        });
+        for (version, message) in [(EthVersion::Eth66, hashes66), (EthVersion::Eth68, hashes68)] {
+            let encoded = encode(ProtocolMessage::<EthNetworkPrimitives>::from(message));
+            let err =
+                ProtocolMessage::<EthNetworkPrimitives>::decode_message(version, &mut &encoded[..])
+                    .unwrap_err();
+            assert!(matches!(err, MessageError::Other(_)));
+        }
+    }
+
+    #[test]
+    fn reject_oversized_announcements_before_decoding() {
+        // the entries are not valid RLP, so only a check of the list header can reject them
+        // with the announcement limit
+        let announcement = |id: EthMessageID, len: usize| {
+            let mut buf = Vec::new();
+            id.encode(&mut buf);
+            Header { list: true, payload_length: len }.encode(&mut buf);
+            buf.resize(buf.len() + len, 0xff);
+            buf
+        };
+
+        for (id, version, len) in [
+            (EthMessageID::NewBlockHashes, EthVersion::Eth68, 43 * 1025),
+            (
+                EthMessageID::NewPooledTransactionHashes,
+                EthVersion::Eth66,
+                33 * (MAX_NEW_POOLED_TRANSACTION_HASHES_ANNOUNCEMENT + 1),
+            ),
+        ] {
+            let encoded = announcement(id, len);
+            let err =
+                ProtocolMessage::<EthNetworkPrimitives>::decode_message(version, &mut &encoded[..])
+                    .unwrap_err();
+            assert!(matches!(err, MessageError::Other(_)), "{id:?}: {err:?}");
+        }
+
+        // eth/68 announcements are bounded by the length of the leading types string
+        let count = MAX_NEW_POOLED_TRANSACTION_HASHES_ANNOUNCEMENT + 1;
+        let mut payload = Vec::new();
+        Header { list: false, payload_length: count }.encode(&mut payload);
+        payload.resize(payload.len() + count, 0xff);
+        let mut encoded = Vec::new();
+        EthMessageID::NewPooledTransactionHashes.encode(&mut encoded);
+        Header { list: true, payload_length: payload.len() }.encode(&mut encoded);
+        encoded.extend_from_slice(&payload);
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &encoded[..],
+        )
+        .unwrap_err();
+        assert!(matches!(err, MessageError::Other(_)));
+    }
+
+    #[test]
+    fn reject_unknown_ids_within_eth_message_space() {
+        // 0x0b is unassigned, but still part of the eth message space
+        let encoded = [0x0b, 0xc0];
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &encoded[..],
+        )
+        .unwrap_err();
+        assert!(matches!(err, MessageError::Invalid(EthVersion::Eth68, EthMessageID::Other(0x0b))));
+
+        // 0x11 is only part of the eth message space since eth/69
+        let range_update = ProtocolMessage::<EthNetworkPrimitives> {
+            message_type: EthMessageID::BlockRangeUpdate,
+            message: EthMessage::BlockRangeUpdate(BlockRangeUpdate {
+                earliest: 1,
+                latest: 2,
+                latest_hash: B256::ZERO,
+            }),
+        };
+        let encoded = encode(range_update.clone());
+        let err = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &encoded[..],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            MessageError::Invalid(EthVersion::Eth68, EthMessageID::BlockRangeUpdate)
+        ));
+        let decoded = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth69,
+            &mut &encoded[..],
+        )
+        .unwrap();
+        assert_eq!(decoded, range_update);
+    }
+
+    #[test]
+    fn decode_many_continues_after_message_errors() {
+        let range_update = ProtocolMessage::<EthNetworkPrimitives> {
+            message_type: EthMessageID::BlockRangeUpdate,
+            message: EthMessage::BlockRangeUpdate(BlockRangeUpdate {
+                earliest: 1,
+                latest: 2,
+                latest_hash: B256::ZERO,
+            }),
+        };
+
+        let mut buf = encode(range_update.clone());
+        // reserved message id
+        buf.extend_from_slice(&[0x0b, 0xc0]);
+        buf.extend(encode(range_update.clone()));
+        // truncated message, the following message can't be located
+        buf.extend_from_slice(&[0x11, 0xc5, 0x01]);
+        buf.extend(encode(range_update.clone()));
+
+        let decoded = ProtocolMessage::<EthNetworkPrimitives>::decode_many(EthVersion::Eth69, &buf)
+            .collect::<Vec<_>>();
+        assert_eq!(decoded.len(), 4);
+        assert_eq!(decoded[0].as_ref().unwrap(), &range_update);
+        assert!(matches!(decoded[1], Err(MessageError::Invalid(_, EthMessageID::Other(0x0b)))));
+        assert_eq!(decoded[2].as_ref().unwrap(), &range_update);
+        assert!(matches!(decoded[3], Err(MessageError::RlpError(Error::InputTooShort))));
+    }
+
     #[test]
     fn custom_message_roundtrip() {
         let custom_payload = vec![1, 2, 3, 4, 5];
@@ -1,9 +1,10 @@
 //! Implements the `GetReceipts` and `Receipts` message types.
 
+use crate::message::MessageError;
 use alloc::vec::Vec;
 use alloy_consensus::{ReceiptWithBloom, RlpDecodableReceipt, RlpEncodableReceipt, TxReceipt};
-use alloy_primitives::B256;
-use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
+use alloy_primitives::{Bloom, B256};
+use alloy_rlp::{Header, RlpDecodableWrapper, RlpEncodableWrapper};
 use derive_more::{Deref, IntoIterator};
 use reth_codecs_derive::add_arbitrary_tests;
 use reth_ethereum_primitives::Receipt;
@@ -93,17 +94,72 @@ impl<T: RlpDecodableReceipt> alloy_rlp::Decodable for Receipts<T> {
     }
 }
 
+/// Custom RLP error returned by [`Receipts69`] decoding if a receipt still carries a logs bloom.
+///
+/// [`Receipts69::decode_message`] reports this as [`MessageError::UnexpectedReceiptBloom`]
+/// instead.
+const UNEXPECTED_RECEIPT_BLOOM: &str = "unexpected logs bloom in eth/69 receipt";
+
 /// Eth/69 receipt response type that removes bloom filters from the protocol.
 ///
 /// This is effectively a subset of [`Receipts`].
-#[derive(
-    Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Deref, IntoIterator,
-)]
+///
+/// Decoding rejects receipts in the bloom-bearing pre-eth/69 layout instead of trying to
+/// interpret the bloom as one of the eth/69 fields.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, Deref, IntoIterator)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[add_arbitrary_tests(rlp)]
 pub struct Receipts69<T = Receipt>(pub Vec<Vec<T>>);
 
+impl<T: alloy_rlp::Decodable> alloy_rlp::Decodable for Receipts69<T> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if has_receipt_bloom(buf)? {
+            return Err(alloy_rlp::Error::Custom(UNEXPECTED_RECEIPT_BLOOM))
+        }
+        alloy_rlp::Decodable::decode(buf).map(Self)
+    }
+}
+
+/// Returns whether any of the encoded receipt lists doesn't use the eth/69 layout,
+/// `[tx-type, status, gas, logs]`.
+///
+/// Only the RLP headers are inspected. Pre-eth/69 receipts are detected: typed receipts are
+/// encoded as a string wrapping the receipt, and legacy receipts carry the bloom as a 256 byte
+/// string field, neither of which can occur in an eth/69 receipt.
+fn has_receipt_bloom(mut buf: &[u8]) -> alloy_rlp::Result<bool> {
+    let mut blocks = Header::decode_bytes(&mut buf, true)?;
+    while !blocks.is_empty() {
+        let mut receipts = Header::decode_bytes(&mut blocks, true)?;
+        while !receipts.is_empty() {
+            if !Header::decode(&mut &receipts[..])?.list {
+                return Ok(true)
+            }
+            let mut fields = Header::decode_bytes(&mut receipts, true)?;
+            while !fields.is_empty() {
+                let field = Header::decode(&mut fields)?;
+                if !field.list && field.payload_length == Bloom::len_bytes() {
+                    return Ok(true)
+                }
+                fields =
+                    fields.get(field.payload_length..).ok_or(alloy_rlp::Error::InputTooShort)?;
+            }
+        }
+    }
+    Ok(false)
+}
+
+impl<T: alloy_rlp::Decodable> Receipts69<T> {
+    /// Like [`Decodable::decode`](alloy_rlp::Decodable::decode), but reports receipts in the
+    /// pre-eth/69 layout as [`MessageError::UnexpectedReceiptBloom`].
+    pub fn decode_message(buf: &mut &[u8]) -> Result<Self, MessageError> {
+        if has_receipt_bloom(buf)? {
+            return Err(MessageError::UnexpectedReceiptBloom)
+        }
+        Ok(alloy_rlp::Decodable::decode(buf).map(Self)?)
+    }
+}
+
 impl<T: TxReceipt> Receipts69<T> {
     /// Encodes all receipts with the bloom filter.
     ///
@@ -342,6 +398,30 @@ mod tests {
         assert_eq!(encoded, data);
     }
 
+    #[test]
+    fn decode_receipts_69_rejects_bloom() {
+        for tx_type in [TxType::Legacy, TxType::Eip1559] {
+            let with_bloom = RequestPair {
+                request_id: 1111,
+                message: Receipts(vec![vec![ReceiptWithBloom {
+                    receipt: Receipt { tx_type, cumulative_gas_used: 1, ..Default::default() },
+                    logs_bloom: Default::default(),
+                }]]),
+            };
+            let data = alloy_rlp::encode(&with_bloom);
+
+            let err = RequestPair::<Receipts69>::decode(&mut &data[..]).unwrap_err();
+            assert_eq!(err, alloy_rlp::Error::Custom(UNEXPECTED_RECEIPT_BLOOM));
+
+            let err = RequestPair::decode_message_with(
+                &mut &data[..],
+                Receipts69::<Receipt>::decode_message,
+            )
+            .unwrap_err();
+            assert!(matches!(err, MessageError::UnexpectedReceiptBloom));
+        }
+    }
+
     #[test]
     fn encode_get_receipts70_inline_shape() {
         let req = RequestPair {
@@ -5,7 +5,9 @@ use alloc::vec::Vec;
 use alloy_consensus::transaction::{PooledTransaction, TxHashRef};
 use alloy_eips::eip7594::Cell;
 use alloy_primitives::{B128, B256};
-use alloy_rlp::{Decodable, RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper};
+use alloy_rlp::{
+    Decodable, Header, RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper,
+};
 use derive_more::{Constructor, Deref, IntoIterator};
 use reth_codecs_derive::add_arbitrary_tests;
 use reth_primitives_traits::InMemorySize;
@@ -82,6 +84,43 @@ impl<T: Decodable + InMemorySize> PooledTransactions<T> {
     }
 }
 
+impl<T: Decodable> PooledTransactions<T> {
+    /// Decodes the RLP list header and returns an iterator that decodes the transactions one at a
+    /// time, so large responses can be processed without buffering all decoded transactions.
+    ///
+    /// `buf` is advanced past the whole list right away. The iterator stops after the first
+    /// transaction that fails to decode. For a response wrapped in a
+    /// [`RequestPair`](crate::message::RequestPair), decode the request id first with
+    /// [`RequestPair::decode_request_id`](crate::message::RequestPair::decode_request_id).
+    pub fn decode_iter<'a>(
+        buf: &mut &'a [u8],
+    ) -> alloy_rlp::Result<impl Iterator<Item = alloy_rlp::Result<T>> + 'a>
+    where
+        T: 'a,
+    {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+
+        let (mut payload, rest) = buf.split_at(header.payload_length);
+        *buf = rest;
+
+        let mut failed = false;
+        Ok(core::iter::from_fn(move || {
+            if failed || payload.is_empty() {
+                return None
+            }
+            let tx = T::decode(&mut payload);
+            failed = tx.is_err();
+            Some(tx)
+        }))
+    }
+}
+
 impl<T: TxHashRef> PooledTransactions<T> {
     /// Returns an iterator over the transaction hashes in this response.
     pub fn hashes(&self) -> impl Iterator<Item = B256> + '_ {
@@ -326,6 +365,33 @@ mod tests {
         assert_eq!(request, expected);
     }
 
+    #[test]
+    fn decode_iter_pooled_transactions_network() {
+        let data = hex!(
+            "f9022980f90225f8650f84832156008287fb94cf7f9e66af820a19257a2108375b180b0ec491678204d2802ca035b7bfeb9ad9ece2cbafaaf8e202e706b4cfaeb233f46198f00b44d4a566a981a0612638fb29427ca33b9a3be2a0a561beecfe0269655be160d35e72d366a6a860b87502f872041a8459682f008459682f0d8252089461815774383099e24810ab832a5b2a5425c154d58829a2241af62c000080c001a059e6b67f48fb32e7e570dfb11e042b5ad2e55e3ce3ce9cd989c7e06e07feeafda0016b83f4f980694ed2eee4d10667242b1f40dc406901b34125b008d334d47469f86b0384773594008398968094d3e8763675e4c425df46cc3b5c0f6cbdac39604687038d7ea4c68000802ba0ce6834447c0a4193c40382e6c57ae33b241379c5418caac9cdc18d786fd12071a03ca3ae86580e94550d7c071e3a02eadb5a77830947c9225165cf9100901bee88f86b01843b9aca00830186a094d3e8763675e4c425df46cc3b5c0f6cbdac3960468702769bb01b2a00802ba0e24d8bd32ad906d6f8b8d7741e08d1959df021698b19ee232feba15361587d0aa05406ad177223213df262cb66ccbb2f46bfdccfdfbbb5ffdda9e2c02d977631daf86b02843b9aca00830186a094d3e8763675e4c425df46cc3b5c0f6cbdac39604687038d7ea4c68000802ba00eb96ca19e8a77102767a41fc85a36afd5c61ccb09911cec5d3e86e193d9c5aea03a456401896b1b6055311536bf00a718568c744d8c1f9df59879e8350220ca18"
+        );
+        let expected = RequestPair::<PooledTransactions>::decode(&mut &data[..]).unwrap();
+
+        let buf = &mut &data[..];
+        let request_id = RequestPair::<PooledTransactions>::decode_request_id(buf).unwrap();
+        assert_eq!(request_id, expected.request_id);
+
+        let txs = PooledTransactions::<PooledTransaction>::decode_iter(buf)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(buf.is_empty());
+        assert_eq!(PooledTransactions(txs), expected.message);
+
+        // decoding stops at the first malformed transaction
+        let mut truncated = Vec::new();
+        alloy_rlp::Header { list: true, payload_length: 3 }.encode(&mut truncated);
+        truncated.extend_from_slice(&[0xc5, 0x01, 0x02]);
+        let results =
+            PooledTransactions::<PooledTransaction>::decode_iter(&mut &truncated[..]).unwrap();
+        assert_eq!(results.map(|tx| tx.is_err()).collect::<Vec<_>>(), vec![true]);
+    }
+
     #[test]
     fn decode_pooled_transactions_network() {
         let data = hex!(
@@ -1,10 +1,10 @@
 //! Support for representing the version of the `eth`
 
-use crate::alloc::string::ToString;
+use crate::{alloc::string::ToString, EthMessageID};
 use alloc::string::String;
 use alloy_rlp::{Decodable, Encodable, Error as RlpError};
 use bytes::BufMut;
-use core::{fmt, str::FromStr};
+use core::{fmt, ops::RangeInclusive, str::FromStr};
 use derive_more::Display;
 use reth_codecs_derive::add_arbitrary_tests;
 
@@ -96,6 +96,32 @@ impl EthVersion {
     pub const fn is_eth69_or_newer(&self) -> bool {
         matches!(self, Self::Eth69 | Self::Eth70 | Self::Eth71 | Self::Eth72)
     }
+
+    /// Returns the message id space this version reserves in the multiplexed capability table.
+    ///
+    /// The range can contain unused ids, see [`Self::supports`] for which ids are valid.
+    pub const fn message_id_range(&self) -> RangeInclusive<u8> {
+        0..=EthMessageID::max(*self)
+    }
+
+    /// Returns true if messages with the given id are valid for this version.
+    ///
+    /// `GetNodeData` and `NodeData` were removed in eth/67, `BlockRangeUpdate` was added in
+    /// eth/69, block access list exchange in eth/71 and cell exchange in eth/72. Ids that don't
+    /// belong to an eth message are never supported.
+    pub const fn supports(&self, id: EthMessageID) -> bool {
+        let version = *self as u8;
+        match id {
+            EthMessageID::GetNodeData | EthMessageID::NodeData => version < Self::Eth67 as u8,
+            EthMessageID::BlockRangeUpdate => version >= Self::Eth69 as u8,
+            EthMessageID::GetBlockAccessLists | EthMessageID::BlockAccessLists => {
+                version >= Self::Eth71 as u8
+            }
+            EthMessageID::GetCells | EthMessageID::Cells => version >= Self::Eth72 as u8,
+            EthMessageID::Other(_) => false,
+            _ => true,
+        }
+    }
 }
 
 /// RLP encodes `EthVersion` as a single byte (66-72).
@@ -246,6 +272,7 @@ impl Decodable for ProtocolVersion {
 #[cfg(test)]
 mod tests {
     use super::EthVersion;
+    use crate::EthMessageID;
     use alloy_rlp::{Decodable, Encodable, Error as RlpError};
     use bytes::BytesMut;
 
@@ -282,6 +309,44 @@ mod tests {
         assert!(EthVersion::Eth72.has_eth68_metadata());
     }
 
+    #[test]
+    fn test_message_id_range() {
+        assert_eq!(EthVersion::Eth66.message_id_range(), 0..=0x10);
+        assert_eq!(EthVersion::Eth67.message_id_range(), 0..=0x10);
+        assert_eq!(EthVersion::Eth68.message_id_range(), 0..=0x10);
+        assert_eq!(EthVersion::Eth69.message_id_range(), 0..=0x11);
+        assert_eq!(EthVersion::Eth71.message_id_range(), 0..=0x13);
+        assert_eq!(EthVersion::Eth72.message_id_range(), 0..=0x15);
+    }
+
+    #[test]
+    fn test_supports_message_ids() {
+        // node data was removed in eth/67
+        assert!(EthVersion::Eth66.supports(EthMessageID::GetNodeData));
+        assert!(EthVersion::Eth66.supports(EthMessageID::NodeData));
+        assert!(!EthVersion::Eth67.supports(EthMessageID::GetNodeData));
+        assert!(!EthVersion::Eth67.supports(EthMessageID::NodeData));
+        assert!(!EthVersion::Eth69.supports(EthMessageID::NodeData));
+
+        // block range updates were added in eth/69
+        assert!(!EthVersion::Eth66.supports(EthMessageID::BlockRangeUpdate));
+        assert!(!EthVersion::Eth68.supports(EthMessageID::BlockRangeUpdate));
+        assert!(EthVersion::Eth69.supports(EthMessageID::BlockRangeUpdate));
+
+        assert!(!EthVersion::Eth70.supports(EthMessageID::GetBlockAccessLists));
+        assert!(EthVersion::Eth71.supports(EthMessageID::BlockAccessLists));
+        assert!(!EthVersion::Eth71.supports(EthMessageID::Cells));
+        assert!(EthVersion::Eth72.supports(EthMessageID::GetCells));
+
+        for version in [EthVersion::Eth66, EthVersion::Eth67, EthVersion::Eth68, EthVersion::Eth69]
+        {
+            assert!(version.supports(EthMessageID::Status));
+            assert!(version.supports(EthMessageID::Receipts));
+            assert!(!version.supports(EthMessageID::Other(0x0b)));
+            assert!(version.message_id_range().contains(&EthMessageID::Receipts.to_u8()));
+        }
+    }
+
     #[test]
     fn test_eth_version_rlp_encode() {
         let versions = [
@@ -157,9 +157,10 @@ where
             return Err(EthStreamError::UnsupportedMessage { message_id: id });
         }
 
-        let msg = match ProtocolMessage::decode_message_with_tx_memory_budget(
+        let msg = match ProtocolMessage::decode_message_with_limits(
             self.version,
             &mut bytes.as_ref(),
+            self.max_message_size,
             self.max_message_size * TX_MEMORY_BUDGET_MULTIPLIER,
         ) {
             Ok(m) => m,
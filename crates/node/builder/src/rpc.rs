@@ -43,7 +43,7 @@ use reth_rpc_builder::{
     RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
-use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
+use reth_rpc_eth_types::{cache::cache_new_blocks_task_with_debounce, EthConfig, EthStateCache};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, info};
 use std::{
@@ -1103,16 +1103,21 @@ where
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
+        let mut cache_config = config.rpc.eth_config().cache;
+        if config.rpc.rpc_state_cache.persist_headers {
+            cache_config.header_persist_path = Some(config.datadir().rpc_cache_headers());
+        }
+        let new_blocks_debounce = cache_config.new_blocks_debounce.unwrap_or_default();
         let cache = EthStateCache::spawn_with(
             node.provider().clone(),
-            config.rpc.eth_config().cache,
+            cache_config,
             node.task_executor().clone(),
         );
 
         let new_canonical_blocks = node.provider().canonical_state_stream();
         let c = cache.clone();
         node.task_executor().spawn_critical_task("cache canonical blocks task", async move {
-            cache_new_blocks_task(c, new_canonical_blocks).await;
+            cache_new_blocks_task_with_debounce(c, new_canonical_blocks, new_blocks_debounce).await;
         });
 
         let eth_config = config.rpc.eth_config().max_batch_size(config.txpool.max_batch_size());
@@ -1,5 +1,7 @@
 //! clap [Args](clap::Args) for engine purposes
 
+#[cfg(feature = "trie-debug")]
+use alloy_primitives::B256;
 use clap::{
     builder::{RangedU64ValueParser, Resettable},
     Args,
@@ -54,6 +56,7 @@ pub struct DefaultEngineValues {
     suppress_persistence_during_build: bool,
     bal_parallel_execution_disabled: bool,
     bal_parallel_state_root_disabled: bool,
+    proof_result_timeout: Option<Duration>,
 }
 
 impl DefaultEngineValues {
@@ -243,6 +246,12 @@ impl DefaultEngineValues {
         self.bal_parallel_state_root_disabled = v;
         self
     }
+
+    /// Set the default proof result timeout.
+    pub const fn with_proof_result_timeout(mut self, v: Option<Duration>) -> Self {
+        self.proof_result_timeout = v;
+        self
+    }
 }
 
 impl Default for DefaultEngineValues {
@@ -277,6 +286,7 @@ impl Default for DefaultEngineValues {
             suppress_persistence_during_build: false,
             bal_parallel_execution_disabled: false,
             bal_parallel_state_root_disabled: false,
+            proof_result_timeout: None,
         }
     }
 }
@@ -521,6 +531,16 @@ pub struct EngineArgs {
     #[arg(long = "engine.disable-bal-parallel-state-root", default_value_t = DefaultEngineValues::get_global().bal_parallel_state_root_disabled)]
     pub bal_parallel_state_root_disabled: bool,
 
+    /// Configure how long the sparse trie task waits for a proof result before failing the
+    /// state root task.
+    ///
+    /// Guards against proof jobs that die without reporting a result. This should be generous,
+    /// since slow proofs are not an error.
+    ///
+    /// When not set, the task waits indefinitely (default).
+    #[arg(long = "engine.proof-result-timeout", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION", default_value = Resettable::from(DefaultEngineValues::get_global().proof_result_timeout.map(|timeout| format_duration_as_secs_or_ms(timeout).into())))]
+    pub proof_result_timeout: Option<Duration>,
+
     /// Disable BAL (Block Access List) storage prefetch IO during prewarming. When set, BAL
     /// storage slots are not read into the execution cache.
     #[arg(long = "engine.disable-bal-batch-io", default_value_t = false)]
@@ -538,6 +558,14 @@ pub struct EngineArgs {
         value_parser = humantime::parse_duration,
     )]
     pub proof_jitter: Option<Duration>,
+
+    /// Recompute the subtree root of these hashed account addresses from a fresh multiproof
+    /// before each state root task and check it against the parent state root (trie-debug only).
+    ///
+    /// --engine.subtree-check-account 0x...
+    #[cfg(feature = "trie-debug")]
+    #[arg(long = "engine.subtree-check-account", value_name = "HASHED_ADDRESS")]
+    pub subtree_check_accounts: Vec<B256>,
 }
 
 #[allow(deprecated)]
@@ -573,6 +601,7 @@ impl Default for EngineArgs {
             suppress_persistence_during_build,
             bal_parallel_execution_disabled,
             bal_parallel_state_root_disabled,
+            proof_result_timeout,
         } = DefaultEngineValues::get_global().clone();
         Self {
             persistence_threshold,
@@ -611,9 +640,12 @@ impl Default for EngineArgs {
             suppress_persistence_during_build,
             bal_parallel_execution_disabled,
             bal_parallel_state_root_disabled,
+            proof_result_timeout,
             disable_bal_batch_io: false,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
+            #[cfg(feature = "trie-debug")]
+            subtree_check_accounts: Vec::new(),
         }
     }
 }
@@ -699,9 +731,12 @@ impl EngineArgs {
             .with_suppress_persistence_during_build(self.suppress_persistence_during_build)
             .without_bal_parallel_execution(self.bal_parallel_execution_disabled)
             .without_bal_parallel_state_root(self.bal_parallel_state_root_disabled)
+            .with_proof_result_timeout(self.proof_result_timeout)
             .without_bal_batch_io(self.disable_bal_batch_io);
         #[cfg(feature = "trie-debug")]
-        let config = config.with_proof_jitter(self.proof_jitter);
+        let config = config
+            .with_proof_jitter(self.proof_jitter)
+            .with_subtree_check_accounts(self.subtree_check_accounts.clone());
         config
     }
 }
@@ -846,9 +881,12 @@ mod tests {
             suppress_persistence_during_build: false,
             bal_parallel_execution_disabled: true,
             bal_parallel_state_root_disabled: true,
+            proof_result_timeout: Some(Duration::from_secs(60)),
             disable_bal_batch_io: true,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
+            #[cfg(feature = "trie-debug")]
+            subtree_check_accounts: Vec::new(),
         };
 
         let parsed_args = CommandParser::<EngineArgs>::parse_from([
@@ -889,6 +927,8 @@ mod tests {
             "2s",
             "--engine.disable-bal-parallel-execution",
             "--engine.disable-bal-parallel-state-root",
+            "--engine.proof-result-timeout",
+            "60",
             "--engine.disable-bal-batch-io",
         ])
         .args;
@@ -1107,6 +1107,8 @@ mod tests {
                 max_bals: 1000,
                 max_concurrent_db_requests: 512,
                 max_cached_tx_hashes: 30_000,
+                max_queued_consumers: 1024,
+                ..Default::default()
             },
             gas_price_oracle: GasPriceOracleArgs {
                 blocks: 20,
@@ -1,8 +1,12 @@
 use clap::Args;
+use humantime::parse_duration;
+use reth_rpc_eth_types::{CachePanicPolicy, EvictionPolicy};
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BAL_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS,
-    DEFAULT_HEADER_CACHE_MAX_LEN, DEFAULT_MAX_CACHED_TX_HASHES, DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_BAL_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_COLD_BLOCK_CACHE_MAX_LEN,
+    DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN, DEFAULT_MAX_CACHED_TX_HASHES,
+    DEFAULT_MAX_QUEUED_CONSUMERS, DEFAULT_RECEIPT_CACHE_MAX_LEN,
 };
+use std::time::Duration;
 
 /// Parameters to configure RPC state cache.
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -50,6 +54,66 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_MAX_CACHED_TX_HASHES,
     )]
     pub max_cached_tx_hashes: u32,
+
+    /// Max number of requests waiting on the same in-flight cache fetch.
+    #[arg(
+        long = "rpc-cache.max-queued-consumers",
+        default_value_t = DEFAULT_MAX_QUEUED_CONSUMERS,
+    )]
+    pub max_queued_consumers: usize,
+
+    /// Max number of entries in the index from canonical block number to block hash.
+    ///
+    /// Defaults to the max number of headers in cache.
+    #[arg(long = "rpc-cache.max-number-index-entries", value_name = "ENTRIES")]
+    pub max_number_index_entries: Option<u32>,
+
+    /// Max number of blocks, and their receipts, in the low priority cache used by backfills.
+    #[arg(
+        long = "rpc-cache.max-cold-blocks",
+        default_value_t = DEFAULT_COLD_BLOCK_CACHE_MAX_LEN,
+    )]
+    pub max_cold_blocks: u32,
+
+    /// Max total size in bytes of the cached blocks, receipts and headers.
+    ///
+    /// Disabled by default, only the per cache lengths are enforced.
+    #[arg(long = "rpc-cache.max-bytes", value_name = "BYTES")]
+    pub total_max_bytes: Option<usize>,
+
+    /// Which entries the caches evict when they are full: lru or lfu.
+    #[arg(long = "rpc-cache.eviction-policy", default_value = "lru", value_name = "POLICY")]
+    pub eviction_policy: EvictionPolicy,
+
+    /// How requests are answered if the fetch serving them panicked: error or retry-once.
+    #[arg(long = "rpc-cache.panic-policy", default_value = "error", value_name = "POLICY")]
+    pub panic_policy: CachePanicPolicy,
+
+    /// Upper bound for a random delay before each database fetch, e.g. 10ms.
+    ///
+    /// Parses strings using [`humantime::parse_duration`].
+    #[arg(long = "rpc-cache.fetch-jitter", value_parser = parse_duration, value_name = "DURATION")]
+    pub fetch_jitter: Option<Duration>,
+
+    /// Delay before newly committed blocks are cached, e.g. 500ms.
+    ///
+    /// Blocks that are reverted within the delay are never cached.
+    ///
+    /// Parses strings using [`humantime::parse_duration`].
+    #[arg(
+        long = "rpc-cache.new-blocks-debounce",
+        value_parser = parse_duration,
+        value_name = "DURATION"
+    )]
+    pub new_blocks_debounce: Option<Duration>,
+
+    /// Skip re-inserting blocks and receipts that are already cached with equal data.
+    #[arg(long = "rpc-cache.skip-duplicate-inserts")]
+    pub skip_duplicate_inserts: bool,
+
+    /// Persist the headers cache in the data directory, so it is warm after a restart.
+    #[arg(long = "rpc-cache.persist-headers")]
+    pub persist_headers: bool,
 }
 
 impl RpcStateCacheArgs {
@@ -71,6 +135,16 @@ impl Default for RpcStateCacheArgs {
             max_bals: DEFAULT_BAL_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
             max_cached_tx_hashes: DEFAULT_MAX_CACHED_TX_HASHES,
+            max_queued_consumers: DEFAULT_MAX_QUEUED_CONSUMERS,
+            max_number_index_entries: None,
+            max_cold_blocks: DEFAULT_COLD_BLOCK_CACHE_MAX_LEN,
+            total_max_bytes: None,
+            eviction_policy: EvictionPolicy::Lru,
+            panic_policy: CachePanicPolicy::Error,
+            fetch_jitter: None,
+            new_blocks_debounce: None,
+            skip_duplicate_inserts: false,
+            persist_headers: false,
         }
     }
 }
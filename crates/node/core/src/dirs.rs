@@ -353,6 +353,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the persisted RPC headers cache file
+    ///
+    /// `<DIR>/<CHAIN_ID>/rpc-cache-headers.rlp`
+    pub fn rpc_cache_headers(&self) -> PathBuf {
+        self.data_dir().join("rpc-cache-headers.rlp")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -129,6 +129,17 @@ impl RethRpcServerConfig for RpcServerArgs {
             max_bals: self.rpc_state_cache.max_bals,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
             max_cached_tx_hashes: self.rpc_state_cache.max_cached_tx_hashes,
+            max_number_index_entries: self.rpc_state_cache.max_number_index_entries,
+            max_queued_consumers: self.rpc_state_cache.max_queued_consumers,
+            fetch_jitter: self.rpc_state_cache.fetch_jitter,
+            panic_policy: self.rpc_state_cache.panic_policy,
+            total_max_bytes: self.rpc_state_cache.total_max_bytes,
+            eviction_policy: self.rpc_state_cache.eviction_policy,
+            new_blocks_debounce: self.rpc_state_cache.new_blocks_debounce,
+            skip_duplicate_inserts: self.rpc_state_cache.skip_duplicate_inserts,
+            max_cold_blocks: self.rpc_state_cache.max_cold_blocks,
+            // the file lives in the data directory, which the node builder resolves
+            header_persist_path: None,
         }
     }
 
@@ -268,9 +279,14 @@ impl RethRpcServerConfig for RpcServerArgs {
 mod tests {
     use clap::{Args, Parser};
     use reth_node_core::args::RpcServerArgs;
-    use reth_rpc_eth_types::RPC_DEFAULT_GAS_CAP;
+    use reth_rpc_eth_types::{
+        CachePanicPolicy, EthStateCacheConfig, EvictionPolicy, RPC_DEFAULT_GAS_CAP,
+    };
     use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
-    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+    use std::{
+        net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+        time::Duration,
+    };
 
     use crate::config::RethRpcServerConfig;
 
@@ -422,4 +438,47 @@ mod tests {
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
     }
+
+    #[test]
+    fn test_state_cache_config() {
+        let config = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args.state_cache_config();
+        assert_eq!(config, EthStateCacheConfig::default());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-cache.max-number-index-entries",
+            "64",
+            "--rpc-cache.max-cold-blocks",
+            "10",
+            "--rpc-cache.max-bytes",
+            "1048576",
+            "--rpc-cache.eviction-policy",
+            "lfu",
+            "--rpc-cache.panic-policy",
+            "retry-once",
+            "--rpc-cache.fetch-jitter",
+            "10ms",
+            "--rpc-cache.new-blocks-debounce",
+            "500ms",
+            "--rpc-cache.skip-duplicate-inserts",
+        ])
+        .args;
+
+        let config = args.state_cache_config();
+        assert_eq!(config.max_number_index_entries, Some(64));
+        assert_eq!(config.max_cold_blocks, 10);
+        assert_eq!(config.total_max_bytes, Some(1024 * 1024));
+        assert_eq!(config.eviction_policy, EvictionPolicy::Lfu);
+        assert_eq!(config.panic_policy, CachePanicPolicy::RetryOnce);
+        assert_eq!(config.fetch_jitter, Some(Duration::from_millis(10)));
+        assert_eq!(config.new_blocks_debounce, Some(Duration::from_millis(500)));
+        assert!(config.skip_duplicate_inserts);
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc-cache.eviction-policy",
+            "fifo",
+        ]);
+        assert!(args.is_err());
+    }
 }
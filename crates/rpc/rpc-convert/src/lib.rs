@@ -14,7 +14,11 @@ mod rpc;
 pub mod transaction;
 
 pub use rpc::*;
-pub use transaction::{RpcConvert, RpcConverter, TransactionConversionError};
+pub use transaction::{
+    validate_simulate_request, verify_recovered_signer, EthTxCompat, MinimalTransaction,
+    RpcConvert, RpcConverter, SimulateValidationError, TransactionConversionError,
+    TransactionInfoExt, TryFromRpcTx,
+};
 
 pub use alloy_evm::rpc::{CallFees, CallFeesError, EthTxEnvError, TryIntoTxEnv};
 
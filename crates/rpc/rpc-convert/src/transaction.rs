@@ -2,14 +2,19 @@
 use crate::{
     RpcHeader, RpcReceipt, RpcTransaction, RpcTxReq, RpcTypes, SignableTxRequest, TryIntoTxEnv,
 };
-use alloy_consensus::{error::ValueError, transaction::Recovered};
-use alloy_primitives::Address;
-use alloy_rpc_types_eth::TransactionInfo;
+use alloy_consensus::{
+    error::ValueError, transaction::Recovered, BlockHeader, EthereumTxEnvelope, TxEip4844,
+    TxEip4844Variant, TxType,
+};
+use alloy_network::TransactionResponse;
+use alloy_primitives::{Address, TxHash, B256};
+use alloy_rpc_types_eth::{AccessList, TransactionInfo, TransactionRequest};
 use core::error;
 use dyn_clone::DynClone;
 use reth_evm::{BlockEnvFor, ConfigureEvm, EvmEnvFor, SpecFor, TxEnvFor};
 use reth_primitives_traits::{
-    BlockTy, HeaderTy, NodePrimitives, SealedBlock, SealedHeader, SealedHeaderFor, TransactionMeta,
+    transaction::signed::RecoveryError, Block, BlockBody, BlockTy, HeaderTy, NodePrimitives,
+    RecoveredBlock, SealedBlock, SealedHeader, SealedHeaderFor, SignedTransaction, TransactionMeta,
     TxTy,
 };
 use reth_rpc_traits::{FromConsensusHeader, FromConsensusTx, TryIntoSimTx, TxInfoMapper};
@@ -56,6 +61,83 @@ pub trait ReceiptConverter<N: NodePrimitives>: Debug + 'static {
     }
 }
 
+/// Extension trait for constructing [`TransactionInfo`] from a block.
+pub trait TransactionInfoExt: Sized {
+    /// Returns the [`TransactionInfo`] of the transaction at `index` in `block`.
+    ///
+    /// Populates the block hash, number, timestamp and base fee from the block header, so that
+    /// the effective gas price can be derived consistently.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    fn from_block_and_index<B: Block>(block: &RecoveredBlock<B>, index: usize) -> Option<Self> {
+        let tx = block.body().transactions().get(index)?;
+        Some(Self::from_block_tx(block, *tx.tx_hash(), index))
+    }
+
+    /// Returns the [`TransactionInfo`] of the transaction with hash `tx_hash` at `index` in
+    /// `block`.
+    ///
+    /// Like [`TransactionInfoExt::from_block_and_index`], for callers that already looked up the
+    /// transaction.
+    fn from_block_tx<B: Block>(block: &RecoveredBlock<B>, tx_hash: TxHash, index: usize) -> Self;
+}
+
+impl TransactionInfoExt for TransactionInfo {
+    fn from_block_tx<B: Block>(block: &RecoveredBlock<B>, tx_hash: TxHash, index: usize) -> Self {
+        let header = block.header();
+
+        Self {
+            hash: Some(tx_hash),
+            block_hash: Some(block.hash()),
+            block_number: Some(header.number()),
+            block_timestamp: Some(header.timestamp()),
+            base_fee: header.base_fee_per_gas(),
+            index: Some(index as u64),
+        }
+    }
+}
+
+/// The identity of a transaction and its position in the chain, without its signed fields.
+///
+/// Responses that only reference transactions, like log or trace context, can use this instead
+/// of the full rpc transaction, see [`RpcConvert::fill_minimal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinimalTransaction {
+    /// Transaction hash.
+    pub hash: TxHash,
+    /// Sender of the transaction.
+    pub from: Address,
+    /// Recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// Nonce of the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Hash of the block the transaction was included in, `None` if pending.
+    pub block_hash: Option<B256>,
+    /// Number of the block the transaction was included in, `None` if pending.
+    #[serde(with = "alloy_serde::quantity::opt")]
+    pub block_number: Option<u64>,
+    /// Index of the transaction in its block, `None` if pending.
+    #[serde(with = "alloy_serde::quantity::opt")]
+    pub transaction_index: Option<u64>,
+}
+
+impl MinimalTransaction {
+    /// Creates the minimal form of an rpc transaction response.
+    pub fn from_rpc_tx<T: TransactionResponse>(tx: &T) -> Self {
+        Self {
+            hash: tx.tx_hash(),
+            from: tx.from(),
+            to: tx.to(),
+            nonce: tx.nonce(),
+            block_hash: tx.block_hash(),
+            block_number: tx.block_number(),
+            transaction_index: tx.transaction_index(),
+        }
+    }
+}
+
 /// A type that knows how to convert a consensus header into an RPC header.
 pub trait HeaderConverter<Consensus, Rpc>: Send + Sync + Unpin + Clone + 'static {
     /// An associated RPC conversion error.
@@ -145,6 +227,79 @@ pub trait RpcConvert: Send + Sync + Unpin + Debug + DynClone + 'static {
         tx_info: TransactionInfo,
     ) -> Result<RpcTransaction<Self::Network>, Self::Error>;
 
+    /// Create a new rpc transaction result for a mined transaction like [`RpcConvert::fill`], using
+    /// `base_fee` instead of [`TransactionInfo::base_fee`] to derive the effective gas price.
+    ///
+    /// This is for callers that resolve the base fee of the including block themselves, so the
+    /// source of the effective gas price is explicit. By default `base_fee` is ignored and this is
+    /// equivalent to [`RpcConvert::fill`], for networks that derive the effective gas price
+    /// otherwise.
+    fn fill_with_base_fee(
+        &self,
+        tx: Recovered<TxTy<Self::Primitives>>,
+        tx_info: TransactionInfo,
+        _base_fee: Option<u64>,
+    ) -> Result<RpcTransaction<Self::Network>, Self::Error> {
+        self.fill(tx, tx_info)
+    }
+
+    /// Create the [`MinimalTransaction`] form of a transaction, for responses that only need the
+    /// transaction's identity and block context.
+    ///
+    /// By default this is derived from the output of [`RpcConvert::fill`], so it matches the
+    /// network's rpc transaction. Implementations can override it to skip building the full rpc
+    /// transaction, as long as the result stays the same.
+    fn fill_minimal(
+        &self,
+        tx: Recovered<TxTy<Self::Primitives>>,
+        tx_info: TransactionInfo,
+    ) -> Result<MinimalTransaction, Self::Error> {
+        self.fill(tx, tx_info).map(|tx| MinimalTransaction::from_rpc_tx(&tx))
+    }
+
+    /// Create a new rpc transaction result for the transaction at `tx_index` in `block`.
+    ///
+    /// The [`TransactionInfo`] is derived from the block, see
+    /// [`TransactionInfoExt::from_block_tx`].
+    ///
+    /// Returns [`TransactionConversionError::TxIndexOutOfBounds`] if the block has no transaction
+    /// at `tx_index`.
+    fn fill_from_block(
+        &self,
+        tx_index: usize,
+        block: &RecoveredBlock<BlockTy<Self::Primitives>>,
+    ) -> Result<RpcTransaction<Self::Network>, Self::Error>
+    where
+        Self::Error: From<TransactionConversionError>,
+    {
+        let (signer, tx) = block.transactions_with_sender().nth(tx_index).ok_or_else(|| {
+            TransactionConversionError::TxIndexOutOfBounds {
+                index: tx_index,
+                len: block.body().transaction_count(),
+            }
+        })?;
+        let tx_info = TransactionInfo::from_block_tx(block, *tx.tx_hash(), tx_index);
+        self.fill(Recovered::new_unchecked(tx.clone(), *signer), tx_info)
+    }
+
+    /// Converts an rpc transaction response back into the recovered consensus transaction.
+    ///
+    /// This is the inverse of [`RpcConvert::fill`]: converting the output of `fill` yields the
+    /// original transaction and signer. The conversion is done by [`TryFromRpcTx`], which checks
+    /// the sender of the response against the signer recovered from the signature. Returns an
+    /// error if the response cannot be converted losslessly.
+    fn try_into_recovered(
+        &self,
+        rpc_tx: RpcTransaction<Self::Network>,
+    ) -> Result<Recovered<TxTy<Self::Primitives>>, Self::Error>
+    where
+        TxTy<Self::Primitives>: TryFromRpcTx<RpcTransaction<Self::Network>>,
+        Self::Error: From<TransactionConversionError>,
+    {
+        TxTy::<Self::Primitives>::try_from_rpc_tx(rpc_tx)
+            .map_err(|err| Self::Error::from(Into::<TransactionConversionError>::into(err)))
+    }
+
     /// Builds a fake transaction from a transaction request for inclusion into block built in
     /// `eth_simulateV1`.
     fn build_simulate_v1_transaction(
@@ -152,6 +307,22 @@ pub trait RpcConvert: Send + Sync + Unpin + Debug + DynClone + 'static {
         request: RpcTxReq<Self::Network>,
     ) -> Result<TxTy<Self::Primitives>, Self::Error>;
 
+    /// Builds a fake transaction like [`RpcConvert::build_simulate_v1_transaction`], applying the
+    /// given EIP-2930 `access_list` to the request first.
+    ///
+    /// An empty `access_list` leaves the request untouched. By default a non-empty list is applied
+    /// as is, [`RpcConverter`] rejects it if the built transaction can't carry an access list.
+    fn build_simulate_v1_transaction_with_access_list(
+        &self,
+        mut request: RpcTxReq<Self::Network>,
+        access_list: AccessList,
+    ) -> Result<TxTy<Self::Primitives>, Self::Error> {
+        if !access_list.is_empty() {
+            request.as_mut().access_list = Some(access_list);
+        }
+        self.build_simulate_v1_transaction(request)
+    }
+
     /// Creates a transaction environment for execution based on `request` with corresponding
     /// `cfg_env` and `block_env`.
     fn tx_env(
@@ -246,6 +417,60 @@ where
     }
 }
 
+/// Converts an RPC transaction response `RpcTx` back into a recovered consensus transaction.
+///
+/// This is the inverse of [`FromConsensusTx`]. Implementations should only succeed if the
+/// conversion is lossless, that is converting the result back with [`FromConsensusTx`] yields the
+/// same `RpcTx`. The sender of `RpcTx` must not be trusted, it has to match the signer recovered
+/// from the signature, see [`verify_recovered_signer`].
+///
+/// There are two implementations provided:
+/// * Identity, for RPC transactions that wrap `Self` directly.
+/// * From the default Ethereum RPC transaction, that wraps a [`TxEip4844Variant`], into an
+///   [`EthereumTxEnvelope`] without sidecars.
+pub trait TryFromRpcTx<RpcTx>: Sized {
+    /// An associated error that can occur during the conversion.
+    type Err: Error + Into<TransactionConversionError>;
+
+    /// Performs the conversion of `rpc_tx` into the recovered `Self`.
+    fn try_from_rpc_tx(rpc_tx: RpcTx) -> Result<Recovered<Self>, Self::Err>;
+}
+
+impl<T: SignedTransaction> TryFromRpcTx<alloy_rpc_types_eth::Transaction<T>> for T {
+    type Err = TransactionConversionError;
+
+    fn try_from_rpc_tx(
+        rpc_tx: alloy_rpc_types_eth::Transaction<T>,
+    ) -> Result<Recovered<Self>, Self::Err> {
+        verify_recovered_signer(rpc_tx.into_recovered())
+    }
+}
+
+impl<T> TryFromRpcTx<alloy_rpc_types_eth::Transaction<EthereumTxEnvelope<TxEip4844Variant<T>>>>
+    for EthereumTxEnvelope<TxEip4844>
+{
+    type Err = TransactionConversionError;
+
+    fn try_from_rpc_tx(
+        rpc_tx: alloy_rpc_types_eth::Transaction<EthereumTxEnvelope<TxEip4844Variant<T>>>,
+    ) -> Result<Recovered<Self>, Self::Err> {
+        verify_recovered_signer(rpc_tx.into_recovered().map(|tx| tx.map_eip4844(Into::into)))
+    }
+}
+
+/// Returns `tx` if its signer is the one recovered from the transaction's signature.
+///
+/// Returns [`TransactionConversionError::SignerMismatch`] otherwise.
+pub fn verify_recovered_signer<T: SignedTransaction>(
+    tx: Recovered<T>,
+) -> Result<Recovered<T>, TransactionConversionError> {
+    let recovered = tx.inner().recover_signer()?;
+    if recovered != tx.signer() {
+        return Err(TransactionConversionError::SignerMismatch { from: tx.signer(), recovered })
+    }
+    Ok(tx)
+}
+
 /// Converts `TxReq` into `SimTx`.
 ///
 /// Where:
@@ -295,6 +520,46 @@ where
     }
 }
 
+/// A ready-made [`RpcTxConverter`] and [`SimTxConverter`] for the canonical Ethereum types.
+///
+/// Converts consensus transactions into [`alloy_rpc_types_eth::Transaction`] and builds simulated
+/// transactions from a [`TransactionRequest`]. It doesn't require any trait implementations on
+/// the node types and can be used as a reference for custom conversions.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct EthTxCompat;
+
+impl<T, Tx> RpcTxConverter<T, alloy_rpc_types_eth::Transaction<Tx>, TransactionInfo> for EthTxCompat
+where
+    T: Into<Tx>,
+    Tx: alloy_consensus::Transaction,
+{
+    type Err = Infallible;
+
+    fn convert_rpc_tx(
+        &self,
+        tx: T,
+        signer: Address,
+        tx_info: TransactionInfo,
+    ) -> Result<alloy_rpc_types_eth::Transaction<Tx>, Self::Err> {
+        Ok(alloy_rpc_types_eth::Transaction::from_transaction(
+            Recovered::new_unchecked(tx.into(), signer),
+            tx_info,
+        ))
+    }
+}
+
+impl SimTxConverter<TransactionRequest, EthereumTxEnvelope<TxEip4844>> for EthTxCompat {
+    type Err = ValueError<TransactionRequest>;
+
+    fn convert_sim_tx(
+        &self,
+        tx_req: TransactionRequest,
+    ) -> Result<EthereumTxEnvelope<TxEip4844>, Self::Err> {
+        tx_req.build_typed_simulate_transaction()
+    }
+}
+
 /// Converts `TxReq` into `TxEnv`.
 ///
 /// Where:
@@ -374,10 +639,70 @@ pub enum TransactionConversionError {
     #[error("Failed to convert transaction into RPC response: {0}")]
     FromTxReq(String),
 
+    /// The transaction built from an `eth_simulateV1` request can't carry its access list.
+    #[error("access lists are not supported by the simulated transaction type")]
+    AccessListNotSupported,
+
+    /// The block has no transaction at the requested index.
+    #[error("transaction index {index} out of bounds for block with {len} transactions")]
+    TxIndexOutOfBounds {
+        /// The requested transaction index.
+        index: usize,
+        /// Number of transactions in the block.
+        len: usize,
+    },
+
+    /// The signer of an RPC transaction response can't be recovered from its signature.
+    #[error(transparent)]
+    InvalidSignature(#[from] RecoveryError),
+
+    /// The sender of an RPC transaction response is not the signer of the transaction.
+    #[error("transaction sender {from} does not match recovered signer {recovered}")]
+    SignerMismatch {
+        /// The sender of the RPC transaction response.
+        from: Address,
+        /// The signer recovered from the transaction's signature.
+        recovered: Address,
+    },
+
     /// Other conversion errors.
     #[error("{0}")]
     Other(String),
 }
+
+/// Describes which fields of an `eth_simulateV1` transaction request are invalid.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SimulateValidationError {
+    /// Both the legacy `gasPrice` and EIP-1559 fee fields are set.
+    #[error("both gasPrice and (maxFeePerGas or maxPriorityFeePerGas) specified")]
+    ConflictingFeeFields,
+    /// Fields required to build a transaction of the preferred type are missing.
+    #[error("missing required fields for {tx_type:?} transaction: {}", .missing.join(", "))]
+    MissingFields {
+        /// The transaction type the request resolves to.
+        tx_type: TxType,
+        /// Names of the missing fields.
+        missing: Vec<&'static str>,
+    },
+}
+
+/// Validates that `request` can be built into a transaction for `eth_simulateV1`.
+///
+/// Returns a [`SimulateValidationError`] describing the first problem found.
+pub fn validate_simulate_request(
+    request: &TransactionRequest,
+) -> Result<(), SimulateValidationError> {
+    if request.gas_price.is_some() && request.has_eip1559_fields() {
+        return Err(SimulateValidationError::ConflictingFeeFields)
+    }
+
+    if let Err((tx_type, missing)) = request.missing_keys() {
+        return Err(SimulateValidationError::MissingFields { tx_type, missing })
+    }
+
+    Ok(())
+}
+
 /// Generic RPC response object converter for `Evm` and network `Network`.
 ///
 /// The main purpose of this struct is to provide an implementation of [`RpcConvert`] for generic
@@ -715,6 +1040,15 @@ where
         self.rpc_tx_converter.convert_rpc_tx(tx, signer, tx_info).map_err(Into::into)
     }
 
+    fn fill_with_base_fee(
+        &self,
+        tx: Recovered<TxTy<N>>,
+        tx_info: TransactionInfo,
+        base_fee: Option<u64>,
+    ) -> Result<Network::TransactionResponse, Self::Error> {
+        self.fill(tx, TransactionInfo { base_fee, ..tx_info })
+    }
+
     fn build_simulate_v1_transaction(
         &self,
         request: RpcTxReq<Network>,
@@ -725,6 +1059,24 @@ where
             .map_err(|e| TransactionConversionError::FromTxReq(e.to_string()))?)
     }
 
+    fn build_simulate_v1_transaction_with_access_list(
+        &self,
+        mut request: RpcTxReq<Network>,
+        access_list: AccessList,
+    ) -> Result<TxTy<N>, Self::Error> {
+        if access_list.is_empty() {
+            return self.build_simulate_v1_transaction(request)
+        }
+
+        request.as_mut().access_list = Some(access_list);
+        let tx = self.build_simulate_v1_transaction(request)?;
+        if alloy_consensus::Transaction::access_list(&tx).is_none() {
+            return Err(TransactionConversionError::AccessListNotSupported.into())
+        }
+
+        Ok(tx)
+    }
+
     fn tx_env(
         &self,
         request: RpcTxReq<Network>,
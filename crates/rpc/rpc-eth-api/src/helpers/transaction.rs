@@ -8,7 +8,7 @@ use crate::{
     RpcTransaction,
 };
 use alloy_consensus::{
-    transaction::{SignerRecoverable, TransactionMeta, TxHashRef},
+    transaction::{SignerRecoverable, TransactionMeta},
     BlockHeader, Transaction,
 };
 use alloy_dyn_abi::TypedData;
@@ -21,7 +21,9 @@ use reth_chain_state::CanonStateSubscriptions;
 use reth_primitives_traits::{
     BlockBody, Recovered, RecoveredBlock, SignedTransaction, TxTy, WithEncoded,
 };
-use reth_rpc_convert::{transaction::RpcConvert, RpcTxReq, TransactionConversionError};
+use reth_rpc_convert::{
+    transaction::RpcConvert, RpcTxReq, TransactionConversionError, TransactionInfoExt,
+};
 use reth_rpc_eth_types::{
     block::convert_transaction_receipt,
     utils::binary_search,
@@ -359,25 +361,11 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         Self: LoadBlock,
     {
         async move {
-            if let Some(block) = self.recovered_block(block_id).await? {
-                let block_hash = block.hash();
-                let block_number = block.number();
-                let block_timestamp = block.timestamp();
-                let base_fee_per_gas = block.base_fee_per_gas();
-                if let Some((signer, tx)) = block.transactions_with_sender().nth(index) {
-                    let tx_info = TransactionInfo {
-                        hash: Some(*tx.tx_hash()),
-                        block_hash: Some(block_hash),
-                        block_number: Some(block_number),
-                        block_timestamp: Some(block_timestamp),
-                        base_fee: base_fee_per_gas,
-                        index: Some(index as u64),
-                    };
-
-                    return Ok(Some(
-                        self.converter().fill(tx.clone().with_signer(*signer), tx_info)?,
-                    ))
-                }
+            if let Some(block) = self.recovered_block(block_id).await? &&
+                let Some((signer, tx)) = block.transactions_with_sender().nth(index)
+            {
+                let tx_info = TransactionInfo::from_block_tx(&block, *tx.tx_hash(), index);
+                return Ok(Some(self.converter().fill(tx.clone().with_signer(*signer), tx_info)?))
             }
 
             Ok(None)
@@ -431,25 +419,16 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
             self.recovered_block(block_id)
                 .await?
                 .and_then(|block| {
-                    let block_hash = block.hash();
-                    let block_number = block.number();
-                    let block_timestamp = block.timestamp();
-                    let base_fee_per_gas = block.base_fee_per_gas();
-
                     block
                         .transactions_with_sender()
                         .enumerate()
                         .find(|(_, (signer, tx))| **signer == sender && (*tx).nonce() == nonce)
                         .map(|(index, (signer, tx))| {
-                            let tx_info = TransactionInfo {
-                                hash: Some(*tx.tx_hash()),
-                                block_hash: Some(block_hash),
-                                block_number: Some(block_number),
-                                block_timestamp: Some(block_timestamp),
-                                base_fee: base_fee_per_gas,
-                                index: Some(index as u64),
-                            };
-                            Ok(self.converter().fill(tx.clone().with_signer(*signer), tx_info)?)
+                            let tx_info =
+                                TransactionInfo::from_block_tx(&block, *tx.tx_hash(), index);
+                            self.converter()
+                                .fill(tx.clone().with_signer(*signer), tx_info)
+                                .map_err(Self::Error::from)
                         })
                 })
                 .ok_or(EthApiError::HeaderNotFound(block_id))?
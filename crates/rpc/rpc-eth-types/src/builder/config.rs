@@ -155,7 +155,7 @@ impl Default for EthConfig {
 
 impl EthConfig {
     /// Configures the caching layer settings
-    pub const fn state_cache(mut self, cache: EthStateCacheConfig) -> Self {
+    pub fn state_cache(mut self, cache: EthStateCacheConfig) -> Self {
         self.cache = cache;
         self
     }
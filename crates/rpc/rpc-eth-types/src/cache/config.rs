@@ -1,14 +1,16 @@
 //! Configuration for RPC cache.
 
 use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, time::Duration};
 
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BAL_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS,
-    DEFAULT_HEADER_CACHE_MAX_LEN, DEFAULT_MAX_CACHED_TX_HASHES, DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_BAL_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_COLD_BLOCK_CACHE_MAX_LEN,
+    DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN, DEFAULT_MAX_CACHED_TX_HASHES,
+    DEFAULT_MAX_QUEUED_CONSUMERS, DEFAULT_RECEIPT_CACHE_MAX_LEN,
 };
 
 /// Settings for the [`EthStateCache`](super::EthStateCache).
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EthStateCacheConfig {
     /// Max number of blocks in cache.
@@ -32,7 +34,87 @@ pub struct EthStateCacheConfig {
     /// Default is 512.
     pub max_concurrent_db_requests: usize,
     /// Maximum number of transaction hashes to cache for transaction lookups.
+    ///
+    /// This bounds the LRU index from transaction hash to block, entries of old blocks age out
+    /// as new blocks are indexed.
     pub max_cached_tx_hashes: u32,
+    /// Optional maximum number of entries in the LRU index from canonical block number to block
+    /// hash.
+    ///
+    /// Default is `None`, the index is as large as the headers cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_number_index_entries: Option<u32>,
+    /// Max number of requests waiting on the same in-flight fetch.
+    ///
+    /// Further requests for the same key fail immediately instead of being queued.
+    ///
+    /// Default is 1024.
+    #[serde(default = "default_max_queued_consumers")]
+    pub max_queued_consumers: usize,
+    /// Optional upper bound for a random delay applied to each database fetch.
+    ///
+    /// Spreads out the IO when many uncached entries are requested at once, e.g. on cold start.
+    ///
+    /// Default is `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetch_jitter: Option<Duration>,
+    /// How requests are answered if the fetch serving them panicked before producing a result.
+    ///
+    /// Default is [`CachePanicPolicy::Error`].
+    #[serde(default)]
+    pub panic_policy: CachePanicPolicy,
+    /// Optional byte budget shared by the block, receipt and header caches.
+    ///
+    /// If set, the tracked memory usage of the three caches is added up after every insert and
+    /// the least recently used entries of whichever cache is largest are evicted until the total
+    /// is within the budget. The per cache length limits still apply.
+    ///
+    /// Default is `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_max_bytes: Option<usize>,
+    /// Which entries the block, receipt and header caches evict when they are full.
+    ///
+    /// Default is [`EvictionPolicy::Lru`].
+    #[serde(default)]
+    pub eviction_policy: EvictionPolicy,
+    /// Optional delay before newly committed blocks are cached by
+    /// [`cache_new_blocks_task_with_debounce`](super::cache_new_blocks_task_with_debounce).
+    ///
+    /// Blocks that are reverted within the delay are never cached, which avoids churning the
+    /// cache with tips that are immediately reorged out.
+    ///
+    /// Default is `None`, blocks are cached as soon as they are committed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_blocks_debounce: Option<Duration>,
+    /// Whether a fetched or committed block or receipts that are already cached with equal data
+    /// are dropped instead of inserted again.
+    ///
+    /// Requests waiting for the entry are still answered. Skipping the insert avoids promoting
+    /// and re-accounting the entry on duplicate notifications, at the cost of comparing the data
+    /// when the entry is already cached.
+    ///
+    /// Default is `false`.
+    #[serde(default)]
+    pub skip_duplicate_inserts: bool,
+    /// Max number of blocks, and their receipts, in the low priority cache.
+    ///
+    /// Blocks inserted with
+    /// [`EthStateCache::insert_low_priority`](super::EthStateCache::insert_low_priority), e.g. by
+    /// a historical backfill, are kept in this separate cache so they don't evict recently used
+    /// blocks from the main caches.
+    ///
+    /// Default is 500.
+    #[serde(default = "default_max_cold_blocks")]
+    pub max_cold_blocks: u32,
+    /// File the headers cache is persisted to, so it is warm after a restart.
+    ///
+    /// The cached headers are loaded from the file in the background after the service started,
+    /// only headers that are still part of the canonical chain are kept. They are written back
+    /// periodically and on graceful shutdown.
+    ///
+    /// Default is `None`, the headers are not persisted.
+    #[serde(default)]
+    pub header_persist_path: Option<PathBuf>,
 }
 
 impl Default for EthStateCacheConfig {
@@ -44,6 +126,93 @@ impl Default for EthStateCacheConfig {
             max_bals: DEFAULT_BAL_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
             max_cached_tx_hashes: DEFAULT_MAX_CACHED_TX_HASHES,
+            max_number_index_entries: None,
+            max_queued_consumers: DEFAULT_MAX_QUEUED_CONSUMERS,
+            fetch_jitter: None,
+            panic_policy: CachePanicPolicy::default(),
+            total_max_bytes: None,
+            eviction_policy: EvictionPolicy::default(),
+            new_blocks_debounce: None,
+            skip_duplicate_inserts: false,
+            max_cold_blocks: DEFAULT_COLD_BLOCK_CACHE_MAX_LEN,
+            header_persist_path: None,
         }
     }
 }
+
+const fn default_max_queued_consumers() -> usize {
+    DEFAULT_MAX_QUEUED_CONSUMERS
+}
+
+const fn default_max_cold_blocks() -> u32 {
+    DEFAULT_COLD_BLOCK_CACHE_MAX_LEN
+}
+
+/// How the [`EthStateCache`](super::EthStateCache) answers requests whose fetch panicked before
+/// producing a result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CachePanicPolicy {
+    /// Fail all waiting requests with a
+    /// [`CacheServiceUnavailable`](super::CacheServiceUnavailable) error.
+    #[default]
+    Error,
+    /// Retry the fetch once, and only fail the waiting requests if the retry panics as well.
+    RetryOnce,
+}
+
+/// Which entry a full [`EthStateCache`](super::EthStateCache) cache evicts to make room.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EvictionPolicy {
+    /// Evict the least recently used entry.
+    #[default]
+    Lru,
+    /// Evict an infrequently used entry, approximating least frequently used.
+    ///
+    /// Entries count their lookups. When the least recently used entry was looked up more than
+    /// once, its count is halved and it is kept as if it was just used, up to a few times per
+    /// eviction. This keeps repeatedly polled historical blocks cached while scans over many
+    /// blocks pass through, and lets entries that stop being used age out.
+    Lfu,
+}
+
+impl std::str::FromStr for EvictionPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lru" => Ok(Self::Lru),
+            "lfu" => Ok(Self::Lfu),
+            _ => Err(format!("Invalid eviction policy: {s}. Valid options are: lru, lfu")),
+        }
+    }
+}
+
+impl EvictionPolicy {
+    /// Returns true if infrequently used entries are evicted first.
+    pub const fn is_lfu(&self) -> bool {
+        matches!(self, Self::Lfu)
+    }
+}
+
+impl std::str::FromStr for CachePanicPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "retry-once" => Ok(Self::RetryOnce),
+            _ => Err(format!(
+                "Invalid cache panic policy: {s}. Valid options are: error, retry-once"
+            )),
+        }
+    }
+}
+
+impl CachePanicPolicy {
+    /// Returns true if a panicked fetch is retried.
+    pub const fn is_retry(&self) -> bool {
+        matches!(self, Self::RetryOnce)
+    }
+}
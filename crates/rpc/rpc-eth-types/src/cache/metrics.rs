@@ -1,6 +1,6 @@
 //! Tracks state of RPC cache.
 
-use metrics::Counter;
+use metrics::{Counter, Histogram};
 use reth_metrics::{metrics::Gauge, Metrics};
 
 #[derive(Metrics)]
@@ -17,3 +17,13 @@ pub(crate) struct CacheMetrics {
     /// The memory usage of the cache.
     pub(crate) memory_usage: Gauge,
 }
+
+/// Tracks contention on the database fetch permits of the RPC cache.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth_cache")]
+pub(crate) struct RateLimiterMetrics {
+    /// The number of fetches that had to wait for a database permit.
+    pub(crate) saturated_acquires_total: Counter,
+    /// How long fetches waited for a database permit.
+    pub(crate) saturated_acquire_wait_duration: Histogram,
+}
@@ -1,16 +1,22 @@
 //! Async caching support for eth RPC
 
-use super::{EthStateCacheConfig, MultiConsumerLruCache};
+use self::metrics::RateLimiterMetrics;
+use super::{CachePanicPolicy, EthStateCacheConfig, MultiConsumerLruCache};
 use crate::block::CachedTransaction;
-use alloy_consensus::{transaction::TxHashRef, BlockHeader};
+use alloy_consensus::{transaction::TxHashRef, BlockHeader, Sealable, TxReceipt};
 use alloy_eip7928::bal::DecodedBal;
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::{Address, TxHash, B256};
-use futures::{stream::FuturesOrdered, Stream, StreamExt};
+use alloy_primitives::{logs_bloom, map::B256Map, Address, BlockNumber, Bloom, TxHash, B256};
+use futures::{
+    stream::{FuturesOrdered, FuturesUnordered},
+    Stream, StreamExt,
+};
 use reth_chain_state::CanonStateNotification;
 use reth_errors::{ProviderError, ProviderResult};
 use reth_execution_types::Chain;
-use reth_primitives_traits::{Block, BlockBody, InMemorySize, NodePrimitives, RecoveredBlock};
+use reth_primitives_traits::{
+    Block, BlockBody, InMemorySize, NodePrimitives, RecoveredBlock, SealedBlock,
+};
 use reth_revm::{
     bytecode::Bytecode,
     primitives::{StorageKey, StorageValue},
@@ -19,30 +25,50 @@ use reth_revm::{
         BalWrites as RevmBalWrites, StorageBal as RevmStorageBal,
     },
 };
-use reth_storage_api::{BalProvider, BlockReader, TransactionVariant};
-use reth_tasks::Runtime;
+use reth_storage_api::{BalProvider, BlockHashReader, BlockReader, TransactionVariant};
+use reth_tasks::{shutdown::GracefulShutdown, Runtime};
 use schnellru::{ByLength, Limiter, LruMap};
 use std::{
+    collections::VecDeque,
     future::Future,
+    path::PathBuf,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
-use tokio::sync::{
-    mpsc::{unbounded_channel, UnboundedSender},
-    oneshot, Semaphore,
+use tokio::{
+    sync::{
+        mpsc::{unbounded_channel, UnboundedSender},
+        oneshot, AcquireError, Semaphore, SemaphorePermit,
+    },
+    time::{Interval, MissedTickBehavior},
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{debug, warn};
 
 pub mod config;
 pub mod db;
 pub mod metrics;
 pub mod multi_consumer;
+mod persist;
+
+/// How often the headers cache is written to disk if persistence is enabled.
+const HEADER_PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a fetch may wait for a database permit before the limiter is reported as saturated.
+const RATE_LIMITER_SATURATION_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Minimum time between two rate limiter saturation warnings.
+const RATE_LIMITER_WARNING_INTERVAL: Duration = Duration::from_secs(30);
 
 /// The type that can send the response to a requested [`RecoveredBlock`]
 type BlockWithSendersResponseSender<B> =
     oneshot::Sender<ProviderResult<Option<Arc<RecoveredBlock<B>>>>>;
 
+/// The type that can send the response to a requested [`CachedBlock`]
+type SealedBlockResponseSender<B> = oneshot::Sender<ProviderResult<Option<CachedBlock<B>>>>;
+
 /// The type that can send the response to the requested receipts of a block.
 type ReceiptsResponseSender<R> = oneshot::Sender<ProviderResult<Option<Arc<Vec<R>>>>>;
 
@@ -54,18 +80,45 @@ type CachedBlockAndReceiptsResponseSender<B, R> =
 /// The type that can send the response to a requested header
 type HeaderResponseSender<H> = oneshot::Sender<ProviderResult<H>>;
 
+/// The type that can send the response to a requested block logs bloom.
+type BloomResponseSender = oneshot::Sender<ProviderResult<Option<Bloom>>>;
+
+/// The type that can send the response to a requested memoized block metadata.
+type BlockMetaResponseSender = oneshot::Sender<Option<CachedBlockMeta>>;
+
 /// The type that can send the response with a chain of cached blocks
 type CachedParentBlocksResponseSender<B> = oneshot::Sender<Vec<Arc<RecoveredBlock<B>>>>;
 
+/// The type that can send the response with the latest canonical block
+type LatestResponseSender = oneshot::Sender<Option<(B256, BlockNumber)>>;
+
+/// The type that can send the response with the current reorg epoch
+type ReorgEpochResponseSender = oneshot::Sender<u64>;
+
 /// The type that can send the response for a transaction hash lookup
 type TransactionHashResponseSender<B, R> = oneshot::Sender<Option<CachedTransaction<B, R>>>;
 
+/// The type that can send a block to a caller waiting for it to be committed.
+type BlockWaiterSender<B> = oneshot::Sender<Arc<RecoveredBlock<B>>>;
+
+/// The type that can send the response to a requested transaction receipt.
+type ReceiptResponseSender<R> = oneshot::Sender<ProviderResult<Option<R>>>;
+
 /// The type that can send the response to a requested revm BAL.
 type BalResponseSender = oneshot::Sender<ProviderResult<Option<CachedRevmBal>>>;
 
+/// The type that can send the response to the requested senders of a block.
+type SendersResponseSender = oneshot::Sender<ProviderResult<Option<CachedSenders>>>;
+
+/// The type that can send the response to the requested transactions of a block.
+type TransactionsResponseSender<T> = oneshot::Sender<ProviderResult<Option<Arc<Vec<T>>>>>;
+
 type BlockLruCache<B, L> =
     MultiConsumerLruCache<B256, Arc<RecoveredBlock<B>>, L, BlockWithSendersResponseSender<B>>;
 
+type SealedBlockLruCache<B, L> =
+    MultiConsumerLruCache<B256, Arc<SealedBlock<B>>, L, SealedBlockResponseSender<B>>;
+
 type ReceiptsLruCache<R, L> =
     MultiConsumerLruCache<B256, Arc<Vec<R>>, L, ReceiptsResponseSender<R>>;
 
@@ -73,6 +126,11 @@ type HeaderLruCache<H, L> = MultiConsumerLruCache<B256, H, L, HeaderResponseSend
 
 type BalLruCache<L> = MultiConsumerLruCache<B256, CachedRevmBal, L, BalResponseSender>;
 
+type SendersLruCache<L> = MultiConsumerLruCache<B256, CachedSenders, L, SendersResponseSender>;
+
+type TransactionsLruCache<T, L> =
+    MultiConsumerLruCache<B256, Arc<Vec<T>>, L, TransactionsResponseSender<T>>;
+
 /// Provides async access to cached eth data
 ///
 /// This is the frontend for the async caching service which manages cached data on a different
@@ -105,20 +163,61 @@ impl<N: NodePrimitives> EthStateCache<N> {
             max_bals,
             max_concurrent_db_requests,
             max_cached_tx_hashes,
+            max_number_index_entries,
+            max_queued_consumers,
+            fetch_jitter,
+            panic_policy,
+            total_max_bytes,
+            eviction_policy,
+            // only used by the task that feeds new blocks into the cache
+            new_blocks_debounce: _,
+            skip_duplicate_inserts,
+            max_cold_blocks,
+            header_persist_path,
         } = config;
         let (to_service, rx) = unbounded_channel();
 
         let service = EthStateCacheService {
             provider,
-            full_block_cache: BlockLruCache::new(max_blocks, "blocks"),
-            receipts_cache: ReceiptsLruCache::new(max_receipts, "receipts"),
-            headers_cache: HeaderLruCache::new(max_headers, "headers"),
-            bal_cache: BalLruCache::new(max_bals, "bals"),
+            full_block_cache: BlockLruCache::new(max_blocks, "blocks")
+                .with_max_queued_consumers(max_queued_consumers)
+                .with_eviction_policy(eviction_policy),
+            receipts_cache: ReceiptsLruCache::new(max_receipts, "receipts")
+                .with_max_queued_consumers(max_queued_consumers)
+                .with_eviction_policy(eviction_policy),
+            headers_cache: HeaderLruCache::new(max_headers, "headers")
+                .with_max_queued_consumers(max_queued_consumers)
+                .with_eviction_policy(eviction_policy),
+            bal_cache: BalLruCache::new(max_bals, "bals")
+                .with_max_queued_consumers(max_queued_consumers),
+            // senders are derived from blocks, so they share the block cache limit
+            senders_cache: SendersLruCache::new(max_blocks, "senders")
+                .with_max_queued_consumers(max_queued_consumers),
+            transactions_cache: TransactionsLruCache::new(max_blocks, "transactions")
+                .with_max_queued_consumers(max_queued_consumers),
+            sealed_block_cache: SealedBlockLruCache::new(max_blocks, "sealed_blocks")
+                .with_max_queued_consumers(max_queued_consumers),
             action_tx: to_service.clone(),
             action_rx: UnboundedReceiverStream::new(rx),
             action_task_spawner,
-            rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_requests)),
+            rate_limiter: Arc::new(FetchRateLimiter::new(max_concurrent_db_requests)),
             tx_hash_index: LruMap::new(ByLength::new(max_cached_tx_hashes)),
+            canonical_hashes: LruMap::new(ByLength::new(
+                max_number_index_entries.unwrap_or(max_headers),
+            )),
+            bloom_cache: LruMap::new(ByLength::new(max_headers)),
+            block_meta_cache: LruMap::new(ByLength::new(max_headers)),
+            cold_blocks: LruMap::new(ByLength::new(max_cold_blocks)),
+            cold_receipts: LruMap::new(ByLength::new(max_cold_blocks)),
+            latest: None,
+            reorg_epoch: 0,
+            header_persist_path,
+            header_persist_interval: None,
+            fetch_jitter,
+            panic_policy,
+            total_max_bytes,
+            skip_duplicate_inserts,
+            block_waiters: Default::default(),
         };
         let cache = Self { to_service };
         (cache, service)
@@ -141,10 +240,58 @@ impl<N: NodePrimitives> EthStateCache<N> {
             + 'static,
     {
         let (this, service) = Self::create(provider, executor.clone(), config);
-        executor.spawn_critical_task("eth state cache", service);
+        service.spawn(&executor);
+        this
+    }
+
+    /// Like [`Self::spawn_with`], but fetches the blocks and receipts of `preload` into the
+    /// caches before any other request is handled.
+    ///
+    /// Passing e.g. the most recent canonical block hashes gives a warm cache right after a
+    /// restart. This doesn't wait for the blocks to be fetched, see [`Self::preload`].
+    pub fn spawn_with_preload<Provider>(
+        provider: Provider,
+        config: EthStateCacheConfig,
+        executor: Runtime,
+        preload: impl IntoIterator<Item = B256>,
+    ) -> Self
+    where
+        Provider: BlockReader<Block = N::Block, Receipt = N::Receipt>
+            + BalProvider
+            + Clone
+            + Unpin
+            + 'static,
+    {
+        let (this, service) = Self::create(provider, executor.clone(), config);
+        // queued before the service runs, so the preloads are handled first
+        this.preload(preload);
+        service.spawn(&executor);
         this
     }
 
+    /// Fetches the blocks and receipts of the given block hashes into the caches in the
+    /// background.
+    ///
+    /// This only queues the fetches and returns immediately. Blocks that don't exist are
+    /// skipped.
+    pub fn preload(&self, block_hashes: impl IntoIterator<Item = B256>) {
+        for block_hash in block_hashes {
+            // nobody waits for the responses, the fetched entries are cached as usual
+            let (response_tx, _) = oneshot::channel();
+            let _ = self.to_service.send(CacheAction::GetBlockWithSenders {
+                block_hash,
+                promote: true,
+                response_tx,
+            });
+            let (response_tx, _) = oneshot::channel();
+            let _ = self.to_service.send(CacheAction::GetReceipts {
+                block_hash,
+                promote: true,
+                response_tx,
+            });
+        }
+    }
+
     /// Requests the  [`RecoveredBlock`] for the block hash
     ///
     /// Returns `None` if the block does not exist.
@@ -153,7 +300,80 @@ impl<N: NodePrimitives> EthStateCache<N> {
         block_hash: B256,
     ) -> ProviderResult<Option<Arc<RecoveredBlock<N::Block>>>> {
         let (response_tx, rx) = oneshot::channel();
-        let _ = self.to_service.send(CacheAction::GetBlockWithSenders { block_hash, response_tx });
+        let _ = self.to_service.send(CacheAction::GetBlockWithSenders {
+            block_hash,
+            promote: true,
+            response_tx,
+        });
+        rx.await.map_err(|_| CacheServiceUnavailable)?
+    }
+
+    /// Requests the block for the block hash without recovering its senders.
+    ///
+    /// If the block is already cached with senders it's returned as is, otherwise only the block
+    /// is fetched and cached. The senders of a cached sealed block are recovered once the first
+    /// caller asks for the [`RecoveredBlock`], e.g. via [`Self::get_recovered_block`].
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn get_sealed_block(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<Option<CachedBlock<N::Block>>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetSealedBlock { block_hash, response_tx });
+        rx.await.map_err(|_| CacheServiceUnavailable)?
+    }
+
+    /// Requests the [`RecoveredBlock`] for the block hash together with the cache's reorg epoch.
+    ///
+    /// The epoch is read right before the block lookup is handled by the service. Since every
+    /// reorg applied by the cache increments it, callers can compare the epochs of two reads to
+    /// detect that the chain moved in between. A reorg racing with this request can only make the
+    /// returned epoch older than the block, never newer, so a change is never missed.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn get_recovered_block_versioned(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<Option<(Arc<RecoveredBlock<N::Block>>, u64)>> {
+        let (epoch_tx, epoch_rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetReorgEpoch { response_tx: epoch_tx });
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetBlockWithSenders {
+            block_hash,
+            promote: true,
+            response_tx,
+        });
+        let epoch = epoch_rx.await.map_err(|_| CacheServiceUnavailable)?;
+        let block = rx.await.map_err(|_| CacheServiceUnavailable)??;
+        Ok(block.map(|block| (block, epoch)))
+    }
+
+    /// Returns the number of reorgs the cache has applied so far.
+    pub async fn reorg_epoch(&self) -> ProviderResult<u64> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetReorgEpoch { response_tx });
+        Ok(rx.await.map_err(|_| CacheServiceUnavailable)?)
+    }
+
+    /// Requests the [`RecoveredBlock`] for the block hash without marking a cached block as
+    /// recently used.
+    ///
+    /// This is intended for scans over many blocks, like `eth_getLogs`, so they don't flush the
+    /// blocks that serve interactive queries from the cache. Blocks that are not cached are
+    /// fetched and cached as usual.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn get_recovered_block_no_promote(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<Option<Arc<RecoveredBlock<N::Block>>>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetBlockWithSenders {
+            block_hash,
+            promote: false,
+            response_tx,
+        });
         rx.await.map_err(|_| CacheServiceUnavailable)?
     }
 
@@ -165,7 +385,29 @@ impl<N: NodePrimitives> EthStateCache<N> {
         block_hash: B256,
     ) -> ProviderResult<Option<Arc<Vec<N::Receipt>>>> {
         let (response_tx, rx) = oneshot::channel();
-        let _ = self.to_service.send(CacheAction::GetReceipts { block_hash, response_tx });
+        let _ = self.to_service.send(CacheAction::GetReceipts {
+            block_hash,
+            promote: true,
+            response_tx,
+        });
+        rx.await.map_err(|_| CacheServiceUnavailable)?
+    }
+
+    /// Requests the receipts for the block hash without marking cached receipts as recently used.
+    ///
+    /// See [`Self::get_recovered_block_no_promote`].
+    ///
+    /// Returns `None` if the block was not found.
+    pub async fn get_receipts_no_promote(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<Option<Arc<Vec<N::Receipt>>>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetReceipts {
+            block_hash,
+            promote: false,
+            response_tx,
+        });
         rx.await.map_err(|_| CacheServiceUnavailable)?
     }
 
@@ -225,6 +467,27 @@ impl<N: NodePrimitives> EthStateCache<N> {
         futures.collect::<FuturesOrdered<_>>()
     }
 
+    /// Streams cached receipts and blocks for a list of block hashes as soon as they are available.
+    ///
+    /// Unlike [`Self::get_receipts_and_maybe_block_stream`] this does not preserve the input
+    /// order, so each item is paired with the block hash it belongs to.
+    #[expect(clippy::type_complexity)]
+    pub fn get_receipts_and_maybe_block_stream_unordered<'a>(
+        &'a self,
+        hashes: Vec<B256>,
+    ) -> impl Stream<
+        Item = (
+            B256,
+            ProviderResult<Option<(Arc<Vec<N::Receipt>>, Option<Arc<RecoveredBlock<N::Block>>>)>>,
+        ),
+    > + 'a {
+        let futures = hashes
+            .into_iter()
+            .map(move |hash| async move { (hash, self.get_receipts_and_maybe_block(hash).await) });
+
+        futures.collect::<FuturesUnordered<_>>()
+    }
+
     /// Requests the header for the given hash.
     ///
     /// Returns an error if the header is not found.
@@ -234,6 +497,108 @@ impl<N: NodePrimitives> EthStateCache<N> {
         rx.await.map_err(|_| CacheServiceUnavailable)?
     }
 
+    /// Removes all cached entries of the given kinds.
+    ///
+    /// Requests that are waiting on an in-flight fetch are still answered by that fetch.
+    pub fn clear(&self, kinds: CacheKinds) {
+        let _ = self.to_service.send(CacheAction::Clear { kinds });
+    }
+
+    /// Caches a block and its receipts with a lower priority than blocks served to callers.
+    ///
+    /// The entries go into a separate, smaller cache that is only consulted if the main caches
+    /// don't have the block, so e.g. a historical backfill doesn't evict the recent blocks that
+    /// serve live traffic. Blocks that are already in the main cache are not inserted again.
+    ///
+    /// See [`EthStateCacheConfig::max_cold_blocks`].
+    pub fn insert_low_priority(
+        &self,
+        block: Arc<RecoveredBlock<N::Block>>,
+        receipts: Arc<Vec<N::Receipt>>,
+    ) {
+        let _ = self.to_service.send(CacheAction::InsertLowPriority { block, receipts });
+    }
+
+    /// Requests the aggregate logs bloom of the block with the given hash.
+    ///
+    /// The bloom is taken from a cached header or block, or computed from cached receipts and
+    /// memoized, so it can serve as a cheap prefilter for log queries.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn get_block_bloom(&self, block_hash: B256) -> ProviderResult<Option<Bloom>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetBloom { block_hash, response_tx });
+        rx.await.map_err(|_| CacheServiceUnavailable)?
+    }
+
+    /// Returns `true` if the block with the given hash is currently being fetched for another
+    /// consumer.
+    ///
+    /// Callers can use this to avoid piling more work onto an in-flight fetch, e.g. by returning
+    /// a retry hint instead. Returns `false` if the cache service is unavailable.
+    pub async fn is_fetching(&self, block_hash: B256) -> bool {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::IsFetching { block_hash, response_tx });
+        rx.await.unwrap_or_default()
+    }
+
+    /// Requests the [`CachedBlockMeta`] of the block with the given hash.
+    ///
+    /// The metadata is derived from a cached block and memoized, so repeated requests don't
+    /// re-derive it. If the block is not cached, it is fetched first and the metadata derived
+    /// from it is memoized as well.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn get_block_meta(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<Option<CachedBlockMeta>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetBlockMeta { block_hash, response_tx });
+        if let Some(meta) = rx.await.map_err(|_| CacheServiceUnavailable)? {
+            return Ok(Some(meta))
+        }
+        let Some(block) = self.get_recovered_block(block_hash).await? else { return Ok(None) };
+        let meta = CachedBlockMeta::new(&block);
+        let _ = self.to_service.send(CacheAction::CacheBlockMeta { block_hash, meta });
+        Ok(Some(meta))
+    }
+
+    /// Requests the canonical header for the given block number.
+    ///
+    /// The number is resolved to a hash through the canonical chain updates observed by the
+    /// cache, so recent headers are served without a provider lookup.
+    ///
+    /// Returns an error if the header is not found.
+    pub async fn get_header_by_number(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<N::BlockHeader> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetHeaderByNumber { block_number, response_tx });
+        rx.await.map_err(|_| CacheServiceUnavailable)?
+    }
+
+    /// Returns the hash and number of the latest canonical block observed by the cache.
+    ///
+    /// Returns `None` if no canonical chain update has been observed yet.
+    pub async fn latest(&self) -> Option<(B256, BlockNumber)> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetLatest { response_tx });
+        rx.await.ok()?
+    }
+
+    /// Returns the hashes of all cached blocks, receipts and headers.
+    ///
+    /// This is a diagnostic for inspecting the cache, e.g. after a reorg. Only the keys and sizes
+    /// of the entries are returned, the cached data itself is not cloned.
+    #[cfg(feature = "cache-snapshot")]
+    pub async fn snapshot(&self) -> ProviderResult<CacheSnapshot> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::Snapshot { response_tx });
+        Ok(rx.await.map_err(|_| CacheServiceUnavailable)?)
+    }
+
     /// Retrieves a chain of connected blocks from the cache, starting from the given block hash
     /// and traversing down through parent hashes. Returns blocks in descending order (newest
     /// first).
@@ -241,15 +606,41 @@ impl<N: NodePrimitives> EthStateCache<N> {
     /// cache without making separate database requests.
     /// Returns `None` if no blocks are found in the cache, otherwise returns `Some(Vec<...>)`
     /// with at least one block.
+    ///
+    /// See [`Self::get_cached_parent_blocks_ascending`] for the blocks in ascending order.
     pub async fn get_cached_parent_blocks(
         &self,
         block_hash: B256,
         max_blocks: usize,
+    ) -> Option<Vec<Arc<RecoveredBlock<N::Block>>>> {
+        self.cached_parent_blocks(block_hash, max_blocks, false).await
+    }
+
+    /// Retrieves the same chain of connected blocks as [`Self::get_cached_parent_blocks`], but in
+    /// ascending order (oldest first), ending with the block of the given hash.
+    ///
+    /// The chain is still collected from the given block down through its parents, so at most
+    /// `max_blocks` blocks are returned and the chain starts after the first parent that is not
+    /// cached.
+    pub async fn get_cached_parent_blocks_ascending(
+        &self,
+        block_hash: B256,
+        max_blocks: usize,
+    ) -> Option<Vec<Arc<RecoveredBlock<N::Block>>>> {
+        self.cached_parent_blocks(block_hash, max_blocks, true).await
+    }
+
+    async fn cached_parent_blocks(
+        &self,
+        block_hash: B256,
+        max_blocks: usize,
+        ascending: bool,
     ) -> Option<Vec<Arc<RecoveredBlock<N::Block>>>> {
         let (response_tx, rx) = oneshot::channel();
         let _ = self.to_service.send(CacheAction::GetCachedParentBlocks {
             block_hash,
             max_blocks,
+            ascending,
             response_tx,
         });
 
@@ -274,6 +665,47 @@ impl<N: NodePrimitives> EthStateCache<N> {
         rx.await.ok()?
     }
 
+    /// Waits until the block with the given hash is cached from a canonical chain commit.
+    ///
+    /// Resolves immediately if the block is already cached. Unlike
+    /// [`Self::get_recovered_block`] this never fetches the block from the provider, the
+    /// future only completes once a [`CanonStateNotification`] containing the block was
+    /// processed. See [`Self::wait_for_block_with_timeout`] for a bounded wait.
+    pub async fn wait_for_block(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<Arc<RecoveredBlock<N::Block>>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::WaitForBlock { block_hash, response_tx });
+        rx.await.map_err(|_| CacheServiceUnavailable.into())
+    }
+
+    /// Like [`Self::wait_for_block`], but gives up after `timeout`.
+    ///
+    /// Returns `None` if the block wasn't committed in time.
+    pub async fn wait_for_block_with_timeout(
+        &self,
+        block_hash: B256,
+        timeout: Duration,
+    ) -> ProviderResult<Option<Arc<RecoveredBlock<N::Block>>>> {
+        match tokio::time::timeout(timeout, self.wait_for_block(block_hash)).await {
+            Ok(block) => block.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Requests the receipt of the transaction with the given hash.
+    ///
+    /// Transactions of cached canonical blocks are resolved through the transaction index and
+    /// served from the receipts cache. Otherwise the receipt is looked up from the provider.
+    ///
+    /// Returns `None` if the transaction does not exist.
+    pub async fn get_receipt(&self, tx_hash: TxHash) -> ProviderResult<Option<N::Receipt>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetReceiptByTxHash { tx_hash, response_tx });
+        rx.await.map_err(|_| CacheServiceUnavailable)?
+    }
+
     /// Requests the revm BAL for the block hash.
     ///
     /// Returns `None` if the BAL does not exist.
@@ -287,7 +719,115 @@ impl<N: NodePrimitives> EthStateCache<N> {
             .map_err(|_| CacheServiceUnavailable)?
             .map(|maybe_bal| maybe_bal.map(|cached| cached.0))
     }
+
+    /// Requests the transactions of the block with the given hash.
+    ///
+    /// Transactions are taken from the cached block if present, otherwise only the block body is
+    /// read from the provider, without recovering the senders.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn get_transactions(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<Option<Arc<Vec<N::SignedTx>>>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetTransactions { block_hash, response_tx });
+        rx.await.map_err(|_| CacheServiceUnavailable)?
+    }
+
+    /// Requests the recovered senders of the block with the given hash.
+    ///
+    /// Senders are taken from the cached block if present, otherwise they are read from the
+    /// provider without loading the full block body.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn get_senders(&self, block_hash: B256) -> ProviderResult<Option<Arc<Vec<Address>>>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetSenders { block_hash, response_tx });
+        rx.await
+            .map_err(|_| CacheServiceUnavailable)?
+            .map(|maybe_senders| maybe_senders.map(|cached| cached.0))
+    }
+}
+
+bitflags::bitflags! {
+    /// A set of caches maintained by the [`EthStateCache`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct CacheKinds: u8 {
+        /// Full blocks, with or without recovered senders.
+        const BLOCKS = 1 << 0;
+        /// Block receipts.
+        const RECEIPTS = 1 << 1;
+        /// Block headers.
+        const HEADERS = 1 << 2;
+        /// Block access lists.
+        const BALS = 1 << 3;
+        /// Recovered block senders.
+        const SENDERS = 1 << 4;
+    }
+}
+
+/// Bounds the number of concurrent database fetches of the cache service.
+///
+/// Fetches that have to wait for a permit are counted, and a throttled warning is emitted if they
+/// wait for long, because that means `max_concurrent_db_requests` is too low for the load.
+struct FetchRateLimiter {
+    semaphore: Semaphore,
+    metrics: RateLimiterMetrics,
+    /// When the last saturation warning was emitted.
+    last_warning: Mutex<Option<Instant>>,
+}
+
+impl FetchRateLimiter {
+    fn new(permits: usize) -> Self {
+        Self {
+            semaphore: Semaphore::new(permits),
+            metrics: RateLimiterMetrics::default(),
+            last_warning: Mutex::new(None),
+        }
+    }
+
+    /// Acquires a permit, waiting for one to become available if necessary.
+    async fn acquire(&self) -> Result<SemaphorePermit<'_>, AcquireError> {
+        if let Ok(permit) = self.semaphore.try_acquire() {
+            return Ok(permit)
+        }
+
+        self.metrics.saturated_acquires_total.increment(1);
+        let start = Instant::now();
+        let permit = self.semaphore.acquire().await;
+        let waited = start.elapsed();
+        self.metrics.saturated_acquire_wait_duration.record(waited);
+
+        if waited >= RATE_LIMITER_SATURATION_THRESHOLD && self.should_warn() {
+            warn!(
+                target: "rpc::eth",
+                ?waited,
+                "Cache database fetches are throttled, consider raising --rpc-cache.max-concurrent-db-requests"
+            );
+        }
+        permit
+    }
+
+    /// Returns true if no saturation warning was emitted within the warning interval, and
+    /// records the current warning if so.
+    fn should_warn(&self) -> bool {
+        let mut last_warning = self.last_warning.lock().unwrap_or_else(|err| err.into_inner());
+        if last_warning.is_some_and(|last| last.elapsed() < RATE_LIMITER_WARNING_INTERVAL) {
+            return false
+        }
+        *last_warning = Some(Instant::now());
+        true
+    }
+}
+
+/// Sleeps for a random duration of up to `max`, if set.
+async fn fetch_jitter(max: Option<Duration>) {
+    if let Some(max) = max.filter(|max| !max.is_zero()) {
+        tokio::time::sleep(max.mul_f64(rand::random::<f64>())).await;
+    }
 }
+
 /// Thrown when the cache service task dropped.
 #[derive(Debug, thiserror::Error)]
 #[error("cache service task stopped")]
@@ -299,39 +839,158 @@ impl From<CacheServiceUnavailable> for ProviderError {
     }
 }
 
-/// A task that manages caches for data required by the `eth` rpc implementation.
-///
-/// It provides a caching layer on top of the given
-/// [`StateProvider`](reth_storage_api::StateProvider) and keeps data fetched via the provider in
-/// memory in an LRU cache. If the requested data is missing in the cache it is fetched and inserted
-/// into the cache afterwards. While fetching data from disk is sync, this service is async since
-/// requests and data is shared via channels.
-///
-/// This type is an endless future that listens for incoming messages from the user facing
-/// [`EthStateCache`] via a channel. If the requested data is not cached then it spawns a new task
-/// that does the IO and sends the result back to it. This way the caching service only
-/// handles messages and does LRU lookups and never blocking IO.
-///
-/// Caution: The channel for the data is _unbounded_ it is assumed that this is mainly used by the
-/// `reth_rpc::EthApi` which is typically invoked by the RPC server, which already uses
-/// permits to limit concurrent requests.
-#[must_use = "Type does nothing unless spawned"]
-pub(crate) struct EthStateCacheService<
-    Provider,
-    Tasks,
-    LimitBlocks = ByLength,
-    LimitReceipts = ByLength,
-    LimitHeaders = ByLength,
-    LimitBals = ByLength,
-> where
-    Provider: BlockReader + BalProvider,
-    LimitBlocks: Limiter<B256, Arc<RecoveredBlock<Provider::Block>>>,
-    LimitReceipts: Limiter<B256, Arc<Vec<Provider::Receipt>>>,
-    LimitHeaders: Limiter<B256, Provider::Header>,
-    LimitBals: Limiter<B256, CachedRevmBal>,
-{
-    /// The type used to lookup data from disk
-    provider: Provider,
+/// Thrown when too many requests are already waiting on the same in-flight fetch.
+#[derive(Debug, thiserror::Error)]
+#[error("too many requests waiting on the same cache entry")]
+pub struct CacheQueueFull;
+
+impl From<CacheQueueFull> for ProviderError {
+    fn from(err: CacheQueueFull) -> Self {
+        Self::other(err)
+    }
+}
+
+/// A block served by the [`EthStateCache`], see [`EthStateCache::get_sealed_block`].
+#[derive(Clone, Debug)]
+pub enum CachedBlock<B: Block> {
+    /// The block without its recovered senders.
+    Sealed(Arc<SealedBlock<B>>),
+    /// The block with its recovered senders.
+    Recovered(Arc<RecoveredBlock<B>>),
+}
+
+impl<B: Block> CachedBlock<B> {
+    /// Returns the sealed block.
+    pub fn sealed_block(&self) -> &SealedBlock<B> {
+        match self {
+            Self::Sealed(block) => block,
+            Self::Recovered(block) => block.sealed_block(),
+        }
+    }
+
+    /// Returns the block with recovered senders, if they were already recovered.
+    pub fn recovered(&self) -> Option<&Arc<RecoveredBlock<B>>> {
+        match self {
+            Self::Sealed(_) => None,
+            Self::Recovered(block) => Some(block),
+        }
+    }
+
+    /// Returns the hash of the block.
+    pub fn hash(&self) -> B256 {
+        self.sealed_block().hash()
+    }
+}
+
+/// Small per-block values derived from a cached block, see [`EthStateCache::get_block_meta`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CachedBlockMeta {
+    /// Block number.
+    pub number: BlockNumber,
+    /// Total gas used by the block's transactions.
+    pub gas_used: u64,
+    /// Total blob gas used by the block's transactions, if the block has the field.
+    pub blob_gas_used: Option<u64>,
+    /// Number of transactions in the block.
+    pub transaction_count: usize,
+}
+
+impl CachedBlockMeta {
+    /// Derives the metadata of the given block.
+    pub fn new<B: Block>(block: &RecoveredBlock<B>) -> Self {
+        Self {
+            number: block.header().number(),
+            gas_used: block.header().gas_used(),
+            blob_gas_used: block.header().blob_gas_used(),
+            transaction_count: block.body().transaction_count(),
+        }
+    }
+}
+
+/// The cached entries of the [`EthStateCache`], see [`EthStateCache::snapshot`].
+#[cfg(feature = "cache-snapshot")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheSnapshot {
+    /// Cached blocks.
+    pub blocks: CacheEntriesSnapshot,
+    /// Cached receipts.
+    pub receipts: CacheEntriesSnapshot,
+    /// Cached headers.
+    pub headers: CacheEntriesSnapshot,
+}
+
+/// The cached entries of a single cache in a [`CacheSnapshot`].
+#[cfg(feature = "cache-snapshot")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheEntriesSnapshot {
+    /// Block hashes of the cached entries, from most to least recently used.
+    pub hashes: Vec<B256>,
+    /// Tracked heap usage of the cached entries in bytes.
+    pub memory_usage: usize,
+}
+
+#[cfg(feature = "cache-snapshot")]
+impl CacheEntriesSnapshot {
+    fn new<V, L, S>(cache: &MultiConsumerLruCache<B256, V, L, S>) -> Self
+    where
+        L: Limiter<B256, V>,
+    {
+        Self {
+            hashes: cache.iter().map(|(hash, _)| *hash).collect(),
+            memory_usage: cache.memory_usage(),
+        }
+    }
+
+    /// Returns the number of cached entries.
+    pub const fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns true if nothing is cached.
+    pub const fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+/// A task that manages caches for data required by the `eth` rpc implementation.
+///
+/// It provides a caching layer on top of the given
+/// [`StateProvider`](reth_storage_api::StateProvider) and keeps data fetched via the provider in
+/// memory in an LRU cache. If the requested data is missing in the cache it is fetched and inserted
+/// into the cache afterwards. While fetching data from disk is sync, this service is async since
+/// requests and data is shared via channels.
+///
+/// This type is an endless future that listens for incoming messages from the user facing
+/// [`EthStateCache`] via a channel. If the requested data is not cached then it spawns a new task
+/// that does the IO and sends the result back to it. This way the caching service only
+/// handles messages and does LRU lookups and never blocking IO.
+///
+/// Caution: The channel for the data is _unbounded_ it is assumed that this is mainly used by the
+/// `reth_rpc::EthApi` which is typically invoked by the RPC server, which already uses
+/// permits to limit concurrent requests.
+#[must_use = "Type does nothing unless spawned"]
+pub(crate) struct EthStateCacheService<
+    Provider,
+    Tasks,
+    LimitBlocks = ByLength,
+    LimitReceipts = ByLength,
+    LimitHeaders = ByLength,
+    LimitBals = ByLength,
+    LimitSenders = ByLength,
+    LimitTransactions = ByLength,
+    LimitSealedBlocks = ByLength,
+> where
+    Provider: BlockReader + BalProvider,
+    LimitBlocks: Limiter<B256, Arc<RecoveredBlock<Provider::Block>>>,
+    LimitReceipts: Limiter<B256, Arc<Vec<Provider::Receipt>>>,
+    LimitHeaders: Limiter<B256, Provider::Header>,
+    LimitBals: Limiter<B256, CachedRevmBal>,
+    LimitSenders: Limiter<B256, CachedSenders>,
+    LimitTransactions: Limiter<B256, Arc<Vec<Provider::Transaction>>>,
+    LimitSealedBlocks: Limiter<B256, Arc<SealedBlock<Provider::Block>>>,
+{
+    /// The type used to lookup data from disk
+    provider: Provider,
     /// The LRU cache for full blocks grouped by their block hash.
     full_block_cache: BlockLruCache<Provider::Block, LimitBlocks>,
     /// The LRU cache for block receipts grouped by the block hash.
@@ -343,6 +1002,19 @@ pub(crate) struct EthStateCacheService<
     headers_cache: HeaderLruCache<Provider::Header, LimitHeaders>,
     /// The LRU cache for revm BALs grouped by the block hash.
     bal_cache: BalLruCache<LimitBals>,
+    /// The LRU cache for recovered block senders grouped by the block hash.
+    senders_cache: SendersLruCache<LimitSenders>,
+    /// The LRU cache for block transactions grouped by the block hash.
+    ///
+    /// Only populated for blocks that were requested without senders, full blocks are served from
+    /// the block cache.
+    transactions_cache: TransactionsLruCache<Provider::Transaction, LimitTransactions>,
+    /// The LRU cache for blocks whose senders haven't been recovered yet, grouped by the block
+    /// hash.
+    ///
+    /// Blocks are moved to the block cache once their senders are recovered, so each block is
+    /// recovered at most once.
+    sealed_block_cache: SealedBlockLruCache<Provider::Block, LimitSealedBlocks>,
     /// Sender half of the action channel.
     action_tx: UnboundedSender<CacheAction<Provider::Block, Provider::Receipt>>,
     /// Receiver half of the action channel.
@@ -352,15 +1024,231 @@ pub(crate) struct EthStateCacheService<
     /// Rate limiter for spawned fetch tasks.
     ///
     /// This restricts the max concurrent fetch tasks at the same time.
-    rate_limiter: Arc<Semaphore>,
+    rate_limiter: Arc<FetchRateLimiter>,
     /// LRU index mapping transaction hashes to their block hash and index within the block.
     tx_hash_index: LruMap<TxHash, (B256, usize), ByLength>,
+    /// LRU index mapping canonical block numbers to their block hash.
+    ///
+    /// Maintained from canonical chain updates, reorged blocks are removed.
+    canonical_hashes: LruMap<BlockNumber, B256, ByLength>,
+    /// LRU cache for block logs blooms computed from cached receipts.
+    bloom_cache: LruMap<B256, Bloom, ByLength>,
+    /// LRU cache for metadata derived from cached blocks.
+    block_meta_cache: LruMap<B256, CachedBlockMeta, ByLength>,
+    /// Low priority tier for blocks inserted by historical backfills.
+    ///
+    /// Only consulted if a block isn't in the main cache, so backfilled blocks never evict
+    /// blocks that serve live traffic.
+    cold_blocks: LruMap<B256, Arc<RecoveredBlock<Provider::Block>>, ByLength>,
+    /// Low priority tier for the receipts of the blocks in [`Self::cold_blocks`].
+    cold_receipts: LruMap<B256, Arc<Vec<Provider::Receipt>>, ByLength>,
+    /// Hash and number of the latest canonical block.
+    ///
+    /// Updated from canonical chain updates, rewound to the fork point on reorgs.
+    latest: Option<(B256, BlockNumber)>,
+    /// Number of reorgs applied to the cache, used as a freshness token for versioned reads.
+    reorg_epoch: u64,
+    /// File the headers cache is persisted to, if enabled.
+    header_persist_path: Option<PathBuf>,
+    /// Timer for periodically persisting the headers cache.
+    ///
+    /// Created lazily on first poll, because creating it requires a runtime context.
+    header_persist_interval: Option<Interval>,
+    /// Upper bound for the random delay applied before each database fetch.
+    fetch_jitter: Option<Duration>,
+    /// How fetches that panicked before producing a result are answered.
+    panic_policy: CachePanicPolicy,
+    /// Byte budget shared by the block, receipt and header caches, if enabled.
+    total_max_bytes: Option<usize>,
+    /// Whether blocks and receipts that are already cached with equal data are not inserted
+    /// again.
+    skip_duplicate_inserts: bool,
+    /// Callers waiting for a block to be committed, by block hash.
+    block_waiters: B256Map<Vec<BlockWaiterSender<Provider::Block>>>,
+}
+
+impl<
+        Provider,
+        Tasks,
+        LimitBlocks,
+        LimitReceipts,
+        LimitHeaders,
+        LimitBals,
+        LimitSenders,
+        LimitTransactions,
+        LimitSealedBlocks,
+    >
+    EthStateCacheService<
+        Provider,
+        Tasks,
+        LimitBlocks,
+        LimitReceipts,
+        LimitHeaders,
+        LimitBals,
+        LimitSenders,
+        LimitTransactions,
+        LimitSealedBlocks,
+    >
+where
+    Provider: BlockReader + BalProvider,
+    LimitBlocks: Limiter<B256, Arc<RecoveredBlock<Provider::Block>>>,
+    LimitReceipts: Limiter<B256, Arc<Vec<Provider::Receipt>>>,
+    LimitHeaders: Limiter<B256, Provider::Header>,
+    LimitBals: Limiter<B256, CachedRevmBal>,
+    LimitSenders: Limiter<B256, CachedSenders>,
+    LimitTransactions: Limiter<B256, Arc<Vec<Provider::Transaction>>>,
+    LimitSealedBlocks: Limiter<B256, Arc<SealedBlock<Provider::Block>>>,
+{
+    /// Returns the cached header of the canonical block with the given number, if both the
+    /// number and the header are cached.
+    fn cached_header_by_number(&mut self, block_number: BlockNumber) -> Option<Provider::Header> {
+        let block_hash = *self.canonical_hashes.get(&block_number)?;
+        if let Some(header) = self.headers_cache.get(&block_hash) {
+            return Some(header.clone())
+        }
+        self.cached_recovered_block(block_hash, true).map(|block| block.clone_header())
+    }
+
+    /// Returns the logs bloom of the given block if it can be derived from cached data.
+    ///
+    /// Blooms computed from cached receipts are memoized.
+    fn cached_block_bloom(&mut self, block_hash: B256) -> Option<Bloom> {
+        if let Some(bloom) = self.bloom_cache.get(&block_hash) {
+            return Some(*bloom)
+        }
+        if let Some(header) = self.headers_cache.get(&block_hash) {
+            return Some(header.logs_bloom())
+        }
+        if let Some(block) = self.full_block_cache.get(&block_hash) {
+            return Some(block.header().logs_bloom())
+        }
+        let receipts = self.receipts_cache.get(&block_hash)?;
+        let bloom = logs_bloom(receipts.iter().flat_map(|receipt| receipt.logs()));
+        self.bloom_cache.insert(block_hash, bloom);
+        Some(bloom)
+    }
+
+    /// Returns the metadata of the given block if the block is cached.
+    ///
+    /// Metadata derived from a cached block is memoized.
+    fn cached_block_meta(&mut self, block_hash: B256) -> Option<CachedBlockMeta> {
+        if let Some(meta) = self.block_meta_cache.get(&block_hash) {
+            return Some(*meta)
+        }
+        let meta = CachedBlockMeta::new(self.full_block_cache.get(&block_hash)?);
+        self.block_meta_cache.insert(block_hash, meta);
+        Some(meta)
+    }
+
+    /// Returns the block from the main cache, or from the low priority tier if it's only
+    /// cached there.
+    ///
+    /// Blocks found in the low priority tier are not moved to the main cache.
+    fn cached_recovered_block(
+        &mut self,
+        block_hash: B256,
+        promote: bool,
+    ) -> Option<Arc<RecoveredBlock<Provider::Block>>> {
+        let cached = if promote {
+            self.full_block_cache.get(&block_hash).cloned()
+        } else {
+            self.full_block_cache.peek(&block_hash).cloned()
+        };
+        if cached.is_some() {
+            return cached
+        }
+        if promote {
+            self.cold_blocks.get(&block_hash).cloned()
+        } else {
+            self.cold_blocks.peek(&block_hash).cloned()
+        }
+    }
+
+    /// Returns the receipts from the main cache, or from the low priority tier if they're only
+    /// cached there.
+    ///
+    /// See [`Self::cached_recovered_block`].
+    fn cached_receipts(
+        &mut self,
+        block_hash: B256,
+        promote: bool,
+    ) -> Option<Arc<Vec<Provider::Receipt>>> {
+        let cached = if promote {
+            self.receipts_cache.get(&block_hash).cloned()
+        } else {
+            self.receipts_cache.peek(&block_hash).cloned()
+        };
+        if cached.is_some() {
+            return cached
+        }
+        if promote {
+            self.cold_receipts.get(&block_hash).cloned()
+        } else {
+            self.cold_receipts.peek(&block_hash).cloned()
+        }
+    }
+
+    /// Returns up to `max_blocks` connected cached blocks, starting from the given block and
+    /// traversing down through parent hashes until a block is not cached.
+    ///
+    /// The blocks are ordered newest first, or oldest first if `ascending` is set.
+    fn cached_parent_blocks(
+        &mut self,
+        block_hash: B256,
+        max_blocks: usize,
+        ascending: bool,
+    ) -> Vec<Arc<RecoveredBlock<Provider::Block>>> {
+        let mut blocks = Vec::new();
+        let mut current_hash = block_hash;
+
+        // Start with the requested block
+        while blocks.len() < max_blocks {
+            let Some(block) = self.cached_recovered_block(current_hash, true) else {
+                // Break the loop if we can't find the current block
+                break
+            };
+            // Get the parent hash for the next iteration
+            current_hash = block.header().parent_hash();
+            blocks.push(block);
+        }
+
+        if ascending {
+            blocks.reverse();
+        }
+        blocks
+    }
+
+    /// Returns the cached headers, from most to least recently used.
+    fn cached_headers(&self) -> Vec<Provider::Header> {
+        self.headers_cache.iter().map(|(_, header)| header.clone()).collect()
+    }
 }
 
 impl<Provider> EthStateCacheService<Provider, Runtime>
 where
     Provider: BlockReader + BalProvider + Clone + Unpin + 'static,
 {
+    /// Caches a backfilled block and its receipts in the low priority tier, unless they're
+    /// already in the main cache.
+    ///
+    /// The transactions of a block added to the low priority tier are indexed, so lookups by
+    /// transaction hash find it.
+    fn insert_low_priority(
+        &mut self,
+        block: Arc<RecoveredBlock<Provider::Block>>,
+        receipts: Arc<Vec<Provider::Receipt>>,
+    ) {
+        let block_hash = block.hash();
+        if self.full_block_cache.peek(&block_hash).is_none() &&
+            self.cold_blocks.insert(block_hash, block.clone())
+        {
+            self.index_block_transactions(&block);
+        }
+        if self.receipts_cache.peek(&block_hash).is_none() {
+            self.cold_receipts.insert(block_hash, receipts);
+        }
+    }
+
     /// Indexes all transactions in a block by transaction hash.
     fn index_block_transactions(&mut self, block: &RecoveredBlock<Provider::Block>) {
         let block_hash = block.hash();
@@ -369,6 +1257,12 @@ where
         }
     }
 
+    /// Returns the receipt of an indexed transaction if the receipts of its block are cached.
+    fn cached_receipt_by_tx_hash(&mut self, tx_hash: TxHash) -> Option<Provider::Receipt> {
+        let (block_hash, idx) = *self.tx_hash_index.get(&tx_hash)?;
+        self.cached_receipts(block_hash, true)?.get(idx).cloned()
+    }
+
     /// Removes transaction index entries for a reorged block.
     fn remove_block_transactions(&mut self, block: &RecoveredBlock<Provider::Block>) {
         for tx in block.body().transactions() {
@@ -376,12 +1270,111 @@ where
         }
     }
 
+    /// Records the block as the canonical block at its height.
+    fn index_canonical_block(&mut self, block: &RecoveredBlock<Provider::Block>) {
+        self.canonical_hashes.insert(block.header().number(), block.hash());
+    }
+
+    /// Removes the canonical index entry of a reorged block, unless its height was already
+    /// reassigned to another block.
+    fn remove_canonical_block(&mut self, block: &RecoveredBlock<Provider::Block>) {
+        if self.canonical_hashes.peek(&block.header().number()) == Some(&block.hash()) {
+            self.canonical_hashes.remove(&block.header().number());
+        }
+    }
+
+    /// Rewinds the latest canonical block to the parent of the reorged chain, if the current tip
+    /// is part of it.
+    fn rewind_latest(&mut self, reorged: &[Arc<RecoveredBlock<Provider::Block>>]) {
+        let Some((latest_hash, _)) = self.latest else { return };
+        if !reorged.iter().any(|block| block.hash() == latest_hash) {
+            return
+        }
+        self.latest = reorged
+            .iter()
+            .min_by_key(|block| block.header().number())
+            .map(|block| (block.header().parent_hash(), block.header().number().saturating_sub(1)));
+    }
+
+    /// Collects the keys and sizes of the cached blocks, receipts and headers.
+    #[cfg(feature = "cache-snapshot")]
+    fn snapshot(&self) -> CacheSnapshot {
+        CacheSnapshot {
+            blocks: CacheEntriesSnapshot::new(&self.full_block_cache),
+            receipts: CacheEntriesSnapshot::new(&self.receipts_cache),
+            headers: CacheEntriesSnapshot::new(&self.headers_cache),
+        }
+    }
+
+    /// Evicts entries until the block, receipt and header caches fit the shared byte budget.
+    ///
+    /// Always evicts the least recently used entry of the cache that currently uses the most
+    /// memory, so the budget shifts towards whatever is cheap to keep.
+    fn enforce_total_max_bytes(&mut self) {
+        let Some(total_max_bytes) = self.total_max_bytes else { return };
+        loop {
+            let blocks = self.full_block_cache.memory_usage();
+            let receipts = self.receipts_cache.memory_usage();
+            let headers = self.headers_cache.memory_usage();
+            if blocks + receipts + headers <= total_max_bytes {
+                return
+            }
+
+            let evicted = if blocks >= receipts && blocks >= headers {
+                self.full_block_cache.pop_oldest().is_some()
+            } else if receipts >= headers {
+                self.receipts_cache.pop_oldest().is_some()
+            } else {
+                self.headers_cache.pop_oldest().is_some()
+            };
+            if !evicted {
+                return
+            }
+        }
+    }
+
+    /// Registers a caller waiting for the block to be committed, or responds right away if the
+    /// block is already cached.
+    fn wait_for_block(
+        &mut self,
+        block_hash: B256,
+        response_tx: BlockWaiterSender<Provider::Block>,
+    ) {
+        if let Some(block) = self.cached_recovered_block(block_hash, true) {
+            let _ = response_tx.send(block);
+            return
+        }
+        let waiters = self.block_waiters.entry(block_hash).or_default();
+        // drop waiters that gave up, e.g. after a timeout
+        waiters.retain(|waiter| !waiter.is_closed());
+        waiters.push(response_tx);
+    }
+
+    /// Sends a committed block to all callers waiting for it.
+    fn notify_block_waiters(&mut self, block: &Arc<RecoveredBlock<Provider::Block>>) {
+        if let Some(waiters) = self.block_waiters.remove(&block.hash()) {
+            for waiter in waiters {
+                let _ = waiter.send(block.clone());
+            }
+        }
+    }
+
     fn on_new_block(
         &mut self,
         block_hash: B256,
         res: ProviderResult<Option<Arc<RecoveredBlock<Provider::Block>>>>,
     ) {
-        if let Some(queued) = self.full_block_cache.remove(&block_hash) {
+        let duplicate = self.skip_duplicate_inserts &&
+            matches!(&res, Ok(Some(block)) if self.full_block_cache
+                .peek(&block_hash)
+                .is_some_and(|cached| Arc::ptr_eq(cached, block) || cached == block));
+        // a duplicate keeps its cached entry, only the queued senders are taken
+        let queued = if duplicate {
+            self.full_block_cache.take_queued(&block_hash)
+        } else {
+            self.full_block_cache.remove(&block_hash)
+        };
+        if let Some(queued) = queued {
             // send the response to queued senders
             for tx in queued {
                 let _ = tx.send(res.clone());
@@ -390,7 +1383,18 @@ where
 
         // cache good block
         if let Ok(Some(block)) = res {
+            // the senders are recovered now, so the sealed block is no longer needed
+            if let Some(queued) = self.sealed_block_cache.remove(&block_hash) {
+                for tx in queued {
+                    let _ = tx.send(Ok(Some(CachedBlock::Recovered(block.clone()))));
+                }
+            }
+            if duplicate {
+                return
+            }
+            self.cold_blocks.remove(&block_hash);
             self.full_block_cache.insert(block_hash, block);
+            self.enforce_total_max_bytes();
         }
     }
 
@@ -399,7 +1403,17 @@ where
         block_hash: B256,
         res: ProviderResult<Option<Arc<Vec<Provider::Receipt>>>>,
     ) {
-        if let Some(queued) = self.receipts_cache.remove(&block_hash) {
+        let duplicate = self.skip_duplicate_inserts &&
+            matches!(&res, Ok(Some(receipts)) if self.receipts_cache
+                .peek(&block_hash)
+                .is_some_and(|cached| Arc::ptr_eq(cached, receipts) || cached == receipts));
+        // a duplicate keeps its cached entry, only the queued senders are taken
+        let queued = if duplicate {
+            self.receipts_cache.take_queued(&block_hash)
+        } else {
+            self.receipts_cache.remove(&block_hash)
+        };
+        if let Some(queued) = queued {
             // send the response to queued senders
             for tx in queued {
                 let _ = tx.send(res.clone());
@@ -408,8 +1422,41 @@ where
 
         // cache good receipts
         if let Ok(Some(receipts)) = res {
+            if duplicate {
+                return
+            }
+            self.cold_receipts.remove(&block_hash);
             self.receipts_cache.insert(block_hash, receipts);
+            self.enforce_total_max_bytes();
+        }
+    }
+
+    fn on_new_sealed_block(
+        &mut self,
+        block_hash: B256,
+        res: ProviderResult<Option<Arc<SealedBlock<Provider::Block>>>>,
+    ) {
+        if let Some(queued) = self.sealed_block_cache.remove(&block_hash) {
+            let res = res.clone().map(|maybe_block| maybe_block.map(CachedBlock::Sealed));
+            for tx in queued {
+                let _ = tx.send(res.clone());
+            }
+        }
+
+        // the block may have been recovered while the sealed block was fetched
+        if let Ok(Some(block)) = res &&
+            self.full_block_cache.peek(&block_hash).is_none()
+        {
+            self.sealed_block_cache.insert(block_hash, block);
+        }
+    }
+
+    /// Returns the cached block, preferring the block with recovered senders.
+    fn cached_block(&mut self, block_hash: B256) -> Option<CachedBlock<Provider::Block>> {
+        if let Some(block) = self.cached_recovered_block(block_hash, true) {
+            return Some(CachedBlock::Recovered(block))
         }
+        self.sealed_block_cache.get(&block_hash).cloned().map(CachedBlock::Sealed)
     }
 
     fn on_new_bal(&mut self, block_hash: B256, res: ProviderResult<Option<CachedRevmBal>>) {
@@ -424,6 +1471,43 @@ where
         }
     }
 
+    fn on_new_senders(&mut self, block_hash: B256, res: ProviderResult<Option<CachedSenders>>) {
+        if let Some(queued) = self.senders_cache.remove(&block_hash) {
+            for tx in queued {
+                let _ = tx.send(res.clone());
+            }
+        }
+
+        if let Ok(Some(senders)) = res {
+            self.senders_cache.insert(block_hash, senders);
+        }
+    }
+
+    fn on_new_transactions(
+        &mut self,
+        block_hash: B256,
+        res: ProviderResult<Option<Arc<Vec<Provider::Transaction>>>>,
+    ) {
+        if let Some(queued) = self.transactions_cache.remove(&block_hash) {
+            for tx in queued {
+                let _ = tx.send(res.clone());
+            }
+        }
+
+        if let Ok(Some(transactions)) = res {
+            self.transactions_cache.insert(block_hash, transactions);
+        }
+    }
+
+    /// Returns the cached transactions of the block, taken from the full block if it's cached.
+    fn cached_transactions(&mut self, block_hash: B256) -> Option<Arc<Vec<Provider::Transaction>>> {
+        if let Some(transactions) = self.transactions_cache.get(&block_hash) {
+            return Some(transactions.clone())
+        }
+        let block = self.full_block_cache.get(&block_hash)?;
+        Some(Arc::new(block.body().transactions().to_vec()))
+    }
+
     fn on_reorg_block(
         &mut self,
         block_hash: B256,
@@ -437,6 +1521,18 @@ where
         }
     }
 
+    fn on_reorg_sealed_block(
+        &mut self,
+        block_hash: B256,
+        res: ProviderResult<Option<CachedBlock<Provider::Block>>>,
+    ) {
+        if let Some(queued) = self.sealed_block_cache.remove(&block_hash) {
+            for tx in queued {
+                let _ = tx.send(res.clone());
+            }
+        }
+    }
+
     fn on_reorg_receipts(
         &mut self,
         block_hash: B256,
@@ -467,21 +1563,278 @@ where
         }
     }
 
+    fn on_reorg_senders(&mut self, block_hash: B256, res: ProviderResult<Option<CachedSenders>>) {
+        if let Some(queued) = self.senders_cache.remove(&block_hash) {
+            for tx in queued {
+                let _ = tx.send(res.clone());
+            }
+        }
+    }
+
+    fn on_reorg_transactions(
+        &mut self,
+        block_hash: B256,
+        res: ProviderResult<Option<Arc<Vec<Provider::Transaction>>>>,
+    ) {
+        if let Some(queued) = self.transactions_cache.remove(&block_hash) {
+            for tx in queued {
+                let _ = tx.send(res.clone());
+            }
+        }
+    }
+
+    /// Removes all cached entries of the given kinds.
+    fn clear(&mut self, kinds: CacheKinds) {
+        if kinds.contains(CacheKinds::BLOCKS) {
+            self.full_block_cache.clear();
+            self.sealed_block_cache.clear();
+            self.cold_blocks.clear();
+            self.transactions_cache.clear();
+            // the index only resolves to cached blocks
+            self.tx_hash_index.clear();
+            self.block_meta_cache.clear();
+        }
+        if kinds.contains(CacheKinds::RECEIPTS) {
+            self.receipts_cache.clear();
+            self.cold_receipts.clear();
+            // memoized blooms are derived from receipts
+            self.bloom_cache.clear();
+        }
+        if kinds.contains(CacheKinds::HEADERS) {
+            self.headers_cache.clear();
+        }
+        if kinds.contains(CacheKinds::BALS) {
+            self.bal_cache.clear();
+        }
+        if kinds.contains(CacheKinds::SENDERS) {
+            self.senders_cache.clear();
+        }
+    }
+
     /// Shrinks the queues but leaves some space for the next requests
+    ///
+    /// Also drops block waiters that gave up, so abandoned waits for blocks that are never
+    /// committed don't accumulate.
     fn shrink_queues(&mut self) {
+        self.block_waiters.retain(|_, waiters| {
+            waiters.retain(|waiter| !waiter.is_closed());
+            !waiters.is_empty()
+        });
+
         let min_capacity = 2;
         self.full_block_cache.shrink_to(min_capacity);
         self.receipts_cache.shrink_to(min_capacity);
         self.headers_cache.shrink_to(min_capacity);
         self.bal_cache.shrink_to(min_capacity);
+        self.senders_cache.shrink_to(min_capacity);
+        self.transactions_cache.shrink_to(min_capacity);
+        self.sealed_block_cache.shrink_to(min_capacity);
     }
 
-    fn update_cached_metrics(&self) {
-        self.full_block_cache.update_cached_metrics();
-        self.receipts_cache.update_cached_metrics();
-        self.headers_cache.update_cached_metrics();
-        self.bal_cache.update_cached_metrics();
-    }
+    /// Spawns the service as a critical task.
+    ///
+    /// If header persistence is enabled, the headers cache is written to disk on graceful
+    /// shutdown.
+    fn spawn(self, executor: &Runtime) {
+        executor.spawn_critical_with_graceful_shutdown_signal("eth state cache", |shutdown| {
+            self.run_until_graceful_shutdown(shutdown)
+        });
+    }
+
+    /// Runs the service until a graceful shutdown is initiated, then writes the headers cache to
+    /// disk if persistence is enabled.
+    async fn run_until_graceful_shutdown(mut self, shutdown: GracefulShutdown) {
+        let _guard = tokio::select! {
+            _ = &mut self => return,
+            guard = shutdown => guard,
+        };
+
+        let Some(path) = self.header_persist_path.clone() else { return };
+        let headers = self.cached_headers();
+        // the shutdown waits for the guard, so the headers are written before the node exits
+        let _ = self
+            .action_task_spawner
+            .spawn_blocking(move || {
+                if let Err(err) = persist::store_headers(&path, &headers) {
+                    warn!(target: "rpc::eth", %err, ?path, "Failed to persist headers");
+                }
+            })
+            .await;
+    }
+
+    /// Loads the persisted headers on a blocking task and sends the headers that are still part
+    /// of the canonical chain back to the service.
+    fn spawn_load_persisted_headers(&self, path: PathBuf) {
+        let provider = self.provider.clone();
+        let action_tx = self.action_tx.clone();
+        self.action_task_spawner.spawn_blocking_task(async move {
+            let headers = match persist::load_headers::<Provider::Header>(&path) {
+                Ok(headers) => headers,
+                Err(err) => {
+                    warn!(target: "rpc::eth", %err, ?path, "Failed to load persisted headers");
+                    return
+                }
+            };
+
+            let total = headers.len();
+            let headers = canonical_headers(&provider, headers);
+            debug!(target: "rpc::eth", loaded = headers.len(), total, ?path, "Loaded persisted headers");
+            let _ = action_tx.send(CacheAction::PersistedHeaders { headers });
+        });
+    }
+
+    /// Writes the headers cache to disk on a blocking task if persistence is enabled and the
+    /// persist interval elapsed.
+    ///
+    /// The persisted headers are loaded on the first poll.
+    fn poll_persist_headers(&mut self, cx: &mut Context<'_>) {
+        let Some(path) = self.header_persist_path.clone() else { return };
+        if self.header_persist_interval.is_none() {
+            self.spawn_load_persisted_headers(path.clone());
+        }
+        let interval = self.header_persist_interval.get_or_insert_with(|| {
+            let mut interval = tokio::time::interval(HEADER_PERSIST_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+        if interval.poll_tick(cx).is_pending() {
+            return
+        }
+
+        let headers = self.cached_headers();
+        self.action_task_spawner.spawn_blocking_task(async move {
+            if let Err(err) = persist::store_headers(&path, &headers) {
+                warn!(target: "rpc::eth", %err, ?path, "Failed to persist headers");
+            }
+        });
+    }
+
+    fn update_cached_metrics(&self) {
+        self.full_block_cache.update_cached_metrics();
+        self.receipts_cache.update_cached_metrics();
+        self.headers_cache.update_cached_metrics();
+        self.bal_cache.update_cached_metrics();
+        self.senders_cache.update_cached_metrics();
+        self.transactions_cache.update_cached_metrics();
+        self.sealed_block_cache.update_cached_metrics();
+    }
+}
+
+impl<Provider> EthStateCacheService<Provider, Runtime>
+where
+    Provider: BlockReader + BalProvider + Clone + Unpin + 'static,
+{
+    /// Spawns a blocking task that fetches the entry of the given kind from the provider and
+    /// reports the result back to the service.
+    ///
+    /// If `retry_on_panic` is set and the task panics before reporting a result, the fetch is
+    /// spawned once more instead of failing all queued requests.
+    fn spawn_fetch(&self, kind: CacheKind, block_hash: B256, retry_on_panic: bool) {
+        let provider = self.provider.clone();
+        let rate_limiter = self.rate_limiter.clone();
+        let jitter = self.fetch_jitter;
+        let mut action_sender = ActionSender::new(kind, block_hash, self.action_tx.clone())
+            .with_retry_on_panic(retry_on_panic);
+        self.action_task_spawner.spawn_blocking_task(async move {
+            fetch_jitter(jitter).await;
+            // Acquire permit
+            let _permit = rate_limiter.acquire().await;
+            match kind {
+                CacheKind::Block => {
+                    // Only look in the database to prevent situations where we
+                    // looking up the tree is blocking
+                    let block_sender = provider
+                        .sealed_block_with_senders(
+                            BlockHashOrNumber::Hash(block_hash),
+                            TransactionVariant::WithHash,
+                        )
+                        .map(|maybe_block| maybe_block.map(Arc::new));
+                    action_sender.send_block(block_sender);
+                }
+                CacheKind::SealedBlock => {
+                    // senders are recovered lazily, once a consumer needs them
+                    let res =
+                        provider.block(BlockHashOrNumber::Hash(block_hash)).map(|maybe_block| {
+                            maybe_block.map(|block| {
+                                Arc::new(SealedBlock::new_unchecked(block, block_hash))
+                            })
+                        });
+                    action_sender.send_sealed_block(res);
+                }
+                CacheKind::Receipt => {
+                    let res = provider
+                        .receipts_by_block(block_hash.into())
+                        .map(|maybe_receipts| maybe_receipts.map(Arc::new));
+                    action_sender.send_receipts(res);
+                }
+                CacheKind::Header => {
+                    let header = provider.header(block_hash).and_then(|header| {
+                        header.ok_or_else(|| ProviderError::HeaderNotFound(block_hash.into()))
+                    });
+                    action_sender.send_header(header);
+                }
+                CacheKind::Bal => {
+                    let res = provider
+                        .bal_store()
+                        .revm_bal_by_hash(block_hash)
+                        .map(|maybe_bal| maybe_bal.map(CachedRevmBal::new));
+                    action_sender.send_bal(res);
+                }
+                CacheKind::Senders => {
+                    let res = senders_by_block_hash(&provider, block_hash)
+                        .map(|maybe_senders| maybe_senders.map(CachedSenders::new));
+                    action_sender.send_senders(res);
+                }
+                CacheKind::Transactions => {
+                    let res = provider
+                        .transactions_by_block(block_hash.into())
+                        .map(|maybe_transactions| maybe_transactions.map(Arc::new));
+                    action_sender.send_transactions(res);
+                }
+            }
+        });
+    }
+
+    /// Spawns a blocking task that recovers the senders of a cached sealed block and reports the
+    /// result back to the service like a block fetch.
+    ///
+    /// This doesn't touch the database, so no fetch permit is acquired.
+    fn spawn_sender_recovery(
+        &self,
+        block_hash: B256,
+        block: Arc<SealedBlock<Provider::Block>>,
+        retry_on_panic: bool,
+    ) {
+        let mut action_sender =
+            ActionSender::new(CacheKind::Block, block_hash, self.action_tx.clone())
+                .with_retry_on_panic(retry_on_panic);
+        self.action_task_spawner.spawn_blocking_task(async move {
+            let res = Arc::unwrap_or_clone(block)
+                .try_recover()
+                .map(|block| Some(Arc::new(block)))
+                .map_err(|_| ProviderError::SenderRecoveryError);
+            action_sender.send_block(res);
+        });
+    }
+
+    /// Spawns a blocking task that looks up the receipt of a transaction that isn't cached and
+    /// responds directly.
+    ///
+    /// The receipt isn't cached, because the receipts cache holds the receipts of whole blocks.
+    fn spawn_receipt_by_tx_hash_fetch(
+        &self,
+        tx_hash: TxHash,
+        response_tx: ReceiptResponseSender<Provider::Receipt>,
+    ) {
+        let provider = self.provider.clone();
+        let rate_limiter = self.rate_limiter.clone();
+        let jitter = self.fetch_jitter;
+        self.action_task_spawner.spawn_blocking_task(async move {
+            fetch_jitter(jitter).await;
+            let _permit = rate_limiter.acquire().await;
+            let _ = response_tx.send(provider.receipt_by_hash(tx_hash));
+        });
+    }
 }
 
 impl<Provider> Future for EthStateCacheService<Provider, Runtime>
@@ -493,6 +1846,8 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
+        this.poll_persist_headers(cx);
+
         loop {
             let Poll::Ready(action) = this.action_rx.poll_next_unpin(cx) else {
                 // shrink queues if we don't have any work to do
@@ -507,65 +1862,83 @@ where
                 Some(action) => {
                     match action {
                         CacheAction::GetCachedBlock { block_hash, response_tx } => {
-                            let _ =
-                                response_tx.send(this.full_block_cache.get(&block_hash).cloned());
+                            let _ = response_tx.send(this.cached_recovered_block(block_hash, true));
                         }
                         CacheAction::GetCachedBlockAndReceipts { block_hash, response_tx } => {
-                            let block = this.full_block_cache.get(&block_hash).cloned();
-                            let receipts = this.receipts_cache.get(&block_hash).cloned();
+                            let block = this.cached_recovered_block(block_hash, true);
+                            let receipts = this.cached_receipts(block_hash, true);
                             let _ = response_tx.send((block, receipts));
                         }
-                        CacheAction::GetBlockWithSenders { block_hash, response_tx } => {
-                            if let Some(block) = this.full_block_cache.get(&block_hash).cloned() {
+                        CacheAction::GetBlockWithSenders { block_hash, promote, response_tx } => {
+                            if let Some(block) = this.cached_recovered_block(block_hash, promote) {
                                 let _ = response_tx.send(Ok(Some(block)));
                                 continue
                             }
 
+                            if this.full_block_cache.is_queue_full(&block_hash) {
+                                let _ = response_tx.send(Err(CacheQueueFull.into()));
+                                continue
+                            }
+
                             // block is not in the cache, request it if this is the first consumer
                             if this.full_block_cache.queue(block_hash, response_tx) {
-                                let provider = this.provider.clone();
-                                let action_tx = this.action_tx.clone();
-                                let rate_limiter = this.rate_limiter.clone();
-                                let mut action_sender =
-                                    ActionSender::new(CacheKind::Block, block_hash, action_tx);
-                                this.action_task_spawner.spawn_blocking_task(async move {
-                                    // Acquire permit
-                                    let _permit = rate_limiter.acquire().await;
-                                    // Only look in the database to prevent situations where we
-                                    // looking up the tree is blocking
-                                    let block_sender = provider
-                                        .sealed_block_with_senders(
-                                            BlockHashOrNumber::Hash(block_hash),
-                                            TransactionVariant::WithHash,
-                                        )
-                                        .map(|maybe_block| maybe_block.map(Arc::new));
-                                    action_sender.send_block(block_sender);
-                                });
+                                // recover the senders of a cached sealed block instead of
+                                // fetching the block again
+                                if let Some(block) =
+                                    this.sealed_block_cache.peek(&block_hash).cloned()
+                                {
+                                    this.spawn_sender_recovery(
+                                        block_hash,
+                                        block,
+                                        this.panic_policy.is_retry(),
+                                    );
+                                } else {
+                                    this.spawn_fetch(
+                                        CacheKind::Block,
+                                        block_hash,
+                                        this.panic_policy.is_retry(),
+                                    );
+                                }
+                            }
+                        }
+                        CacheAction::GetSealedBlock { block_hash, response_tx } => {
+                            if let Some(block) = this.cached_block(block_hash) {
+                                let _ = response_tx.send(Ok(Some(block)));
+                                continue
+                            }
+
+                            if this.sealed_block_cache.is_queue_full(&block_hash) {
+                                let _ = response_tx.send(Err(CacheQueueFull.into()));
+                                continue
+                            }
+
+                            if this.sealed_block_cache.queue(block_hash, response_tx) {
+                                this.spawn_fetch(
+                                    CacheKind::SealedBlock,
+                                    block_hash,
+                                    this.panic_policy.is_retry(),
+                                );
                             }
                         }
-                        CacheAction::GetReceipts { block_hash, response_tx } => {
+                        CacheAction::GetReceipts { block_hash, promote, response_tx } => {
                             // check if block is cached
-                            if let Some(receipts) = this.receipts_cache.get(&block_hash).cloned() {
+                            if let Some(receipts) = this.cached_receipts(block_hash, promote) {
                                 let _ = response_tx.send(Ok(Some(receipts)));
                                 continue
                             }
 
+                            if this.receipts_cache.is_queue_full(&block_hash) {
+                                let _ = response_tx.send(Err(CacheQueueFull.into()));
+                                continue
+                            }
+
                             // block is not in the cache, request it if this is the first consumer
                             if this.receipts_cache.queue(block_hash, response_tx) {
-                                let provider = this.provider.clone();
-                                let action_tx = this.action_tx.clone();
-                                let rate_limiter = this.rate_limiter.clone();
-                                let mut action_sender =
-                                    ActionSender::new(CacheKind::Receipt, block_hash, action_tx);
-                                this.action_task_spawner.spawn_blocking_task(async move {
-                                    // Acquire permit
-                                    let _permit = rate_limiter.acquire().await;
-                                    let res = provider
-                                        .receipts_by_block(block_hash.into())
-                                        .map(|maybe_receipts| maybe_receipts.map(Arc::new));
-
-                                    action_sender.send_receipts(res);
-                                });
+                                this.spawn_fetch(
+                                    CacheKind::Receipt,
+                                    block_hash,
+                                    this.panic_policy.is_retry(),
+                                );
                             }
                         }
                         CacheAction::GetHeader { block_hash, response_tx } => {
@@ -576,30 +1949,84 @@ where
                             }
 
                             // it's possible we have the entire block cached
-                            if let Some(block) = this.full_block_cache.get(&block_hash) {
+                            if let Some(block) = this.cached_recovered_block(block_hash, true) {
                                 let _ = response_tx.send(Ok(block.clone_header()));
                                 continue
                             }
 
+                            if this.headers_cache.is_queue_full(&block_hash) {
+                                let _ = response_tx.send(Err(CacheQueueFull.into()));
+                                continue
+                            }
+
                             // header is not in the cache, request it if this is the first
                             // consumer
                             if this.headers_cache.queue(block_hash, response_tx) {
-                                let provider = this.provider.clone();
-                                let action_tx = this.action_tx.clone();
-                                let rate_limiter = this.rate_limiter.clone();
-                                let mut action_sender =
-                                    ActionSender::new(CacheKind::Header, block_hash, action_tx);
-                                this.action_task_spawner.spawn_blocking_task(async move {
-                                    // Acquire permit
-                                    let _permit = rate_limiter.acquire().await;
-                                    let header = provider.header(block_hash).and_then(|header| {
+                                this.spawn_fetch(
+                                    CacheKind::Header,
+                                    block_hash,
+                                    this.panic_policy.is_retry(),
+                                );
+                            }
+                        }
+                        CacheAction::GetHeaderByNumber { block_number, response_tx } => {
+                            if let Some(header) = this.cached_header_by_number(block_number) {
+                                let _ = response_tx.send(Ok(header));
+                                continue
+                            }
+
+                            let provider = this.provider.clone();
+                            let action_tx = this.action_tx.clone();
+                            let rate_limiter = this.rate_limiter.clone();
+                            let jitter = this.fetch_jitter;
+                            this.action_task_spawner.spawn_blocking_task(async move {
+                                fetch_jitter(jitter).await;
+                                let _permit = rate_limiter.acquire().await;
+                                let header =
+                                    provider.header_by_number(block_number).and_then(|header| {
                                         header.ok_or_else(|| {
-                                            ProviderError::HeaderNotFound(block_hash.into())
+                                            ProviderError::HeaderNotFound(block_number.into())
                                         })
                                     });
-                                    action_sender.send_header(header);
-                                });
+                                // cache the header by its hash for subsequent lookups
+                                if let Ok(header) = &header {
+                                    let _ = action_tx.send(CacheAction::HeaderResult {
+                                        block_hash: header.hash_slow(),
+                                        res: Box::new(Ok(header.clone())),
+                                    });
+                                }
+                                let _ = response_tx.send(header);
+                            });
+                        }
+                        CacheAction::GetBloom { block_hash, response_tx } => {
+                            if let Some(bloom) = this.cached_block_bloom(block_hash) {
+                                let _ = response_tx.send(Ok(Some(bloom)));
+                                continue
                             }
+
+                            // the header carries the aggregate bloom and is cheaper to load than
+                            // the receipts
+                            let provider = this.provider.clone();
+                            let action_tx = this.action_tx.clone();
+                            let rate_limiter = this.rate_limiter.clone();
+                            let jitter = this.fetch_jitter;
+                            this.action_task_spawner.spawn_blocking_task(async move {
+                                fetch_jitter(jitter).await;
+                                let _permit = rate_limiter.acquire().await;
+                                let header = provider.header(block_hash);
+                                let bloom = header
+                                    .as_ref()
+                                    .map(|header| header.as_ref().map(|header| header.logs_bloom()))
+                                    .map_err(Clone::clone);
+                                let _ = response_tx.send(bloom);
+                                // cache the header for subsequent lookups
+                                if let Ok(Some(header)) = header {
+                                    let _ = action_tx.send(CacheAction::HeaderResult {
+                                        block_hash,
+                                        res: Box::new(Ok(header)),
+                                    });
+                                }
+                            });
                         }
                         CacheAction::GetBal { block_hash, response_tx } => {
                             if let Some(bal) = this.bal_cache.get(&block_hash).cloned() {
@@ -607,28 +2034,80 @@ where
                                 continue
                             }
 
+                            if this.bal_cache.is_queue_full(&block_hash) {
+                                let _ = response_tx.send(Err(CacheQueueFull.into()));
+                                continue
+                            }
+
                             if this.bal_cache.queue(block_hash, response_tx) {
-                                let provider = this.provider.clone();
-                                let action_tx = this.action_tx.clone();
-                                let rate_limiter = this.rate_limiter.clone();
-                                let mut action_sender =
-                                    ActionSender::new(CacheKind::Bal, block_hash, action_tx);
-                                this.action_task_spawner.spawn_blocking_task(async move {
-                                    let _permit = rate_limiter.acquire().await;
-                                    let res = provider
-                                        .bal_store()
-                                        .revm_bal_by_hash(block_hash)
-                                        .map(|maybe_bal| maybe_bal.map(CachedRevmBal::new));
-                                    action_sender.send_bal(res);
-                                });
+                                this.spawn_fetch(
+                                    CacheKind::Bal,
+                                    block_hash,
+                                    this.panic_policy.is_retry(),
+                                );
+                            }
+                        }
+                        CacheAction::GetSenders { block_hash, response_tx } => {
+                            if let Some(senders) = this.senders_cache.get(&block_hash).cloned() {
+                                let _ = response_tx.send(Ok(Some(senders)));
+                                continue
+                            }
+
+                            // it's possible we have the entire block cached
+                            if let Some(block) = this.full_block_cache.get(&block_hash) {
+                                let senders = CachedSenders::new(block.senders().to_vec());
+                                this.senders_cache.insert(block_hash, senders.clone());
+                                let _ = response_tx.send(Ok(Some(senders)));
+                                continue
+                            }
+
+                            if this.senders_cache.is_queue_full(&block_hash) {
+                                let _ = response_tx.send(Err(CacheQueueFull.into()));
+                                continue
+                            }
+
+                            if this.senders_cache.queue(block_hash, response_tx) {
+                                this.spawn_fetch(
+                                    CacheKind::Senders,
+                                    block_hash,
+                                    this.panic_policy.is_retry(),
+                                );
+                            }
+                        }
+                        CacheAction::GetTransactions { block_hash, response_tx } => {
+                            if let Some(transactions) = this.cached_transactions(block_hash) {
+                                let _ = response_tx.send(Ok(Some(transactions)));
+                                continue
+                            }
+
+                            if this.transactions_cache.is_queue_full(&block_hash) {
+                                let _ = response_tx.send(Err(CacheQueueFull.into()));
+                                continue
+                            }
+
+                            if this.transactions_cache.queue(block_hash, response_tx) {
+                                this.spawn_fetch(
+                                    CacheKind::Transactions,
+                                    block_hash,
+                                    this.panic_policy.is_retry(),
+                                );
                             }
                         }
                         CacheAction::ReceiptsResult { block_hash, res } => {
                             this.on_new_receipts(block_hash, res);
                         }
+                        CacheAction::SealedBlockResult { block_hash, res } => {
+                            this.on_new_sealed_block(block_hash, res);
+                        }
                         CacheAction::BalResult { block_hash, res } => {
                             this.on_new_bal(block_hash, res);
                         }
+                        CacheAction::SendersResult { block_hash, res } => {
+                            this.on_new_senders(block_hash, res);
+                        }
+                        CacheAction::TransactionsResult { block_hash, res } => {
+                            this.on_new_transactions(block_hash, res);
+                        }
                         CacheAction::BlockWithSendersResult { block_hash, res } => match res {
                             Ok(Some(block_with_senders)) => {
                                 this.on_new_block(block_hash, Ok(Some(block_with_senders)));
@@ -652,12 +2131,25 @@ where
                             // cache good header
                             if let Ok(data) = res {
                                 this.headers_cache.insert(block_hash, data);
+                                this.enforce_total_max_bytes();
+                            }
+                        }
+                        CacheAction::PersistedHeaders { headers } => {
+                            // headers are persisted from most to least recently used
+                            for (block_hash, header) in headers.into_iter().rev() {
+                                this.headers_cache.insert(block_hash, header);
                             }
+                            this.enforce_total_max_bytes();
                         }
                         CacheAction::CacheNewCanonicalChain { chain_change } => {
+                            if let Some(tip) = chain_change.blocks.last() {
+                                this.latest = Some((tip.hash(), tip.header().number()));
+                            }
                             for block in chain_change.blocks {
                                 // Index transactions before caching the block
                                 this.index_block_transactions(&block);
+                                this.index_canonical_block(&block);
+                                this.notify_block_waiters(&block);
                                 this.on_new_block(block.hash(), Ok(Some(block)));
                             }
 
@@ -669,14 +2161,27 @@ where
                             }
                         }
                         CacheAction::RemoveReorgedChain { chain_change } => {
+                            this.reorg_epoch += 1;
+                            this.rewind_latest(&chain_change.blocks);
                             for block in chain_change.blocks {
                                 let block_hash = block.hash();
                                 let header = block.clone_header();
                                 // Remove transaction index entries for reorged blocks
                                 this.remove_block_transactions(&block);
+                                this.remove_canonical_block(&block);
+                                this.bloom_cache.remove(&block_hash);
+                                this.block_meta_cache.remove(&block_hash);
+                                this.cold_blocks.remove(&block_hash);
+                                this.cold_receipts.remove(&block_hash);
+                                this.on_reorg_sealed_block(
+                                    block_hash,
+                                    Ok(Some(CachedBlock::Recovered(block.clone()))),
+                                );
                                 this.on_reorg_block(block_hash, Ok(Some(block)));
                                 this.on_reorg_header(block_hash, Ok(header));
                                 this.on_reorg_bal(block_hash, Ok(None));
+                                this.on_reorg_senders(block_hash, Ok(None));
+                                this.on_reorg_transactions(block_hash, Ok(None));
                             }
 
                             for block_receipts in chain_change.receipts {
@@ -686,39 +2191,69 @@ where
                                 );
                             }
                         }
+                        CacheAction::Clear { kinds } => {
+                            this.clear(kinds);
+                        }
+                        CacheAction::InsertLowPriority { block, receipts } => {
+                            this.insert_low_priority(block, receipts);
+                        }
+                        CacheAction::RetryFetch { kind, block_hash } => {
+                            debug!(
+                                target: "rpc::eth",
+                                ?kind,
+                                %block_hash,
+                                "Retrying panicked cache fetch"
+                            );
+                            this.spawn_fetch(kind, block_hash, false);
+                        }
+                        CacheAction::GetLatest { response_tx } => {
+                            let _ = response_tx.send(this.latest);
+                        }
+                        CacheAction::GetReorgEpoch { response_tx } => {
+                            let _ = response_tx.send(this.reorg_epoch);
+                        }
+                        CacheAction::GetBlockMeta { block_hash, response_tx } => {
+                            let _ = response_tx.send(this.cached_block_meta(block_hash));
+                        }
+                        CacheAction::CacheBlockMeta { block_hash, meta } => {
+                            this.block_meta_cache.insert(block_hash, meta);
+                        }
+                        CacheAction::IsFetching { block_hash, response_tx } => {
+                            let _ = response_tx.send(this.full_block_cache.is_queued(&block_hash));
+                        }
+                        #[cfg(feature = "cache-snapshot")]
+                        CacheAction::Snapshot { response_tx } => {
+                            let _ = response_tx.send(this.snapshot());
+                        }
                         CacheAction::GetCachedParentBlocks {
                             block_hash,
                             max_blocks,
+                            ascending,
                             response_tx,
                         } => {
-                            let mut blocks = Vec::new();
-                            let mut current_hash = block_hash;
-
-                            // Start with the requested block
-                            while blocks.len() < max_blocks {
-                                if let Some(block) =
-                                    this.full_block_cache.get(&current_hash).cloned()
-                                {
-                                    // Get the parent hash for the next iteration
-                                    current_hash = block.header().parent_hash();
-                                    blocks.push(block);
-                                } else {
-                                    // Break the loop if we can't find the current block
-                                    break;
-                                }
-                            }
-
-                            let _ = response_tx.send(blocks);
+                            let _ = response_tx
+                                .send(this.cached_parent_blocks(block_hash, max_blocks, ascending));
                         }
                         CacheAction::GetTransactionByHash { tx_hash, response_tx } => {
-                            let result =
-                                this.tx_hash_index.get(&tx_hash).and_then(|(block_hash, idx)| {
-                                    let block = this.full_block_cache.get(block_hash).cloned()?;
-                                    let receipts = this.receipts_cache.get(block_hash).cloned();
-                                    Some(CachedTransaction::new(block, *idx, receipts))
-                                });
+                            let result = this.tx_hash_index.get(&tx_hash).copied().and_then(
+                                |(block_hash, idx)| {
+                                    let block = this.cached_recovered_block(block_hash, true)?;
+                                    let receipts = this.cached_receipts(block_hash, true);
+                                    Some(CachedTransaction::new(block, idx, receipts))
+                                },
+                            );
                             let _ = response_tx.send(result);
                         }
+                        CacheAction::WaitForBlock { block_hash, response_tx } => {
+                            this.wait_for_block(block_hash, response_tx);
+                        }
+                        CacheAction::GetReceiptByTxHash { tx_hash, response_tx } => {
+                            if let Some(receipt) = this.cached_receipt_by_tx_hash(tx_hash) {
+                                let _ = response_tx.send(Ok(Some(receipt)));
+                                continue
+                            }
+                            this.spawn_receipt_by_tx_hash_fetch(tx_hash, response_tx);
+                        }
                     };
                     this.update_cached_metrics();
                 }
@@ -731,20 +2266,44 @@ where
 enum CacheAction<B: Block, R> {
     GetBlockWithSenders {
         block_hash: B256,
+        /// Whether a cached block is marked as recently used
+        promote: bool,
         response_tx: BlockWithSendersResponseSender<B>,
     },
+    GetSealedBlock {
+        block_hash: B256,
+        response_tx: SealedBlockResponseSender<B>,
+    },
     GetHeader {
         block_hash: B256,
         response_tx: HeaderResponseSender<B::Header>,
     },
+    GetHeaderByNumber {
+        block_number: BlockNumber,
+        response_tx: HeaderResponseSender<B::Header>,
+    },
     GetReceipts {
         block_hash: B256,
+        /// Whether cached receipts are marked as recently used
+        promote: bool,
         response_tx: ReceiptsResponseSender<R>,
     },
     GetBal {
         block_hash: B256,
         response_tx: BalResponseSender,
     },
+    GetBloom {
+        block_hash: B256,
+        response_tx: BloomResponseSender,
+    },
+    GetSenders {
+        block_hash: B256,
+        response_tx: SendersResponseSender,
+    },
+    GetTransactions {
+        block_hash: B256,
+        response_tx: TransactionsResponseSender<<B::Body as BlockBody>::Transaction>,
+    },
     GetCachedBlock {
         block_hash: B256,
         response_tx: CachedBlockResponseSender<B>,
@@ -757,6 +2316,10 @@ enum CacheAction<B: Block, R> {
         block_hash: B256,
         res: ProviderResult<Option<Arc<RecoveredBlock<B>>>>,
     },
+    SealedBlockResult {
+        block_hash: B256,
+        res: ProviderResult<Option<Arc<SealedBlock<B>>>>,
+    },
     ReceiptsResult {
         block_hash: B256,
         res: ProviderResult<Option<Arc<Vec<R>>>>,
@@ -769,15 +2332,68 @@ enum CacheAction<B: Block, R> {
         block_hash: B256,
         res: ProviderResult<Option<CachedRevmBal>>,
     },
+    SendersResult {
+        block_hash: B256,
+        res: ProviderResult<Option<CachedSenders>>,
+    },
+    TransactionsResult {
+        block_hash: B256,
+        res: ProviderResult<Option<Arc<Vec<<B::Body as BlockBody>::Transaction>>>>,
+    },
+    /// Persisted headers that are still canonical, from most to least recently used
+    PersistedHeaders {
+        headers: Vec<(B256, B::Header)>,
+    },
     CacheNewCanonicalChain {
         chain_change: ChainChange<B, R>,
     },
     RemoveReorgedChain {
         chain_change: ChainChange<B, R>,
     },
+    Clear {
+        kinds: CacheKinds,
+    },
+    /// Cache a backfilled block in the low priority tier
+    InsertLowPriority {
+        block: Arc<RecoveredBlock<B>>,
+        receipts: Arc<Vec<R>>,
+    },
+    GetLatest {
+        response_tx: LatestResponseSender,
+    },
+    /// Look up the number of reorgs applied so far
+    GetReorgEpoch {
+        response_tx: ReorgEpochResponseSender,
+    },
+    /// Look up the memoized metadata of a cached block
+    GetBlockMeta {
+        block_hash: B256,
+        response_tx: BlockMetaResponseSender,
+    },
+    /// Memoize the metadata derived from a block fetched outside of the cache
+    CacheBlockMeta {
+        block_hash: B256,
+        meta: CachedBlockMeta,
+    },
+    /// Check whether a block is being fetched
+    IsFetching {
+        block_hash: B256,
+        response_tx: oneshot::Sender<bool>,
+    },
+    #[cfg(feature = "cache-snapshot")]
+    Snapshot {
+        response_tx: oneshot::Sender<CacheSnapshot>,
+    },
+    /// Respawn a fetch whose task panicked before reporting a result
+    RetryFetch {
+        kind: CacheKind,
+        block_hash: B256,
+    },
     GetCachedParentBlocks {
         block_hash: B256,
         max_blocks: usize,
+        /// Whether the blocks are returned oldest first
+        ascending: bool,
         response_tx: CachedParentBlocksResponseSender<B>,
     },
     /// Look up a transaction's cached data by its hash
@@ -785,6 +2401,32 @@ enum CacheAction<B: Block, R> {
         tx_hash: TxHash,
         response_tx: TransactionHashResponseSender<B, R>,
     },
+    GetReceiptByTxHash {
+        tx_hash: TxHash,
+        response_tx: ReceiptResponseSender<R>,
+    },
+    WaitForBlock {
+        block_hash: B256,
+        response_tx: BlockWaiterSender<B>,
+    },
+}
+
+/// Returns the given headers with their hashes, keeping only the headers that are still part of
+/// the canonical chain.
+///
+/// This drops persisted headers of blocks that were reorged out while the node was offline.
+fn canonical_headers<H: BlockHeader + Sealable>(
+    provider: &impl BlockHashReader,
+    headers: Vec<H>,
+) -> Vec<(B256, H)> {
+    headers
+        .into_iter()
+        .filter_map(|header| {
+            let block_hash = header.hash_slow();
+            (provider.block_hash(header.number()).ok().flatten() == Some(block_hash))
+                .then_some((block_hash, header))
+        })
+        .collect()
 }
 
 struct BlockReceipts<R> {
@@ -815,15 +2457,24 @@ impl<B: Block, R: Clone> ChainChange<B, R> {
             .unzip();
         Self { blocks, receipts }
     }
+
+    /// Keeps only the blocks and receipts whose block hash matches the predicate.
+    fn retain(&mut self, mut f: impl FnMut(B256) -> bool) {
+        self.blocks.retain(|block| f(block.hash()));
+        self.receipts.retain(|receipts| f(receipts.block_hash));
+    }
 }
 
 /// Identifier for the caches.
 #[derive(Copy, Clone, Debug)]
 enum CacheKind {
     Block,
+    SealedBlock,
     Receipt,
     Header,
     Bal,
+    Senders,
+    Transactions,
 }
 
 /// Drop aware sender struct that ensures a response is always emitted even if the db task panics
@@ -835,11 +2486,19 @@ struct ActionSender<B: Block, R: Send + Sync> {
     kind: CacheKind,
     blockhash: B256,
     tx: Option<UnboundedSender<CacheAction<B, R>>>,
+    /// Whether a drop without a result requests a retry instead of emitting an error.
+    retry_on_panic: bool,
 }
 
 impl<R: Send + Sync, B: Block> ActionSender<B, R> {
     const fn new(kind: CacheKind, blockhash: B256, tx: UnboundedSender<CacheAction<B, R>>) -> Self {
-        Self { kind, blockhash, tx: Some(tx) }
+        Self { kind, blockhash, tx: Some(tx), retry_on_panic: false }
+    }
+
+    /// Requests a retry of the fetch instead of an error response if dropped without a result.
+    const fn with_retry_on_panic(mut self, retry_on_panic: bool) -> Self {
+        self.retry_on_panic = retry_on_panic;
+        self
     }
 
     fn send_block(&mut self, block_sender: Result<Option<Arc<RecoveredBlock<B>>>, ProviderError>) {
@@ -851,6 +2510,13 @@ impl<R: Send + Sync, B: Block> ActionSender<B, R> {
         }
     }
 
+    fn send_sealed_block(&mut self, block: Result<Option<Arc<SealedBlock<B>>>, ProviderError>) {
+        if let Some(tx) = self.tx.take() {
+            let _ =
+                tx.send(CacheAction::SealedBlockResult { block_hash: self.blockhash, res: block });
+        }
+    }
+
     fn send_receipts(&mut self, receipts: Result<Option<Arc<Vec<R>>>, ProviderError>) {
         if let Some(tx) = self.tx.take() {
             let _ =
@@ -872,15 +2538,43 @@ impl<R: Send + Sync, B: Block> ActionSender<B, R> {
             let _ = tx.send(CacheAction::BalResult { block_hash: self.blockhash, res: bal });
         }
     }
+
+    fn send_senders(&mut self, senders: Result<Option<CachedSenders>, ProviderError>) {
+        if let Some(tx) = self.tx.take() {
+            let _ =
+                tx.send(CacheAction::SendersResult { block_hash: self.blockhash, res: senders });
+        }
+    }
+
+    fn send_transactions(
+        &mut self,
+        transactions: Result<Option<Arc<Vec<<B::Body as BlockBody>::Transaction>>>, ProviderError>,
+    ) {
+        if let Some(tx) = self.tx.take() {
+            let _ = tx.send(CacheAction::TransactionsResult {
+                block_hash: self.blockhash,
+                res: transactions,
+            });
+        }
+    }
 }
 impl<R: Send + Sync, B: Block> Drop for ActionSender<B, R> {
     fn drop(&mut self) {
         if let Some(tx) = self.tx.take() {
+            if self.retry_on_panic {
+                let _ = tx
+                    .send(CacheAction::RetryFetch { kind: self.kind, block_hash: self.blockhash });
+                return
+            }
             let msg = match self.kind {
                 CacheKind::Block => CacheAction::BlockWithSendersResult {
                     block_hash: self.blockhash,
                     res: Err(CacheServiceUnavailable.into()),
                 },
+                CacheKind::SealedBlock => CacheAction::SealedBlockResult {
+                    block_hash: self.blockhash,
+                    res: Err(CacheServiceUnavailable.into()),
+                },
                 CacheKind::Receipt => CacheAction::ReceiptsResult {
                     block_hash: self.blockhash,
                     res: Err(CacheServiceUnavailable.into()),
@@ -893,6 +2587,14 @@ impl<R: Send + Sync, B: Block> Drop for ActionSender<B, R> {
                     block_hash: self.blockhash,
                     res: Err(CacheServiceUnavailable.into()),
                 },
+                CacheKind::Senders => CacheAction::SendersResult {
+                    block_hash: self.blockhash,
+                    res: Err(CacheServiceUnavailable.into()),
+                },
+                CacheKind::Transactions => CacheAction::TransactionsResult {
+                    block_hash: self.blockhash,
+                    res: Err(CacheServiceUnavailable.into()),
+                },
             };
             let _ = tx.send(msg);
         }
@@ -917,175 +2619,957 @@ pub async fn cache_new_blocks_task<St, N: NodePrimitives>(
                 eth_state_cache.to_service.send(CacheAction::RemoveReorgedChain { chain_change });
         }
 
-        let chain_change = ChainChange::new(event.committed());
+        let chain_change = ChainChange::new(event.committed());
+
+        let _ =
+            eth_state_cache.to_service.send(CacheAction::CacheNewCanonicalChain { chain_change });
+    }
+}
+
+/// Like [`cache_new_blocks_task`], but holds committed blocks back for `debounce` before caching
+/// them.
+///
+/// Blocks that are reverted while they are held back are never cached, so tips that are
+/// immediately reorged out during reorg flapping don't churn the cache. Reorged blocks are still
+/// removed from the cache immediately. A zero `debounce` caches committed blocks immediately.
+pub async fn cache_new_blocks_task_with_debounce<St, N: NodePrimitives>(
+    eth_state_cache: EthStateCache<N>,
+    mut events: St,
+    debounce: Duration,
+) where
+    St: Stream<Item = CanonStateNotification<N>> + Unpin + 'static,
+{
+    if debounce.is_zero() {
+        return cache_new_blocks_task(eth_state_cache, events).await
+    }
+
+    // committed chains waiting to be cached, in commit order
+    let mut pending = VecDeque::<(tokio::time::Instant, ChainChange<N::Block, N::Receipt>)>::new();
+    loop {
+        let next_deadline = pending.front().map(|(deadline, _)| *deadline);
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else { break };
+
+                if let Some(reverted) = event.reverted() {
+                    let chain_change = ChainChange::new(reverted);
+                    for (_, held) in &mut pending {
+                        held.retain(|hash| {
+                            !chain_change.blocks.iter().any(|block| block.hash() == hash)
+                        });
+                    }
+
+                    let _ = eth_state_cache
+                        .to_service
+                        .send(CacheAction::RemoveReorgedChain { chain_change });
+                }
+
+                let deadline = tokio::time::Instant::now() + debounce;
+                pending.push_back((deadline, ChainChange::new(event.committed())));
+            }
+            _ = tokio::time::sleep_until(next_deadline.unwrap_or_else(tokio::time::Instant::now)),
+                if next_deadline.is_some() =>
+            {
+                if let Some((_, chain_change)) = pending.pop_front() &&
+                    !chain_change.blocks.is_empty()
+                {
+                    let _ = eth_state_cache
+                        .to_service
+                        .send(CacheAction::CacheNewCanonicalChain { chain_change });
+                }
+            }
+        }
+    }
+
+    // the stream ended, blocks that are still canonical are cached right away
+    for (_, chain_change) in pending {
+        if !chain_change.blocks.is_empty() {
+            let _ = eth_state_cache
+                .to_service
+                .send(CacheAction::CacheNewCanonicalChain { chain_change });
+        }
+    }
+}
+
+/// Cached decoded revm BAL.
+#[derive(Clone, Debug)]
+pub(crate) struct CachedRevmBal(Arc<DecodedBal<Arc<RevmBal>>>);
+
+impl CachedRevmBal {
+    /// Creates a cached revm BAL from an owned decoded BAL.
+    #[inline]
+    fn new(bal: DecodedBal<Arc<RevmBal>>) -> Self {
+        Self(Arc::new(bal))
+    }
+}
+
+impl InMemorySize for CachedRevmBal {
+    fn size(&self) -> usize {
+        core::mem::size_of::<Self>() + decoded_revm_bal_size(&self.0)
+    }
+}
+
+/// Cached recovered senders of a block.
+#[derive(Clone, Debug)]
+pub(crate) struct CachedSenders(Arc<Vec<Address>>);
+
+impl CachedSenders {
+    /// Creates cached senders from the recovered senders of a block.
+    #[inline]
+    fn new(senders: Vec<Address>) -> Self {
+        Self(Arc::new(senders))
+    }
+}
+
+impl InMemorySize for CachedSenders {
+    fn size(&self) -> usize {
+        core::mem::size_of::<Self>() +
+            core::mem::size_of::<Vec<Address>>() +
+            self.0.capacity() * core::mem::size_of::<Address>()
+    }
+}
+
+/// Reads the senders of the block with the given hash without loading the block body.
+///
+/// Falls back to recovering the senders from the block if they have been pruned.
+fn senders_by_block_hash<Provider: BlockReader>(
+    provider: &Provider,
+    block_hash: B256,
+) -> ProviderResult<Option<Vec<Address>>> {
+    let Some(number) = provider.block_number(block_hash)? else { return Ok(None) };
+    let Some(indices) = provider.block_body_indices(number)? else { return Ok(None) };
+
+    let senders = provider.senders_by_tx_range(indices.tx_num_range())?;
+    if senders.len() as u64 == indices.tx_count() {
+        return Ok(Some(senders))
+    }
+
+    Ok(provider
+        .sealed_block_with_senders(block_hash.into(), TransactionVariant::NoHash)?
+        .map(|block| block.senders().to_vec()))
+}
+
+fn decoded_revm_bal_size(bal: &DecodedBal<Arc<RevmBal>>) -> usize {
+    core::mem::size_of::<DecodedBal<Arc<RevmBal>>>() +
+        bal.as_raw().len() +
+        revm_bal_size(bal.as_bal())
+}
+
+fn revm_bal_size(bal: &Arc<RevmBal>) -> usize {
+    core::mem::size_of::<RevmBal>() +
+        bal.accounts.capacity() * core::mem::size_of::<(Address, RevmAccountBal)>() +
+        bal.accounts.values().map(revm_account_bal_heap_size).sum::<usize>()
+}
+
+fn revm_account_bal_heap_size(account: &RevmAccountBal) -> usize {
+    revm_account_info_bal_heap_size(&account.account_info) +
+        revm_storage_bal_heap_size(&account.storage)
+}
+
+fn revm_account_info_bal_heap_size(account_info: &RevmAccountInfoBal) -> usize {
+    revm_bal_writes_heap_size(&account_info.nonce, |_| 0) +
+        revm_bal_writes_heap_size(&account_info.balance, |_| 0) +
+        revm_bal_writes_heap_size(&account_info.code, revm_code_write_heap_size)
+}
+
+fn revm_storage_bal_heap_size(storage: &RevmStorageBal) -> usize {
+    storage.storage.len() * core::mem::size_of::<(StorageKey, RevmBalWrites<StorageValue>)>() +
+        storage
+            .storage
+            .values()
+            .map(|writes| revm_bal_writes_heap_size(writes, |_| 0))
+            .sum::<usize>()
+}
+
+fn revm_bal_writes_heap_size<T, F>(writes: &RevmBalWrites<T>, mut item_heap_size: F) -> usize
+where
+    T: PartialEq + Clone,
+    F: FnMut(&T) -> usize,
+{
+    writes.writes.capacity() * core::mem::size_of::<(u64, T)>() +
+        writes.writes.iter().map(|(_, item)| item_heap_size(item)).sum::<usize>()
+}
+
+fn revm_code_write_heap_size((_, bytecode): &(B256, Bytecode)) -> usize {
+    bytecode.bytes_ref().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::config::EvictionPolicy;
+    use alloy_consensus::{transaction::TransactionMeta, Header};
+    use alloy_eip7928::BlockAccessIndex;
+    use alloy_eips::{BlockHashOrNumber, NumHash};
+    use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes, Signature, TxHash, TxNumber};
+    use core::ops::{RangeBounds, RangeInclusive};
+    use futures::FutureExt;
+    use reth_db_models::StoredBlockBodyIndices;
+    use reth_ethereum_primitives::{
+        Block, BlockBody, EthPrimitives, Receipt, Transaction, TransactionSigned,
+    };
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives_traits::{RecoveredBlock, SealedHeader};
+    use reth_storage_api::{
+        noop::NoopProvider, BalProvider, BalStore, BalStoreHandle, BlockBodyIndicesProvider,
+        BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider, ReceiptProvider,
+        TransactionVariant, TransactionsProvider,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn test_service() -> EthStateCacheService<NoopProvider, Runtime> {
+        let (_cache, service) = EthStateCache::<EthPrimitives>::create(
+            NoopProvider::default(),
+            Runtime::test(),
+            EthStateCacheConfig {
+                max_blocks: 4,
+                max_receipts: 4,
+                max_headers: 4,
+                max_bals: 4,
+                max_concurrent_db_requests: 1,
+                max_cached_tx_hashes: 16,
+                max_number_index_entries: None,
+                max_queued_consumers: 2,
+                fetch_jitter: None,
+                panic_policy: Default::default(),
+                total_max_bytes: None,
+                eviction_policy: Default::default(),
+                new_blocks_debounce: None,
+                skip_duplicate_inserts: false,
+                max_cold_blocks: 0,
+                header_persist_path: None,
+            },
+        );
+        service
+    }
+
+    #[test]
+    fn block_is_fetching_while_consumers_are_queued() {
+        let mut service = test_service();
+        let block_hash = B256::repeat_byte(0x01);
+        assert!(!service.full_block_cache.is_queued(&block_hash));
+
+        let (response_tx, _rx) = oneshot::channel();
+        service.full_block_cache.queue(block_hash, response_tx);
+        assert!(service.full_block_cache.is_queued(&block_hash));
+
+        service.full_block_cache.remove(&block_hash);
+        assert!(!service.full_block_cache.is_queued(&block_hash));
+    }
+
+    #[tokio::test]
+    async fn is_fetching_reports_in_flight_block_fetches() {
+        let (cache, mut service) = EthStateCache::<EthPrimitives>::create(
+            NoopProvider::default(),
+            Runtime::test(),
+            EthStateCacheConfig::default(),
+        );
+        let block_hash = B256::repeat_byte(0x01);
+        let (response_tx, _rx) = oneshot::channel();
+        service.full_block_cache.queue(block_hash, response_tx);
+        let service = tokio::spawn(service);
+
+        assert!(cache.is_fetching(block_hash).await);
+        assert!(!cache.is_fetching(B256::repeat_byte(0x02)).await);
+
+        // reported as not fetching once the service is gone
+        service.abort();
+        let _ = service.await;
+        assert!(!cache.is_fetching(block_hash).await);
+    }
+
+    #[test]
+    fn preload_queues_block_and_receipts_fetches() {
+        let (cache, mut service) = EthStateCache::<EthPrimitives>::create(
+            NoopProvider::default(),
+            Runtime::test(),
+            EthStateCacheConfig::default(),
+        );
+        let hashes = [B256::repeat_byte(0x01), B256::repeat_byte(0x02)];
+        cache.preload(hashes);
+
+        for hash in hashes {
+            let action = service.action_rx.next().now_or_never().flatten().unwrap();
+            assert!(matches!(
+                action,
+                CacheAction::GetBlockWithSenders { block_hash, promote: true, .. } if block_hash == hash
+            ));
+            let action = service.action_rx.next().now_or_never().flatten().unwrap();
+            assert!(matches!(
+                action,
+                CacheAction::GetReceipts { block_hash, promote: true, .. } if block_hash == hash
+            ));
+        }
+        assert!(service.action_rx.next().now_or_never().is_none());
+    }
+
+    #[tokio::test]
+    async fn unordered_stream_yields_available_blocks_first() {
+        let (cache, mut service) = EthStateCache::<EthPrimitives>::create(
+            NoopProvider::default(),
+            Runtime::test(),
+            EthStateCacheConfig::default(),
+        );
+        // the receipts of this block are being fetched and never arrive
+        let pending = B256::repeat_byte(0x42);
+        let (response_tx, _rx) = oneshot::channel();
+        service.receipts_cache.queue(pending, response_tx);
+        tokio::spawn(service);
+
+        let block = Arc::new(test_block());
+        let block_hash = block.hash();
+        let receipts = Arc::new(vec![Receipt::default()]);
+        let _ = cache.to_service.send(CacheAction::CacheNewCanonicalChain {
+            chain_change: ChainChange {
+                blocks: vec![block],
+                receipts: vec![BlockReceipts { block_hash, receipts: receipts.clone() }],
+            },
+        });
+
+        let mut stream = std::pin::pin!(
+            cache.get_receipts_and_maybe_block_stream_unordered(vec![pending, block_hash])
+        );
+        let (hash, res) = stream.next().await.unwrap();
+        assert_eq!(hash, block_hash);
+        let (cached_receipts, cached_block) = res.unwrap().unwrap();
+        assert_eq!(cached_receipts, receipts);
+        assert_eq!(cached_block.unwrap().hash(), block_hash);
+
+        assert!(stream.next().now_or_never().is_none());
+    }
+
+    fn test_decoded_revm_bal() -> DecodedBal<Arc<RevmBal>> {
+        DecodedBal::new(Arc::new(RevmBal::default()), Bytes::from_static(&[0xc0]))
+    }
+
+    fn test_block() -> RecoveredBlock<Block> {
+        RecoveredBlock::new_unhashed(
+            Block {
+                header: Header { number: 1, ..Default::default() },
+                body: BlockBody {
+                    transactions: vec![TransactionSigned::new_unhashed(
+                        Transaction::Legacy(Default::default()),
+                        Signature::test_signature(),
+                    )],
+                    ..Default::default()
+                },
+            },
+            vec![Address::ZERO],
+        )
+    }
+
+    #[test]
+    fn low_priority_blocks_dont_evict_main_cache() {
+        let (_cache, mut service) = EthStateCache::<EthPrimitives>::create(
+            NoopProvider::default(),
+            Runtime::test(),
+            EthStateCacheConfig {
+                max_blocks: 1,
+                max_receipts: 1,
+                max_cold_blocks: 1,
+                ..Default::default()
+            },
+        );
+        let block_with_number = |number| {
+            Arc::new(RecoveredBlock::new_unhashed(
+                Block { header: Header { number, ..Default::default() }, body: Default::default() },
+                vec![],
+            ))
+        };
+        let live = Arc::new(test_block());
+        service.on_new_block(live.hash(), Ok(Some(live.clone())));
+
+        let first = block_with_number(2);
+        service.insert_low_priority(first.clone(), Arc::new(vec![]));
+        assert!(service.full_block_cache.peek(&live.hash()).is_some());
+        assert!(service.cached_recovered_block(first.hash(), true).is_some());
+        assert!(service.cached_receipts(first.hash(), true).is_some());
+
+        // the low priority tier evicts its own entries only
+        let second = block_with_number(3);
+        service.insert_low_priority(second.clone(), Arc::new(vec![]));
+        assert!(service.cached_recovered_block(first.hash(), true).is_none());
+        assert!(service.cached_recovered_block(second.hash(), true).is_some());
+        assert!(service.full_block_cache.peek(&live.hash()).is_some());
+
+        // blocks in the main cache are not duplicated
+        service.insert_low_priority(live.clone(), Arc::new(vec![]));
+        assert!(service.cold_blocks.peek(&live.hash()).is_none());
+        assert!(service.cold_blocks.peek(&second.hash()).is_some());
+
+        // a block cached in the main cache leaves the low priority tier
+        service.on_new_block(second.hash(), Ok(Some(second.clone())));
+        assert!(service.cold_blocks.peek(&second.hash()).is_none());
+        assert!(service.full_block_cache.peek(&second.hash()).is_some());
+    }
+
+    /// Spawns a cache with [`test_block`] in the low priority tier.
+    fn cache_with_low_priority_block() -> (EthStateCache<EthPrimitives>, Arc<RecoveredBlock<Block>>)
+    {
+        let cache = EthStateCache::<EthPrimitives>::spawn_with(
+            NoopProvider::default(),
+            EthStateCacheConfig::default(),
+            Runtime::test(),
+        );
+        let block = Arc::new(test_block());
+        cache.insert_low_priority(block.clone(), Arc::new(vec![Receipt::default()]));
+        (cache, block)
+    }
+
+    #[tokio::test]
+    async fn header_lookup_falls_back_to_low_priority_blocks() {
+        let (cache, block) = cache_with_low_priority_block();
+
+        // the provider doesn't have the header, so it can only come from the cached block
+        let header = cache.get_header(block.hash()).await.unwrap();
+        assert_eq!(header, *block.header());
+    }
+
+    #[tokio::test]
+    async fn parent_blocks_lookup_continues_into_low_priority_blocks() {
+        let (cache, parent) = cache_with_low_priority_block();
+        let child = Arc::new(RecoveredBlock::new_unhashed(
+            Block {
+                header: Header { number: 2, parent_hash: parent.hash(), ..Default::default() },
+                body: Default::default(),
+            },
+            vec![],
+        ));
+        let _ = cache.to_service.send(CacheAction::CacheNewCanonicalChain {
+            chain_change: ChainChange { blocks: vec![child.clone()], receipts: vec![] },
+        });
+
+        let blocks = cache.get_cached_parent_blocks(child.hash(), 4).await.unwrap();
+        assert_eq!(
+            blocks.iter().map(|block| block.hash()).collect::<Vec<_>>(),
+            vec![child.hash(), parent.hash()]
+        );
+    }
+
+    #[tokio::test]
+    async fn transaction_lookup_by_hash_finds_low_priority_blocks() {
+        let (cache, block) = cache_with_low_priority_block();
+        let tx_hash = *block.body().transactions[0].tx_hash();
+
+        let cached = cache.get_transaction_by_hash(tx_hash).await.unwrap();
+        assert_eq!(cached.block.hash(), block.hash());
+        assert_eq!(cached.tx_index, 0);
+        assert_eq!(cached.receipts.unwrap().len(), 1);
+        assert_eq!(cache.get_receipt(tx_hash).await.unwrap(), Some(Receipt::default()));
+    }
+
+    #[tokio::test]
+    async fn wait_for_block_resolves_with_low_priority_block() {
+        let (cache, block) = cache_with_low_priority_block();
+
+        let waited = cache
+            .wait_for_block_with_timeout(block.hash(), Duration::from_secs(5))
+            .await
+            .unwrap()
+            .expect("cached block resolves the wait right away");
+        assert!(Arc::ptr_eq(&waited, &block));
+    }
+
+    #[test]
+    fn reorg_evicts_cached_headers() {
+        let mut service = test_service();
+        let block_hash = B256::repeat_byte(0x11);
+
+        assert!(service
+            .headers_cache
+            .insert(block_hash, Header { number: 42, ..Default::default() }));
+        assert!(service.headers_cache.get(&block_hash).is_some());
+
+        service.on_reorg_header(block_hash, Ok(Header { number: 7, ..Default::default() }));
+
+        assert!(service.headers_cache.get(&block_hash).is_none());
+    }
+
+    #[test]
+    fn reorg_forwards_header_to_queued_requests() {
+        let mut service = test_service();
+        let block_hash = B256::repeat_byte(0x22);
+        let (response_tx, mut response_rx) = oneshot::channel();
+        let header = Header { number: 7, ..Default::default() };
+
+        assert!(service.headers_cache.queue(block_hash, response_tx));
+
+        service.on_reorg_header(block_hash, Ok(header));
+
+        let header =
+            response_rx.try_recv().expect("queued header response").expect("header result");
+
+        assert_eq!(header.number, 7);
+    }
+
+    #[test]
+    fn reorg_removes_tx_hash_index_entries_unconditionally() {
+        let mut service = test_service();
+        let block = test_block();
+        let tx_hash = *block.body().transactions().next().expect("test transaction").tx_hash();
+
+        service.tx_hash_index.insert(tx_hash, (B256::repeat_byte(0x33), 0));
+
+        service.remove_block_transactions(&block);
+
+        assert!(service.tx_hash_index.get(&tx_hash).is_none());
+    }
+
+    #[test]
+    fn receipt_by_tx_hash_resolves_through_tx_index() {
+        let mut service = test_service();
+        let block = test_block();
+        let tx_hash = *block.body().transactions().next().expect("test transaction").tx_hash();
+        let receipt = Receipt { cumulative_gas_used: 21_000, ..Default::default() };
+
+        service.index_block_transactions(&block);
+        // the receipts of the block aren't cached yet
+        assert!(service.cached_receipt_by_tx_hash(tx_hash).is_none());
+
+        assert!(service.receipts_cache.insert(block.hash(), Arc::new(vec![receipt.clone()])));
+        assert_eq!(service.cached_receipt_by_tx_hash(tx_hash), Some(receipt));
+
+        service.remove_block_transactions(&block);
+        assert!(service.cached_receipt_by_tx_hash(tx_hash).is_none());
+    }
+
+    #[test]
+    fn closed_block_waiters_are_pruned_when_idle() {
+        let mut service = test_service();
+        let block_hash = B256::random();
+        let other_hash = B256::random();
+        let (timed_out_tx, timed_out_rx) = oneshot::channel();
+        let (waiter_tx, _waiter_rx) = oneshot::channel();
+
+        service.wait_for_block(block_hash, timed_out_tx);
+        service.wait_for_block(other_hash, waiter_tx);
+        drop(timed_out_rx);
+
+        service.shrink_queues();
+        assert!(!service.block_waiters.contains_key(&block_hash));
+        assert_eq!(service.block_waiters[&other_hash].len(), 1);
+    }
+
+    #[test]
+    fn block_waiters_complete_on_commit() {
+        let mut service = test_service();
+        let block = Arc::new(test_block());
+        let block_hash = block.hash();
+        let (waiter_tx, mut waiter_rx) = oneshot::channel();
+        let (timed_out_tx, timed_out_rx) = oneshot::channel();
+
+        service.wait_for_block(block_hash, timed_out_tx);
+        drop(timed_out_rx);
+        service.wait_for_block(block_hash, waiter_tx);
+        assert_eq!(service.block_waiters[&block_hash].len(), 1);
+        assert!(waiter_rx.try_recv().is_err());
+
+        service.notify_block_waiters(&block);
+        assert_eq!(waiter_rx.try_recv().unwrap().hash(), block_hash);
+        assert!(service.block_waiters.is_empty());
+
+        // an already cached block resolves right away
+        service.on_new_block(block_hash, Ok(Some(block)));
+        let (waiter_tx, mut waiter_rx) = oneshot::channel();
+        service.wait_for_block(block_hash, waiter_tx);
+        assert_eq!(waiter_rx.try_recv().unwrap().hash(), block_hash);
+    }
+
+    #[test]
+    fn header_by_number_resolves_through_canonical_index() {
+        let mut service = test_service();
+        let block = test_block();
+        let header = block.clone_header();
+
+        assert!(service.cached_header_by_number(header.number).is_none());
+
+        service.index_canonical_block(&block);
+        assert!(service.headers_cache.insert(block.hash(), header.clone()));
+        assert_eq!(service.cached_header_by_number(header.number), Some(header.clone()));
+
+        service.remove_canonical_block(&block);
+        assert!(service.cached_header_by_number(header.number).is_none());
+    }
+
+    #[test]
+    fn number_index_respects_configured_capacity() {
+        let (_cache, mut service) = EthStateCache::<EthPrimitives>::create(
+            NoopProvider::default(),
+            Runtime::test(),
+            EthStateCacheConfig { max_number_index_entries: Some(1), ..Default::default() },
+        );
+
+        for number in 1..=3 {
+            service.canonical_hashes.insert(number, B256::with_last_byte(number as u8));
+        }
+        assert_eq!(service.canonical_hashes.len(), 1);
+        assert_eq!(service.canonical_hashes.get(&3), Some(&B256::with_last_byte(3)));
+    }
+
+    #[test]
+    fn reorg_keeps_reassigned_canonical_index_entries() {
+        let mut service = test_service();
+        let block = test_block();
+        let new_hash = B256::repeat_byte(0x34);
+
+        service.index_canonical_block(&block);
+        service.canonical_hashes.insert(block.header().number(), new_hash);
+
+        service.remove_canonical_block(&block);
+
+        assert_eq!(service.canonical_hashes.get(&block.header().number()), Some(&new_hash));
+    }
+
+    #[test]
+    fn reorg_rewinds_latest_to_fork_point() {
+        let mut service = test_service();
+        let block = Arc::new(test_block());
+
+        service.latest = Some((block.hash(), block.header().number()));
+        service.rewind_latest(&[]);
+        assert_eq!(service.latest, Some((block.hash(), block.header().number())));
+
+        service.rewind_latest(core::slice::from_ref(&block));
+        assert_eq!(service.latest, Some((block.header().parent_hash(), 0)));
+    }
+
+    #[test]
+    fn transactions_are_served_from_cached_blocks() {
+        let mut service = test_service();
+        let block = Arc::new(test_block());
+        let block_hash = block.hash();
+
+        assert!(service.cached_transactions(block_hash).is_none());
+
+        service.on_new_block(block_hash, Ok(Some(block.clone())));
+        assert_eq!(
+            service.cached_transactions(block_hash).as_deref(),
+            Some(&block.body().transactions)
+        );
+        // served from the block, not duplicated in the transactions cache
+        assert!(service.transactions_cache.get(&block_hash).is_none());
+
+        let other_hash = B256::repeat_byte(0x39);
+        service.on_new_transactions(other_hash, Ok(Some(Arc::new(Vec::new()))));
+        assert_eq!(service.cached_transactions(other_hash).as_deref(), Some(&Vec::new()));
+    }
+
+    #[cfg(feature = "cache-snapshot")]
+    #[test]
+    fn snapshot_lists_cached_hashes() {
+        let mut service = test_service();
+        let block = Arc::new(test_block());
+        let block_hash = block.hash();
+
+        assert_eq!(service.snapshot(), CacheSnapshot::default());
 
-        let _ =
-            eth_state_cache.to_service.send(CacheAction::CacheNewCanonicalChain { chain_change });
+        service.on_new_block(block_hash, Ok(Some(block)));
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot.blocks.hashes, vec![block_hash]);
+        assert!(snapshot.blocks.memory_usage > 0);
+        assert!(snapshot.receipts.is_empty());
+        assert!(snapshot.headers.is_empty());
     }
-}
 
-/// Cached decoded revm BAL.
-#[derive(Clone, Debug)]
-pub(crate) struct CachedRevmBal(Arc<DecodedBal<Arc<RevmBal>>>);
+    #[test]
+    fn peeked_receipts_are_evicted_first() {
+        let mut service = test_service();
+        let hashes = (0..5u8).map(B256::repeat_byte).collect::<Vec<_>>();
 
-impl CachedRevmBal {
-    /// Creates a cached revm BAL from an owned decoded BAL.
-    #[inline]
-    fn new(bal: DecodedBal<Arc<RevmBal>>) -> Self {
-        Self(Arc::new(bal))
+        for hash in &hashes[..4] {
+            assert!(service.receipts_cache.insert(*hash, Arc::new(Vec::new())));
+        }
+        // a scan doesn't protect the oldest entry from eviction
+        assert!(service.receipts_cache.peek(&hashes[0]).is_some());
+        assert!(service.receipts_cache.get(&hashes[1]).is_some());
+        assert!(service.receipts_cache.insert(hashes[4], Arc::new(Vec::new())));
+
+        assert!(service.receipts_cache.peek(&hashes[0]).is_none());
+        assert!(service.receipts_cache.peek(&hashes[1]).is_some());
     }
-}
 
-impl InMemorySize for CachedRevmBal {
-    fn size(&self) -> usize {
-        core::mem::size_of::<Self>() + decoded_revm_bal_size(&self.0)
+    #[test]
+    fn total_max_bytes_evicts_from_largest_cache() {
+        let mut service = test_service();
+        let header_size = Header::default().size();
+        service.total_max_bytes = Some(3 * header_size);
+        let hashes = (0..4u8).map(B256::repeat_byte).collect::<Vec<_>>();
+
+        for hash in &hashes[..3] {
+            assert!(service.headers_cache.insert(*hash, Header::default()));
+            service.enforce_total_max_bytes();
+        }
+        assert_eq!(service.headers_cache.memory_usage(), 3 * header_size);
+
+        // the headers are the largest cache, so they give up the space for the receipts
+        service.on_new_receipts(hashes[3], Ok(Some(Arc::new(vec![Receipt::default()]))));
+        assert!(service.receipts_cache.peek(&hashes[3]).is_some());
+        assert!(service.headers_cache.peek(&hashes[0]).is_none());
+        assert!(service.headers_cache.peek(&hashes[1]).is_some());
+        assert!(
+            service.full_block_cache.memory_usage() +
+                service.receipts_cache.memory_usage() +
+                service.headers_cache.memory_usage() <=
+                3 * header_size
+        );
     }
-}
 
-fn decoded_revm_bal_size(bal: &DecodedBal<Arc<RevmBal>>) -> usize {
-    core::mem::size_of::<DecodedBal<Arc<RevmBal>>>() +
-        bal.as_raw().len() +
-        revm_bal_size(bal.as_bal())
-}
+    #[test]
+    fn skip_duplicate_inserts_keeps_cached_entry() {
+        let insert_twice = |skip_duplicate_inserts| {
+            let mut service = test_service();
+            service.skip_duplicate_inserts = skip_duplicate_inserts;
+            let (first, second) = (B256::repeat_byte(0x01), B256::repeat_byte(0x02));
+            service.on_new_receipts(first, Ok(Some(Arc::new(vec![Receipt::default()]))));
+            service.on_new_receipts(second, Ok(Some(Arc::new(Vec::new()))));
+
+            // a duplicate notification still answers the waiting requests
+            let (response_tx, mut response_rx) = oneshot::channel();
+            service.receipts_cache.queue(first, response_tx);
+            service.on_new_receipts(first, Ok(Some(Arc::new(vec![Receipt::default()]))));
+            assert!(matches!(response_rx.try_recv(), Ok(Ok(Some(_)))));
+
+            service.receipts_cache.pop_oldest().map(|(hash, _)| hash == first)
+        };
 
-fn revm_bal_size(bal: &Arc<RevmBal>) -> usize {
-    core::mem::size_of::<RevmBal>() +
-        bal.accounts.capacity() * core::mem::size_of::<(Address, RevmAccountBal)>() +
-        bal.accounts.values().map(revm_account_bal_heap_size).sum::<usize>()
-}
+        // the duplicate is not promoted, so it is still the least recently used entry
+        assert_eq!(insert_twice(true), Some(true));
+        assert_eq!(insert_twice(false), Some(false));
+    }
 
-fn revm_account_bal_heap_size(account: &RevmAccountBal) -> usize {
-    revm_account_info_bal_heap_size(&account.account_info) +
-        revm_storage_bal_heap_size(&account.storage)
-}
+    #[test]
+    fn lfu_keeps_polled_entries_during_scans() {
+        // a historical block that is polled repeatedly while scans pass through the cache
+        let hit_rate = |eviction_policy| {
+            let mut cache = ReceiptsLruCache::<Receipt, ByLength>::new(4, "receipts")
+                .with_eviction_policy(eviction_policy);
+            let polled = B256::repeat_byte(0xff);
+            let mut hits = 0;
+            for scan in 0..10u8 {
+                for _ in 0..3 {
+                    if cache.get(&polled).is_some() {
+                        hits += 1;
+                    } else {
+                        cache.insert(polled, Arc::new(Vec::new()));
+                    }
+                }
+                for block in 0..6u8 {
+                    let hash = B256::with_last_byte(scan * 6 + block);
+                    if cache.get(&hash).is_none() {
+                        cache.insert(hash, Arc::new(Vec::new()));
+                    }
+                }
+            }
+            hits
+        };
 
-fn revm_account_info_bal_heap_size(account_info: &RevmAccountInfoBal) -> usize {
-    revm_bal_writes_heap_size(&account_info.nonce, |_| 0) +
-        revm_bal_writes_heap_size(&account_info.balance, |_| 0) +
-        revm_bal_writes_heap_size(&account_info.code, revm_code_write_heap_size)
-}
+        let lru_hits = hit_rate(EvictionPolicy::Lru);
+        let lfu_hits = hit_rate(EvictionPolicy::Lfu);
+        assert!(lfu_hits > lru_hits, "lfu {lfu_hits} <= lru {lru_hits}");
+    }
 
-fn revm_storage_bal_heap_size(storage: &RevmStorageBal) -> usize {
-    storage.storage.len() * core::mem::size_of::<(StorageKey, RevmBalWrites<StorageValue>)>() +
-        storage
-            .storage
-            .values()
-            .map(|writes| revm_bal_writes_heap_size(writes, |_| 0))
-            .sum::<usize>()
-}
+    #[tokio::test]
+    async fn rate_limiter_warnings_are_throttled() {
+        let limiter = FetchRateLimiter::new(1);
 
-fn revm_bal_writes_heap_size<T, F>(writes: &RevmBalWrites<T>, mut item_heap_size: F) -> usize
-where
-    T: PartialEq + Clone,
-    F: FnMut(&T) -> usize,
-{
-    writes.writes.capacity() * core::mem::size_of::<(u64, T)>() +
-        writes.writes.iter().map(|(_, item)| item_heap_size(item)).sum::<usize>()
-}
+        let permit = limiter.acquire().await.unwrap();
+        assert!(limiter.semaphore.try_acquire().is_err());
+        drop(permit);
 
-fn revm_code_write_heap_size((_, bytecode): &(B256, Bytecode)) -> usize {
-    bytecode.bytes_ref().len()
-}
+        assert!(limiter.should_warn());
+        assert!(!limiter.should_warn());
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use alloy_consensus::{transaction::TransactionMeta, Header};
-    use alloy_eip7928::BlockAccessIndex;
-    use alloy_eips::{BlockHashOrNumber, NumHash};
-    use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes, Signature, TxHash, TxNumber};
-    use core::ops::{RangeBounds, RangeInclusive};
-    use reth_db_models::StoredBlockBodyIndices;
-    use reth_ethereum_primitives::{
-        Block, BlockBody, EthPrimitives, Receipt, Transaction, TransactionSigned,
-    };
-    use reth_primitives_traits::{RecoveredBlock, SealedHeader};
-    use reth_storage_api::{
-        noop::NoopProvider, BalProvider, BalStore, BalStoreHandle, BlockBodyIndicesProvider,
-        BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider, ReceiptProvider,
-        TransactionVariant, TransactionsProvider,
-    };
-    use std::sync::atomic::{AtomicUsize, Ordering};
+    #[tokio::test]
+    async fn fetch_jitter_is_bounded() {
+        // disabled jitter doesn't delay fetches at all
+        assert!(fetch_jitter(None).now_or_never().is_some());
+        assert!(fetch_jitter(Some(Duration::ZERO)).now_or_never().is_some());
 
-    fn test_service() -> EthStateCacheService<NoopProvider, Runtime> {
-        let (_cache, service) = EthStateCache::<EthPrimitives>::create(
+        let max = Duration::from_millis(20);
+        assert!(tokio::time::timeout(max * 50, fetch_jitter(Some(max))).await.is_ok());
+
+        // fetches still complete after the jitter
+        let cache = EthStateCache::<EthPrimitives>::spawn_with(
             NoopProvider::default(),
+            EthStateCacheConfig { fetch_jitter: Some(max), ..Default::default() },
             Runtime::test(),
-            EthStateCacheConfig {
-                max_blocks: 4,
-                max_receipts: 4,
-                max_headers: 4,
-                max_bals: 4,
-                max_concurrent_db_requests: 1,
-                max_cached_tx_hashes: 16,
-            },
         );
-        service
+        assert!(cache.get_receipts(B256::repeat_byte(0x42)).await.unwrap().is_none());
     }
 
-    fn test_decoded_revm_bal() -> DecodedBal<Arc<RevmBal>> {
-        DecodedBal::new(Arc::new(RevmBal::default()), Bytes::from_static(&[0xc0]))
+    #[test]
+    fn dropped_action_sender_requests_retry_once() {
+        let block_hash = B256::repeat_byte(0x38);
+        let (tx, mut rx) = unbounded_channel::<CacheAction<Block, Receipt>>();
+
+        drop(
+            ActionSender::new(CacheKind::Header, block_hash, tx.clone()).with_retry_on_panic(true),
+        );
+        let Ok(CacheAction::RetryFetch { kind, block_hash: hash }) = rx.try_recv() else {
+            panic!("expected a retry request")
+        };
+        assert!(matches!(kind, CacheKind::Header));
+        assert_eq!(hash, block_hash);
+
+        drop(ActionSender::new(CacheKind::Header, block_hash, tx));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(CacheAction::HeaderResult { res, .. }) if res.is_err()
+        ));
     }
 
-    fn test_block() -> RecoveredBlock<Block> {
-        RecoveredBlock::new_unhashed(
-            Block {
-                header: Header { number: 1, ..Default::default() },
-                body: BlockBody {
-                    transactions: vec![TransactionSigned::new_unhashed(
-                        Transaction::Legacy(Default::default()),
-                        Signature::test_signature(),
-                    )],
-                    ..Default::default()
-                },
-            },
-            vec![Address::ZERO],
-        )
+    #[test]
+    fn header_queue_is_bounded_per_key() {
+        let mut service = test_service();
+        let block_hash = B256::repeat_byte(0x35);
+
+        for _ in 0..2 {
+            assert!(!service.headers_cache.is_queue_full(&block_hash));
+            service.headers_cache.queue(block_hash, oneshot::channel().0);
+        }
+
+        assert!(service.headers_cache.is_queue_full(&block_hash));
+        assert!(!service.headers_cache.is_queue_full(&B256::repeat_byte(0x36)));
+
+        service.on_reorg_header(block_hash, Ok(Header::default()));
+        assert!(!service.headers_cache.is_queue_full(&block_hash));
     }
 
     #[test]
-    fn reorg_evicts_cached_headers() {
+    fn clear_only_removes_selected_kinds() {
         let mut service = test_service();
-        let block_hash = B256::repeat_byte(0x11);
+        let block_hash = B256::repeat_byte(0x37);
+        let (response_tx, mut response_rx) = oneshot::channel();
 
-        assert!(service
-            .headers_cache
-            .insert(block_hash, Header { number: 42, ..Default::default() }));
-        assert!(service.headers_cache.get(&block_hash).is_some());
+        assert!(service.headers_cache.insert(block_hash, Header::default()));
+        assert!(service.bal_cache.insert(block_hash, CachedRevmBal::new(test_decoded_revm_bal())));
+        assert!(service.senders_cache.queue(block_hash, response_tx));
 
-        service.on_reorg_header(block_hash, Ok(Header { number: 7, ..Default::default() }));
+        service.clear(CacheKinds::HEADERS | CacheKinds::SENDERS);
 
         assert!(service.headers_cache.get(&block_hash).is_none());
+        assert!(service.bal_cache.get(&block_hash).is_some());
+
+        // queued consumers are still answered by the in-flight fetch
+        service.on_new_senders(block_hash, Ok(None));
+        assert!(response_rx.try_recv().expect("queued senders response").is_ok());
     }
 
     #[test]
-    fn reorg_forwards_header_to_queued_requests() {
+    fn block_bloom_is_computed_from_cached_receipts() {
         let mut service = test_service();
-        let block_hash = B256::repeat_byte(0x22);
-        let (response_tx, mut response_rx) = oneshot::channel();
-        let header = Header { number: 7, ..Default::default() };
+        let block_hash = B256::repeat_byte(0x38);
+        let log = alloy_primitives::Log::new_unchecked(
+            Address::repeat_byte(0x01),
+            vec![B256::repeat_byte(0x02)],
+            Bytes::new(),
+        );
+        let receipt = Receipt { logs: vec![log.clone()], ..Default::default() };
 
-        assert!(service.headers_cache.queue(block_hash, response_tx));
+        assert!(service.cached_block_bloom(block_hash).is_none());
 
-        service.on_reorg_header(block_hash, Ok(header));
+        assert!(service.receipts_cache.insert(block_hash, Arc::new(vec![receipt])));
+        let bloom = service.cached_block_bloom(block_hash).expect("bloom from receipts");
+        assert!(bloom.contains_input(alloy_primitives::BloomInput::Raw(log.address.as_slice())));
 
-        let header =
-            response_rx.try_recv().expect("queued header response").expect("header result");
+        // memoized independently of the receipts
+        service.clear(CacheKinds::HEADERS);
+        assert_eq!(service.bloom_cache.get(&block_hash), Some(&bloom));
+    }
 
-        assert_eq!(header.number, 7);
+    #[tokio::test]
+    async fn debounced_new_blocks_skip_reverted_blocks() {
+        let (to_service, mut actions) = unbounded_channel();
+        let cache = EthStateCache::<EthPrimitives> { to_service };
+        let (events_tx, events_rx) = unbounded_channel();
+        let task = tokio::spawn(cache_new_blocks_task_with_debounce(
+            cache,
+            UnboundedReceiverStream::new(events_rx),
+            Duration::from_secs(3600),
+        ));
+
+        let chain = |block: RecoveredBlock<Block>| {
+            let first_block = block.header().number();
+            Arc::new(Chain::<EthPrimitives>::new(
+                [block],
+                ExecutionOutcome { receipts: vec![vec![]], first_block, ..Default::default() },
+                Default::default(),
+            ))
+        };
+        let reverted = test_block();
+        let mut canonical = test_block().into_block();
+        canonical.header.gas_limit = 1;
+        let canonical = RecoveredBlock::new_unhashed(canonical, vec![Address::ZERO]);
+        let (reverted_hash, canonical_hash) = (reverted.hash(), canonical.hash());
+
+        events_tx.send(CanonStateNotification::Commit { new: chain(reverted.clone()) }).unwrap();
+        events_tx
+            .send(CanonStateNotification::Reorg { old: chain(reverted), new: chain(canonical) })
+            .unwrap();
+
+        // the reorg is applied immediately
+        let Some(CacheAction::RemoveReorgedChain { chain_change }) = actions.recv().await else {
+            panic!("expected reorged chain")
+        };
+        assert_eq!(chain_change.blocks[0].hash(), reverted_hash);
+
+        // ending the stream flushes the held back blocks that are still canonical
+        drop(events_tx);
+        task.await.unwrap();
+        let Some(CacheAction::CacheNewCanonicalChain { chain_change }) = actions.recv().await
+        else {
+            panic!("expected new canonical chain")
+        };
+        assert_eq!(chain_change.blocks.len(), 1);
+        assert_eq!(chain_change.blocks[0].hash(), canonical_hash);
+        assert!(actions.recv().await.is_none());
     }
 
     #[test]
-    fn reorg_removes_tx_hash_index_entries_unconditionally() {
+    fn cached_parent_blocks_in_both_orders() {
         let mut service = test_service();
-        let block = test_block();
-        let tx_hash = *block.body().transactions().next().expect("test transaction").tx_hash();
+        let parent = test_block();
+        let mut child = test_block().into_block();
+        child.header.number = 2;
+        child.header.parent_hash = parent.hash();
+        let child = RecoveredBlock::new_unhashed(child, vec![Address::ZERO]);
+        let (parent_hash, child_hash) = (parent.hash(), child.hash());
+        assert!(service.full_block_cache.insert(parent_hash, Arc::new(parent)));
+        assert!(service.full_block_cache.insert(child_hash, Arc::new(child)));
+
+        let hashes = |blocks: Vec<Arc<RecoveredBlock<Block>>>| {
+            blocks.iter().map(|block| block.hash()).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            hashes(service.cached_parent_blocks(child_hash, 4, false)),
+            [child_hash, parent_hash]
+        );
+        assert_eq!(
+            hashes(service.cached_parent_blocks(child_hash, 4, true)),
+            [parent_hash, child_hash]
+        );
+        // the limit still applies from the requested block down
+        assert_eq!(hashes(service.cached_parent_blocks(child_hash, 1, true)), [child_hash]);
+    }
 
-        service.tx_hash_index.insert(tx_hash, (B256::repeat_byte(0x33), 0));
+    #[test]
+    fn block_meta_is_memoized_from_cached_block() {
+        let mut service = test_service();
+        let block = Arc::new(test_block());
+        let block_hash = block.hash();
 
-        service.remove_block_transactions(&block);
+        assert!(service.cached_block_meta(block_hash).is_none());
 
-        assert!(service.tx_hash_index.get(&tx_hash).is_none());
+        assert!(service.full_block_cache.insert(block_hash, Arc::clone(&block)));
+        let meta = service.cached_block_meta(block_hash).expect("meta from cached block");
+        assert_eq!(meta.number, 1);
+        assert_eq!(meta.transaction_count, 1);
+        assert_eq!(service.block_meta_cache.get(&block_hash), Some(&meta));
+
+        // memoized metadata is derived from blocks
+        service.clear(CacheKinds::BLOCKS);
+        assert!(service.block_meta_cache.get(&block_hash).is_none());
     }
 
     #[test]
@@ -1117,6 +3601,34 @@ mod tests {
         assert!(bal.is_some());
     }
 
+    #[test]
+    fn new_senders_are_forwarded_and_cached() {
+        let mut service = test_service();
+        let block_hash = B256::repeat_byte(0x56);
+        let (response_tx, mut response_rx) = oneshot::channel();
+
+        assert!(service.senders_cache.queue(block_hash, response_tx));
+
+        service.on_new_senders(block_hash, Ok(Some(CachedSenders::new(vec![Address::ZERO]))));
+
+        let senders =
+            response_rx.try_recv().expect("queued senders response").expect("senders result");
+        assert_eq!(senders.expect("senders").0.as_slice(), &[Address::ZERO]);
+        assert!(service.senders_cache.get(&block_hash).is_some());
+    }
+
+    #[test]
+    fn reorg_evicts_cached_senders() {
+        let mut service = test_service();
+        let block_hash = B256::repeat_byte(0x57);
+
+        assert!(service.senders_cache.insert(block_hash, CachedSenders::new(vec![Address::ZERO])));
+
+        service.on_reorg_senders(block_hash, Ok(None));
+
+        assert!(service.senders_cache.get(&block_hash).is_none());
+    }
+
     #[test]
     fn cached_revm_bal_size_accounts_for_nested_allocations() {
         let mut account = RevmAccountBal::default();
@@ -1146,6 +3658,64 @@ mod tests {
         assert!(CachedRevmBal::new(DecodedBal::new(Arc::new(bal), raw)).size() > previous_estimate);
     }
 
+    #[tokio::test]
+    async fn versioned_block_read_observes_reorgs() {
+        let cache = EthStateCache::<EthPrimitives>::spawn_with(
+            NoopProvider::default(),
+            EthStateCacheConfig::default(),
+            Runtime::test(),
+        );
+        let block = Arc::new(test_block());
+        let block_hash = block.hash();
+        let _ = cache.to_service.send(CacheAction::CacheNewCanonicalChain {
+            chain_change: ChainChange { blocks: vec![block.clone()], receipts: vec![] },
+        });
+
+        let (cached, epoch) =
+            cache.get_recovered_block_versioned(block_hash).await.unwrap().unwrap();
+        assert_eq!(cached.hash(), block_hash);
+        assert_eq!(epoch, 0);
+
+        let _ = cache.to_service.send(CacheAction::RemoveReorgedChain {
+            chain_change: ChainChange { blocks: vec![], receipts: vec![] },
+        });
+        assert_eq!(cache.reorg_epoch().await.unwrap(), 1);
+
+        let (_, epoch) = cache.get_recovered_block_versioned(block_hash).await.unwrap().unwrap();
+        assert_eq!(epoch, 1);
+        assert!(cache
+            .get_recovered_block_versioned(B256::repeat_byte(0x42))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn sealed_block_senders_are_recovered_on_demand() {
+        let cache = EthStateCache::<EthPrimitives>::spawn_with(
+            NoopProvider::default(),
+            EthStateCacheConfig::default(),
+            Runtime::test(),
+        );
+        let block = test_block().into_sealed_block();
+        let block_hash = block.hash();
+        let _ = cache
+            .to_service
+            .send(CacheAction::SealedBlockResult { block_hash, res: Ok(Some(Arc::new(block))) });
+
+        let cached = cache.get_sealed_block(block_hash).await.unwrap().unwrap();
+        assert!(matches!(cached, CachedBlock::Sealed(_)));
+        assert_eq!(cached.hash(), block_hash);
+
+        // the provider doesn't have the block, so it can only be recovered from the cache
+        let recovered = cache.get_recovered_block(block_hash).await.unwrap().unwrap();
+        assert_eq!(recovered.hash(), block_hash);
+        assert_eq!(recovered.senders().len(), 1);
+
+        let cached = cache.get_sealed_block(block_hash).await.unwrap().unwrap();
+        assert!(Arc::ptr_eq(cached.recovered().unwrap(), &recovered));
+    }
+
     #[tokio::test]
     async fn get_bal_uses_cached_revm_bal() {
         let fetches = Arc::new(AtomicUsize::default());
@@ -1159,6 +3729,16 @@ mod tests {
                 max_bals: 4,
                 max_concurrent_db_requests: 1,
                 max_cached_tx_hashes: 0,
+                max_number_index_entries: None,
+                max_queued_consumers: 1024,
+                fetch_jitter: None,
+                panic_policy: Default::default(),
+                total_max_bytes: None,
+                eviction_policy: Default::default(),
+                new_blocks_debounce: None,
+                skip_duplicate_inserts: false,
+                max_cold_blocks: 0,
+                header_persist_path: None,
             },
             Runtime::test(),
         );
@@ -1183,6 +3763,16 @@ mod tests {
                 max_bals: 4,
                 max_concurrent_db_requests: 1,
                 max_cached_tx_hashes: 0,
+                max_number_index_entries: None,
+                max_queued_consumers: 1024,
+                fetch_jitter: None,
+                panic_policy: Default::default(),
+                total_max_bytes: None,
+                eviction_policy: Default::default(),
+                new_blocks_debounce: None,
+                skip_duplicate_inserts: false,
+                max_cold_blocks: 0,
+                header_persist_path: None,
             },
             Runtime::test(),
         );
@@ -1195,14 +3785,95 @@ mod tests {
         assert_eq!(fetches.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn block_meta_of_fetched_block_is_memoized() {
+        let block_fetches = Arc::new(AtomicUsize::default());
+        let provider =
+            TestBalProvider::new(Arc::default()).with_block_fetches(block_fetches.clone());
+        // blocks aren't cached, so only the memoized metadata can answer the second lookup
+        let cache = EthStateCache::<EthPrimitives>::spawn_with(
+            provider,
+            EthStateCacheConfig {
+                max_blocks: 0,
+                max_receipts: 0,
+                max_headers: 4,
+                max_bals: 0,
+                max_concurrent_db_requests: 1,
+                max_cached_tx_hashes: 0,
+                max_number_index_entries: None,
+                max_queued_consumers: 1024,
+                fetch_jitter: None,
+                panic_policy: Default::default(),
+                total_max_bytes: None,
+                eviction_policy: Default::default(),
+                new_blocks_debounce: None,
+                skip_duplicate_inserts: false,
+                max_cold_blocks: 0,
+                header_persist_path: None,
+            },
+            Runtime::test(),
+        );
+        let block = test_block();
+        let block_hash = block.hash();
+
+        let meta = cache.get_block_meta(block_hash).await.unwrap().unwrap();
+        assert_eq!(meta, CachedBlockMeta::new(&block));
+        assert_eq!(block_fetches.load(Ordering::SeqCst), 1);
+
+        assert_eq!(cache.get_block_meta(block_hash).await.unwrap(), Some(meta));
+        assert_eq!(block_fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn persisted_headers_of_reorged_blocks_are_dropped() {
+        let canonical = Header { number: 1, ..Default::default() };
+        let reorged = Header { number: 2, ..Default::default() };
+        let unknown = Header { number: 3, ..Default::default() };
+        let provider = CanonicalHashes(std::collections::HashMap::from([
+            (1, canonical.hash_slow()),
+            (2, B256::repeat_byte(0x02)),
+        ]));
+
+        assert_eq!(
+            canonical_headers(&provider, vec![canonical.clone(), reorged, unknown]),
+            vec![(canonical.hash_slow(), canonical)]
+        );
+    }
+
+    /// Serves the canonical hashes of a fixed set of block numbers.
+    #[derive(Debug)]
+    struct CanonicalHashes(std::collections::HashMap<BlockNumber, B256>);
+
+    impl BlockHashReader for CanonicalHashes {
+        fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+            Ok(self.0.get(&number).copied())
+        }
+
+        fn canonical_hashes_range(
+            &self,
+            _start: BlockNumber,
+            _end: BlockNumber,
+        ) -> ProviderResult<Vec<B256>> {
+            Ok(Vec::new())
+        }
+    }
+
     #[derive(Clone, Debug, Default)]
     struct TestBalProvider {
         bal_store: BalStoreHandle,
+        /// Counts block fetches, blocks are only served if set.
+        block_fetches: Option<Arc<AtomicUsize>>,
     }
 
     impl TestBalProvider {
         fn new(fetches: Arc<AtomicUsize>) -> Self {
-            Self { bal_store: BalStoreHandle::new(TestBalStore { fetches }) }
+            Self { bal_store: BalStoreHandle::new(TestBalStore { fetches }), block_fetches: None }
+        }
+
+        /// Serves [`test_block`] for any hash and counts the fetches.
+        fn with_block_fetches(mut self, block_fetches: Arc<AtomicUsize>) -> Self {
+            self.block_fetches = Some(block_fetches);
+            self
         }
     }
 
@@ -1455,7 +4126,9 @@ mod tests {
             _id: BlockHashOrNumber,
             _transaction_kind: TransactionVariant,
         ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-            Ok(None)
+            let Some(block_fetches) = &self.block_fetches else { return Ok(None) };
+            block_fetches.fetch_add(1, Ordering::SeqCst);
+            Ok(Some(test_block()))
         }
 
         fn block_range(
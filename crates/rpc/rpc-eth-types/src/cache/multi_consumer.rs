@@ -1,7 +1,7 @@
 //! Metered cache, which also provides storage for senders in order to queue queries that result in
 //! a cache miss.
 
-use super::metrics::CacheMetrics;
+use super::{config::EvictionPolicy, metrics::CacheMetrics};
 use reth_primitives_traits::InMemorySize;
 use schnellru::{ByLength, Limiter, LruMap};
 use std::{
@@ -10,6 +10,10 @@ use std::{
     hash::Hash,
 };
 
+/// How many times a single eviction may keep a frequently used entry with
+/// [`EvictionPolicy::Lfu`] before it evicts the least recently used entry regardless.
+const LFU_MAX_SECOND_CHANCES: usize = 8;
+
 /// A multi-consumer LRU cache.
 pub struct MultiConsumerLruCache<K, V, L, S>
 where
@@ -20,10 +24,16 @@ where
     cache: LruMap<K, V, L>,
     /// All queued consumers.
     queued: HashMap<K, Vec<S>>,
+    /// Maximum number of consumers queued for a single key.
+    max_queued_consumers: usize,
     /// Cache metrics
     metrics: CacheMetrics,
     // Tracked heap usage
     memory_usage: usize,
+    /// Which entry is evicted when the cache is full.
+    eviction_policy: EvictionPolicy,
+    /// Lookup counts of the cached entries, only tracked for [`EvictionPolicy::Lfu`].
+    frequencies: HashMap<K, u32>,
 }
 
 impl<K, V, L, S> Debug for MultiConsumerLruCache<K, V, L, S>
@@ -63,6 +73,18 @@ where
         }
     }
 
+    /// Returns true if the queue for the given key reached the maximum number of consumers.
+    ///
+    /// Further consumers for the key should be rejected instead of queued.
+    pub fn is_queue_full(&self, key: &K) -> bool {
+        self.queued.get(key).is_some_and(|queued| queued.len() >= self.max_queued_consumers)
+    }
+
+    /// Returns true if consumers are queued for the given key, i.e. its value is being fetched.
+    pub fn is_queued(&self, key: &K) -> bool {
+        self.queued.get(key).is_some_and(|queued| !queued.is_empty())
+    }
+
     /// Remove consumers for a given key, this will also remove the key from the cache.
     pub fn remove(&mut self, key: &K) -> Option<Vec<S>>
     where
@@ -71,6 +93,14 @@ where
         self.cache
             .remove(key)
             .inspect(|value| self.memory_usage = self.memory_usage.saturating_sub(value.size()));
+        self.frequencies.remove(key);
+        self.queued
+            .remove(key)
+            .inspect(|removed| self.metrics.queued_consumers_count.decrement(removed.len() as f64))
+    }
+
+    /// Removes the consumers queued for the given key, but keeps a cached value.
+    pub fn take_queued(&mut self, key: &K) -> Option<Vec<S>> {
         self.queued
             .remove(key)
             .inspect(|removed| self.metrics.queued_consumers_count.decrement(removed.len() as f64))
@@ -81,6 +111,9 @@ where
     pub fn get(&mut self, key: &K) -> Option<&mut V> {
         let entry = self.cache.get(key);
         if entry.is_some() {
+            if let Some(frequency) = self.frequencies.get_mut(key) {
+                *frequency = frequency.saturating_add(1);
+            }
             self.metrics.hits_total.increment(1);
         } else {
             self.metrics.misses_total.increment(1);
@@ -88,6 +121,32 @@ where
         entry
     }
 
+    /// Returns a reference to the value for a given key without promoting it.
+    ///
+    /// Use this for wide scans that touch every entry once, so they don't evict the entries that
+    /// are actually in use.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let entry = self.cache.peek(key);
+        if entry.is_some() {
+            self.metrics.hits_total.increment(1);
+        } else {
+            self.metrics.misses_total.increment(1);
+        }
+        entry
+    }
+
+    /// Returns an iterator over the cached entries, from most to least recently used.
+    ///
+    /// This does not promote any entries or record cache hits.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.cache.iter()
+    }
+
+    /// Returns the tracked heap usage of the cached values in bytes.
+    pub const fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+
     /// Inserts a new element into the map.
     ///
     /// Can fail if the element is rejected by the limiter or if we fail to grow an empty map.
@@ -95,26 +154,74 @@ where
     /// See [`LruMap::insert`] for more info.
     pub fn insert<'a>(&mut self, key: L::KeyToInsert<'a>, value: V) -> bool
     where
+        K: Clone,
         L::KeyToInsert<'a>: Hash + PartialEq<K>,
         V: InMemorySize,
     {
         let size = value.size();
 
-        if self.cache.limiter().is_over_the_limit(self.cache.len() + 1) &&
-            let Some((_, evicted)) = self.cache.pop_oldest()
-        {
-            // update tracked memory with the evicted value
-            self.memory_usage = self.memory_usage.saturating_sub(evicted.size());
+        if self.cache.limiter().is_over_the_limit(self.cache.len() + 1) {
+            self.evict();
         }
 
         if self.cache.insert(key, value) {
             self.memory_usage = self.memory_usage.saturating_add(size);
+            if self.eviction_policy.is_lfu() &&
+                let Some((key, _)) = self.cache.peek_newest()
+            {
+                self.frequencies.entry(key.clone()).or_insert(1);
+            }
             true
         } else {
             false
         }
     }
 
+    /// Removes the least recently used entry from the cache and returns it.
+    pub fn pop_oldest(&mut self) -> Option<(K, V)>
+    where
+        V: InMemorySize,
+    {
+        self.cache.pop_oldest().inspect(|(key, value)| {
+            self.frequencies.remove(key);
+            self.memory_usage = self.memory_usage.saturating_sub(value.size());
+        })
+    }
+
+    /// Evicts an entry to make room for a new one, according to the [`EvictionPolicy`].
+    fn evict(&mut self)
+    where
+        K: Clone,
+        V: InMemorySize,
+    {
+        if self.eviction_policy.is_lfu() {
+            // give frequently used entries a second chance, halving their count so entries that
+            // stopped being used eventually age out
+            for _ in 0..LFU_MAX_SECOND_CHANCES {
+                let Some((key, _)) = self.cache.peek_oldest() else { break };
+                let key = key.clone();
+                match self.frequencies.get_mut(&key) {
+                    Some(frequency) if *frequency > 1 => {
+                        *frequency /= 2;
+                        // promote without counting it as a lookup
+                        self.cache.get(&key);
+                    }
+                    _ => break,
+                }
+            }
+        }
+        self.pop_oldest();
+    }
+
+    /// Removes all cached entries.
+    ///
+    /// Queued consumers are kept, they are still answered by the fetch they are waiting on.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.frequencies.clear();
+        self.memory_usage = 0;
+    }
+
     /// Shrinks the capacity of the queue with a lower limit.
     #[inline]
     pub fn shrink_to(&mut self, min_capacity: usize) {
@@ -138,8 +245,23 @@ where
         Self {
             cache: LruMap::new(ByLength::new(max_len)),
             queued: Default::default(),
+            max_queued_consumers: usize::MAX,
             metrics: CacheMetrics::new_with_labels(&[("cache", cache_id.to_string())]),
             memory_usage: 0,
+            eviction_policy: EvictionPolicy::default(),
+            frequencies: Default::default(),
         }
     }
+
+    /// Sets which entry is evicted when the cache is full.
+    pub const fn with_eviction_policy(mut self, eviction_policy: EvictionPolicy) -> Self {
+        self.eviction_policy = eviction_policy;
+        self
+    }
+
+    /// Sets the maximum number of consumers queued for a single key.
+    pub const fn with_max_queued_consumers(mut self, max_queued_consumers: usize) -> Self {
+        self.max_queued_consumers = max_queued_consumers;
+        self
+    }
 }
@@ -0,0 +1,65 @@
+//! On-disk persistence for the headers cache.
+
+use alloy_rlp::{Decodable, Encodable};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Reads the persisted headers from the given file.
+///
+/// Returns an empty list if the file does not exist.
+pub(super) fn load_headers<H: Decodable>(path: &Path) -> io::Result<Vec<H>> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Vec::<H>::decode(&mut bytes.as_slice())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Writes the headers to the given file.
+///
+/// The headers are written to a temporary file first, which then replaces the previous file, so
+/// an interrupted write never leaves a truncated file behind.
+pub(super) fn store_headers<H: Encodable>(path: &Path, headers: &[H]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut buf = Vec::with_capacity(headers.length());
+    headers.encode(&mut buf);
+
+    let tmp_path = tmp_path(path);
+    fs::write(&tmp_path, buf)?;
+    fs::rename(tmp_path, path)
+}
+
+/// Returns the path of the temporary file used while writing `path`.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    tmp.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+
+    #[test]
+    fn headers_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("headers.rlp");
+        let headers = vec![
+            Header { number: 1, ..Default::default() },
+            Header { number: 2, ..Default::default() },
+        ];
+
+        assert!(load_headers::<Header>(&path).unwrap().is_empty());
+
+        store_headers(&path, &headers).unwrap();
+        assert_eq!(load_headers::<Header>(&path).unwrap(), headers);
+    }
+}
@@ -12,7 +12,9 @@ use core::time::Duration;
 use reth_errors::{BlockExecutionError, BlockValidationError, RethError};
 use reth_primitives_traits::transaction::{error::InvalidTransactionError, signed::RecoveryError};
 use reth_revm::db::bal::EvmDatabaseError;
-use reth_rpc_convert::{CallFeesError, EthTxEnvError, TransactionConversionError};
+use reth_rpc_convert::{
+    CallFeesError, EthTxEnvError, SimulateValidationError, TransactionConversionError,
+};
 use reth_rpc_server_types::result::{
     block_id_to_str, internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code,
 };
@@ -187,6 +189,9 @@ pub enum EthApiError {
     /// Error encountered when converting a transaction type
     #[error(transparent)]
     TransactionConversionError(#[from] TransactionConversionError),
+    /// An `eth_simulateV1` transaction request is incomplete or has conflicting fee fields
+    #[error("invalid simulate request: {0}")]
+    InvalidSimulateRequest(#[from] SimulateValidationError),
     /// Error thrown when tracing with a muxTracer fails
     #[error(transparent)]
     MuxTracerError(#[from] MuxError),
@@ -348,6 +353,7 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::Unsupported(msg) => internal_rpc_err(msg),
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
+            err @ EthApiError::InvalidSimulateRequest(_) => invalid_params_rpc_err(err.to_string()),
             err @ EthApiError::ExecutionTimedOut(_) => rpc_error_with_code(
                 jsonrpsee_types::error::CALL_EXECUTION_FAILED_CODE,
                 err.to_string(),
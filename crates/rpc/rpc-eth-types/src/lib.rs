@@ -31,9 +31,13 @@ pub use alloy_rpc_types_eth::FillTransaction;
 pub use block::CachedTransaction;
 pub use builder::config::{EthConfig, EthFilterConfig};
 pub use cache::{
-    config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
-    EthStateCache,
+    config::{CachePanicPolicy, EthStateCacheConfig, EvictionPolicy},
+    db::StateCacheDb,
+    multi_consumer::MultiConsumerLruCache,
+    CacheKinds, CachedBlockMeta, EthStateCache,
 };
+#[cfg(feature = "cache-snapshot")]
+pub use cache::{CacheEntriesSnapshot, CacheSnapshot};
 pub use capabilities::{EthCapabilities, EthCapabilitiesHead, EthCapabilitiesResource};
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
@@ -22,7 +22,7 @@ use reth_evm::{
 use reth_primitives_traits::{
     BlockBody as _, BlockTy, NodePrimitives, Recovered, RecoveredBlock, SealedHeader,
 };
-use reth_rpc_convert::{RpcBlock, RpcConvert, RpcTxReq};
+use reth_rpc_convert::{validate_simulate_request, RpcBlock, RpcConvert, RpcTxReq};
 use reth_rpc_server_types::result::{block_id_to_str, rpc_err};
 use reth_storage_api::{noop::NoopProvider, StateProvider};
 use revm::{
@@ -480,8 +480,13 @@ where
         }
     }
 
-    let tx =
-        converter.build_simulate_v1_transaction(tx).map_err(|e| EthApiError::other(e.into()))?;
+    validate_simulate_request(tx.as_ref()).map_err(EthApiError::InvalidSimulateRequest)?;
+
+    // An explicitly empty access list stays on the request since it still selects the tx type.
+    let access_list = tx.as_mut().access_list.take_if(|list| !list.is_empty()).unwrap_or_default();
+    let tx = converter
+        .build_simulate_v1_transaction_with_access_list(tx, access_list)
+        .map_err(|e| EthApiError::other(e.into()))?;
 
     Ok(Recovered::new_unchecked(tx, from))
 }
@@ -51,23 +51,8 @@ impl<T: SignedTransaction> TransactionSource<T> {
     {
         match self {
             Self::Pool(tx) => resp_builder.fill_pending(tx),
-            Self::Block {
-                transaction,
-                index,
-                block_hash,
-                block_number,
-                block_timestamp,
-                base_fee,
-            } => {
-                let tx_info = TransactionInfo {
-                    hash: Some(*transaction.tx_hash()),
-                    index: Some(index),
-                    block_hash: Some(block_hash),
-                    block_number: Some(block_number),
-                    block_timestamp: Some(block_timestamp),
-                    base_fee,
-                };
-
+            Self::Block { .. } => {
+                let (transaction, tx_info) = self.split();
                 resp_builder.fill(transaction, tx_info)
             }
         }
@@ -117,6 +117,9 @@ pub mod cache {
     /// Default cache size for the block cache: 5000 blocks.
     pub const DEFAULT_BLOCK_CACHE_MAX_LEN: u32 = 5000;
 
+    /// Default cache size for the low priority block cache: 500 blocks.
+    pub const DEFAULT_COLD_BLOCK_CACHE_MAX_LEN: u32 = 500;
+
     /// Default cache size for the receipts cache: 2000 receipts.
     pub const DEFAULT_RECEIPT_CACHE_MAX_LEN: u32 = 2000;
 
@@ -131,4 +134,7 @@ pub mod cache {
 
     /// Default maximum number of transaction hashes to cache for lookups.
     pub const DEFAULT_MAX_CACHED_TX_HASHES: u32 = 30_000;
+
+    /// Default maximum number of consumers waiting on the same in-flight cache fetch.
+    pub const DEFAULT_MAX_QUEUED_CONSUMERS: usize = 1024;
 }
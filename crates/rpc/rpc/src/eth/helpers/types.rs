@@ -13,11 +13,22 @@ pub type EthRpcConverter<ChainSpec> =
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_consensus::{Transaction, TxType};
-    use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_consensus::{
+        transaction::{Recovered, SignerRecoverable},
+        Header, Transaction, TxEip1559, TxLegacy, TxType,
+    };
+    use alloy_primitives::{Address, Signature, B256};
+    use alloy_rpc_types_eth::{AccessList, AccessListItem, TransactionInfo, TransactionRequest};
     use reth_chainspec::MAINNET;
-    use reth_rpc_eth_types::simulate::resolve_transaction;
+    use reth_ethereum_primitives::{Block, BlockBody, TransactionSigned};
+    use reth_primitives_traits::RecoveredBlock;
+    use reth_rpc_convert::{
+        EthTxCompat, MinimalTransaction, RpcConvert, SimulateValidationError,
+        TransactionConversionError, TransactionInfoExt,
+    };
+    use reth_rpc_eth_types::{simulate::resolve_transaction, EthApiError};
     use revm::database::CacheDB;
+    use std::convert::Infallible;
 
     #[test]
     fn test_resolve_transaction_empty_request() {
@@ -69,6 +80,28 @@ mod tests {
         assert_eq!(tx.gas_price(), None);
     }
 
+    #[test]
+    fn test_resolve_transaction_conflicting_fee_fields() {
+        let mut db = CacheDB::<reth_revm::db::EmptyDBTyped<reth_errors::ProviderError>>::default();
+        let rpc_converter = EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone()));
+
+        let tx = TransactionRequest {
+            gas_price: Some(100),
+            max_fee_per_gas: Some(200),
+            ..Default::default()
+        };
+
+        let err = resolve_transaction(tx, 21000, 0, 1, false, &mut db, &rpc_converter).unwrap_err();
+
+        assert!(matches!(
+            err,
+            EthApiError::InvalidSimulateRequest(SimulateValidationError::ConflictingFeeFields)
+        ));
+        let err = jsonrpsee_types::ErrorObject::from(err);
+        assert_eq!(err.code(), jsonrpsee_types::error::INVALID_PARAMS_CODE);
+        assert!(err.message().contains("both gasPrice and"));
+    }
+
     #[test]
     fn test_resolve_transaction_wraps_max_nonce_when_nonce_check_disabled() {
         let mut db = CacheDB::<reth_revm::db::EmptyDBTyped<reth_errors::ProviderError>>::default();
@@ -80,4 +113,219 @@ mod tests {
 
         assert_eq!(result.nonce(), 0);
     }
+
+    #[test]
+    fn test_resolve_transaction_with_access_list() {
+        let mut db = CacheDB::<reth_revm::db::EmptyDBTyped<reth_errors::ProviderError>>::default();
+        let rpc_converter = EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone()));
+
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::random(),
+            storage_keys: vec![B256::random()],
+        }]);
+        let tx = TransactionRequest {
+            gas_price: Some(100),
+            access_list: Some(access_list.clone()),
+            ..Default::default()
+        };
+
+        let result = resolve_transaction(tx, 21000, 0, 1, false, &mut db, &rpc_converter).unwrap();
+
+        assert_eq!(result.tx_type(), TxType::Eip2930);
+        assert_eq!(result.access_list(), Some(&access_list));
+    }
+
+    #[test]
+    fn test_resolve_transaction_rejects_unsupported_access_list() {
+        let mut db = CacheDB::<reth_revm::db::EmptyDBTyped<reth_errors::ProviderError>>::default();
+        // Builds legacy transactions only, which can't carry an access list.
+        let rpc_converter = EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone()))
+            .with_sim_tx_converter(|_: TransactionRequest| {
+                Ok::<_, Infallible>(TransactionSigned::new_unhashed(
+                    TxLegacy::default().into(),
+                    Signature::test_signature(),
+                ))
+            });
+
+        let tx = TransactionRequest {
+            access_list: Some(AccessList(vec![AccessListItem {
+                address: Address::random(),
+                storage_keys: Vec::new(),
+            }])),
+            ..Default::default()
+        };
+        let err = resolve_transaction(tx, 21000, 0, 1, false, &mut db, &rpc_converter).unwrap_err();
+        assert!(err.to_string().contains("access lists are not supported"));
+
+        // Without an access list the converter is used as is.
+        let tx = TransactionRequest::default();
+        let result = resolve_transaction(tx, 21000, 0, 1, false, &mut db, &rpc_converter).unwrap();
+        assert_eq!(result.tx_type(), TxType::Legacy);
+    }
+
+    #[test]
+    fn test_fill_try_into_recovered_roundtrip() {
+        let rpc_converter = EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone()));
+
+        let tx = TransactionSigned::new_unhashed(
+            TxLegacy { gas_limit: 21000, gas_price: 100, ..Default::default() }.into(),
+            Signature::test_signature(),
+        );
+        let recovered = tx.try_into_recovered().unwrap();
+
+        let rpc_tx = rpc_converter.fill(recovered.clone(), TransactionInfo::default()).unwrap();
+        let roundtrip = rpc_converter.try_into_recovered(rpc_tx).unwrap();
+
+        assert_eq!(roundtrip, recovered);
+    }
+
+    #[test]
+    fn test_try_into_recovered_rejects_wrong_sender() {
+        let rpc_converter = EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone()));
+
+        let tx = TransactionSigned::new_unhashed(
+            TxLegacy { gas_limit: 21000, gas_price: 100, ..Default::default() }.into(),
+            Signature::test_signature(),
+        );
+        let signer = tx.recover_signer().unwrap();
+        let sender = Address::random();
+
+        let rpc_tx = rpc_converter
+            .fill(Recovered::new_unchecked(tx, sender), TransactionInfo::default())
+            .unwrap();
+        let err = rpc_converter.try_into_recovered(rpc_tx).unwrap_err();
+
+        assert!(err.to_string().contains(&sender.to_string()));
+        assert!(err.to_string().contains(&signer.to_string()));
+    }
+
+    #[test]
+    fn test_eth_tx_compat_roundtrip() {
+        let rpc_converter = EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone()))
+            .with_rpc_tx_converter(EthTxCompat)
+            .with_sim_tx_converter(EthTxCompat);
+
+        let tx = TransactionSigned::new_unhashed(
+            TxLegacy { gas_limit: 21000, gas_price: 100, ..Default::default() }.into(),
+            Signature::test_signature(),
+        );
+        let recovered = tx.try_into_recovered().unwrap();
+
+        let rpc_tx = rpc_converter.fill(recovered.clone(), TransactionInfo::default()).unwrap();
+        assert_eq!(rpc_converter.try_into_recovered(rpc_tx).unwrap(), recovered);
+    }
+
+    /// Returns a block with two legacy transactions from distinct senders.
+    fn test_block() -> RecoveredBlock<Block> {
+        let transactions = (0..2)
+            .map(|nonce| {
+                TransactionSigned::new_unhashed(
+                    TxLegacy { nonce, gas_limit: 21000, gas_price: 100, ..Default::default() }
+                        .into(),
+                    Signature::test_signature(),
+                )
+            })
+            .collect();
+        let block = Block {
+            header: Header {
+                number: 7,
+                timestamp: 12,
+                base_fee_per_gas: Some(10),
+                ..Default::default()
+            },
+            body: BlockBody { transactions, ..Default::default() },
+        };
+        RecoveredBlock::new_unhashed(block, vec![Address::repeat_byte(1), Address::repeat_byte(2)])
+    }
+
+    #[test]
+    fn test_fill_from_block() {
+        let rpc_converter = EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone()));
+        let block = test_block();
+
+        let rpc_tx = rpc_converter.fill_from_block(1, &block).unwrap();
+
+        assert_eq!(rpc_tx.inner.signer(), Address::repeat_byte(2));
+        assert_eq!(rpc_tx.inner.nonce(), 1);
+        assert_eq!(rpc_tx.block_hash, Some(block.hash()));
+        assert_eq!(rpc_tx.block_number, Some(7));
+        assert_eq!(rpc_tx.transaction_index, Some(1));
+        assert_eq!(
+            rpc_tx,
+            rpc_converter
+                .fill(
+                    block.recovered_transaction(1).unwrap().cloned(),
+                    TransactionInfo::from_block_and_index(&block, 1).unwrap(),
+                )
+                .unwrap()
+        );
+
+        let err = rpc_converter.fill_from_block(2, &block).unwrap_err();
+        assert!(matches!(
+            err,
+            EthApiError::TransactionConversionError(
+                TransactionConversionError::TxIndexOutOfBounds { index: 2, len: 2 }
+            )
+        ));
+    }
+
+    #[test]
+    fn test_fill_minimal() {
+        let block = test_block();
+        let tx = block.recovered_transaction(1).unwrap().cloned();
+        let tx_info = TransactionInfo::from_block_and_index(&block, 1).unwrap();
+
+        let rpc_converter = EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone()));
+        let minimal = rpc_converter.fill_minimal(tx.clone(), tx_info).unwrap();
+        assert_eq!(
+            minimal,
+            MinimalTransaction {
+                hash: tx_info.hash.unwrap(),
+                from: Address::repeat_byte(2),
+                to: None,
+                nonce: 1,
+                block_hash: Some(block.hash()),
+                block_number: Some(7),
+                transaction_index: Some(1),
+            }
+        );
+
+        // The minimal form follows the rpc transaction built by a custom converter.
+        let rpc_converter = rpc_converter.with_rpc_tx_converter(
+            |tx: TransactionSigned, _signer: Address, tx_info: TransactionInfo| {
+                Ok::<_, Infallible>(alloy_rpc_types_eth::Transaction::from_transaction(
+                    Recovered::new_unchecked(tx.into(), Address::ZERO),
+                    tx_info,
+                ))
+            },
+        );
+        let minimal = rpc_converter.fill_minimal(tx, tx_info).unwrap();
+        assert_eq!(minimal.from, Address::ZERO);
+        assert_eq!(minimal.block_hash, Some(block.hash()));
+    }
+
+    #[test]
+    fn test_fill_with_base_fee() {
+        let rpc_converter = EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone()));
+
+        let tx = TransactionSigned::new_unhashed(
+            TxEip1559 {
+                gas_limit: 21000,
+                max_fee_per_gas: 100,
+                max_priority_fee_per_gas: 5,
+                ..Default::default()
+            }
+            .into(),
+            Signature::test_signature(),
+        );
+        let tx = Recovered::new_unchecked(tx, Address::repeat_byte(1));
+        let tx_info = TransactionInfo { base_fee: Some(10), ..Default::default() };
+
+        // the given base fee replaces the one of the transaction info
+        let rpc_tx = rpc_converter.fill_with_base_fee(tx.clone(), tx_info, Some(50)).unwrap();
+        assert_eq!(rpc_tx.effective_gas_price, Some(55));
+
+        let rpc_tx = rpc_converter.fill(tx, tx_info).unwrap();
+        assert_eq!(rpc_tx.effective_gas_price, Some(15));
+    }
 }
@@ -0,0 +1,115 @@
+//! Async access to a [`BlobStore`].
+
+use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError};
+use alloy_eips::eip7594::BlobTransactionSidecarVariant;
+use alloy_primitives::B256;
+use std::sync::Arc;
+
+/// Wraps a [`BlobStore`] and runs its operations on the blocking thread pool.
+///
+/// Blob store implementations like the [`DiskFileBlobStore`](crate::blobstore::DiskFileBlobStore)
+/// do blocking file IO, this adapter lets async consumers use them without stalling the runtime.
+#[derive(Debug)]
+pub struct AsyncBlobStore<S> {
+    store: Arc<S>,
+}
+
+impl<S> Clone for AsyncBlobStore<S> {
+    fn clone(&self) -> Self {
+        Self { store: Arc::clone(&self.store) }
+    }
+}
+
+impl<S: BlobStore> AsyncBlobStore<S> {
+    /// Creates a new async adapter for the given store.
+    pub fn new(store: S) -> Self {
+        Self { store: Arc::new(store) }
+    }
+
+    /// Returns the wrapped store.
+    pub fn inner(&self) -> &S {
+        &self.store
+    }
+
+    /// Retrieves the decoded blob data for the given transaction hash.
+    pub async fn get(
+        &self,
+        tx: B256,
+    ) -> Result<Option<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
+        self.spawn_blocking(move |store| store.get(tx)).await?
+    }
+
+    /// Retrieves all decoded blob data for the given transaction hashes.
+    ///
+    /// See [`BlobStore::get_all`].
+    pub async fn get_all(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<(B256, Arc<BlobTransactionSidecarVariant>)>, BlobStoreError> {
+        self.spawn_blocking(move |store| store.get_all(txs)).await?
+    }
+
+    /// Inserts the blob sidecar into the store.
+    pub async fn insert(
+        &self,
+        tx: B256,
+        data: BlobTransactionSidecarVariant,
+    ) -> Result<(), BlobStoreError> {
+        self.spawn_blocking(move |store| store.insert(tx, data)).await?
+    }
+
+    /// Inserts multiple blob sidecars into the store.
+    pub async fn insert_all(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
+        self.spawn_blocking(move |store| store.insert_all(txs)).await?
+    }
+
+    /// Deletes the blob sidecar from the store.
+    pub async fn delete(&self, tx: B256) -> Result<(), BlobStoreError> {
+        self.spawn_blocking(move |store| store.delete(tx)).await?
+    }
+
+    /// Runs the cleanup of the store.
+    ///
+    /// See [`BlobStore::cleanup`].
+    pub async fn cleanup(&self) -> Result<BlobStoreCleanupStat, BlobStoreError> {
+        self.spawn_blocking(|store| store.cleanup()).await
+    }
+
+    /// Runs the given closure with the store on the blocking thread pool.
+    async fn spawn_blocking<T, F>(&self, f: F) -> Result<T, BlobStoreError>
+    where
+        F: FnOnce(&S) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let store = Arc::clone(&self.store);
+        tokio::task::spawn_blocking(move || f(&store))
+            .await
+            .map_err(|err| BlobStoreError::Other(Box::new(err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blobstore::InMemoryBlobStore;
+    use alloy_consensus::BlobTransactionSidecar;
+
+    #[tokio::test]
+    async fn async_store_roundtrip() {
+        let store = AsyncBlobStore::new(InMemoryBlobStore::default());
+        let tx = B256::random();
+        let sidecar = BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar::default());
+
+        store.insert(tx, sidecar.clone()).await.unwrap();
+        assert_eq!(store.get(tx).await.unwrap().as_deref(), Some(&sidecar));
+        assert_eq!(store.get_all(vec![tx]).await.unwrap().len(), 1);
+
+        store.delete(tx).await.unwrap();
+        store.cleanup().await.unwrap();
+        assert!(store.get(tx).await.unwrap().is_none());
+        assert_eq!(store.inner().blobs_len(), 0);
+    }
+}
@@ -1,26 +1,56 @@
 //! A simple diskstore for blobs
 
 use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize};
+#[cfg(feature = "metrics")]
+use crate::metrics::DiskBlobStoreReadMetrics;
 use alloy_eips::{
-    eip4844::{BlobAndProofV1, BlobAndProofV2, BlobCellsAndProofsV1},
+    eip4844::{
+        env_settings::EnvKzgSettings, BlobAndProofV1, BlobAndProofV2, BlobCellsAndProofsV1,
+        BlobTransactionSidecar,
+    },
     eip7594::{BlobCellMask, BlobTransactionSidecarVariant, Cell},
     eip7840::BlobParams,
     merge::EPOCH_SLOTS,
 };
-use alloy_primitives::{map::B256Set, TxHash, B128, B256};
+use alloy_primitives::{
+    map::{B256Map, B256Set},
+    Bytes, TxHash, B128, B256,
+};
 use parking_lot::{Mutex, RwLock};
 use schnellru::{ByLength, LruMap};
-use std::{fmt, fs, io, path::PathBuf, sync::Arc};
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tracing::{debug, trace};
 
 /// How many [`BlobTransactionSidecarVariant`] to cache in memory.
 pub const DEFAULT_MAX_CACHED_BLOBS: u32 = 100;
 
+/// Delay before the first retry of a blob file read that failed with a transient error, doubled
+/// for every further retry.
+const READ_RETRY_BACKOFF: Duration = Duration::from_millis(1);
+
+/// Upper bound for [`DiskFileBlobStoreConfig::read_retries`], which keeps the total backoff of a
+/// single read around 30ms.
+pub const MAX_READ_RETRIES: u32 = 5;
+
+/// Into how many independently locked shards the in-memory blob cache is split by default.
+pub const DEFAULT_BLOB_CACHE_SHARDS: usize = 1;
+
+/// How many of the most recently used sidecars every blob cache shard keeps when the cache is
+/// shrunk.
+const BLOB_CACHE_SHRINK_FLOOR: usize = 2;
+
 /// A cache size heuristic based on the highest blob params
 ///
 /// This uses the max blobs per tx and max blobs per block over 16 epochs: `21 * 6 * 512 = 64512`
 /// This should be ~4MB
-const VERSIONED_HASH_TO_TX_HASH_CACHE_SIZE: u64 =
+///
+/// Used unless [`DiskFileBlobStoreConfig::max_versioned_hash_entries`] is set.
+pub const VERSIONED_HASH_TO_TX_HASH_CACHE_SIZE: u64 =
     BlobParams::bpo2().max_blobs_per_tx * BlobParams::bpo2().max_blob_count * EPOCH_SLOTS * 16;
 
 /// A blob store that stores blob data on disk.
@@ -40,8 +70,41 @@ impl DiskFileBlobStore {
         opts: DiskFileBlobStoreConfig,
     ) -> Result<Self, DiskFileBlobStoreError> {
         let blob_dir = blob_dir.into();
-        let DiskFileBlobStoreConfig { max_cached_entries, .. } = opts;
-        let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries);
+        let DiskFileBlobStoreConfig {
+            max_cached_entries,
+            cache_shards,
+            file_mode,
+            max_blob_file_bytes,
+            allow_migration,
+            read_retries,
+            #[cfg(feature = "io-threads")]
+            io_threads,
+            max_versioned_hash_entries,
+            dedup_hardlinks,
+            shrink_cache_after_idle,
+            ..
+        } = opts;
+        let mut inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries, cache_shards);
+        if let Some(max_versioned_hash_entries) = max_versioned_hash_entries {
+            inner.versioned_hashes_to_txhash =
+                Mutex::new(LruMap::new(ByLength::new(max_versioned_hash_entries)));
+        }
+        inner.file_mode = file_mode;
+        inner.max_blob_file_bytes = max_blob_file_bytes;
+        inner.allow_migration = allow_migration;
+        inner.read_retries = read_retries.min(MAX_READ_RETRIES);
+        inner.dedup_hardlinks = dedup_hardlinks && cfg!(unix);
+        inner.shrink_cache_after_idle = shrink_cache_after_idle;
+        #[cfg(feature = "io-threads")]
+        if io_threads > 1 {
+            inner.io_pool = Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(io_threads)
+                    .thread_name(|i| format!("blob-io-{i:02}"))
+                    .build()
+                    .map_err(DiskFileBlobStoreError::IoThreadPool)?,
+            );
+        }
 
         // initialize the blob store
         inner.delete_all()?;
@@ -50,14 +113,115 @@ impl DiskFileBlobStore {
         Ok(Self { inner: Arc::new(inner) })
     }
 
+    /// Reads and decodes every blob file and validates the blobs against their commitments.
+    ///
+    /// This is a diagnostic for suspected disk corruption, files are read one at a time so the
+    /// store is never loaded into memory at once. Files written concurrently may or may not be
+    /// included.
+    pub fn verify(&self) -> Result<VerifyReport, DiskFileBlobStoreError> {
+        self.inner.verify()
+    }
+
+    /// Reports what [`BlobStore::cleanup`] would delete, without touching the disk.
+    ///
+    /// The pending deletions are kept, so a later cleanup still removes them. Blobs deleted or
+    /// re-inserted in the meantime can make the actual cleanup differ from the preview.
+    pub fn cleanup_dry_run(&self) -> BlobStoreCleanupStat {
+        self.inner.cleanup(true)
+    }
+
+    /// Compacts the blob directory.
+    ///
+    /// This runs a full [`BlobStore::cleanup`], removes stray files that don't belong to a live
+    /// blob, such as leftover temporary files or blob files that fail to decode, and rewrites
+    /// every remaining blob file in place to defragment it.
+    ///
+    /// This is a maintenance operation meant to run during low traffic: every blob file is read
+    /// and written once, and each rewrite or removal holds the file lock.
+    pub fn compact(&self) -> CompactReport {
+        self.inner.compact()
+    }
+
+    /// Shrinks the in-memory blob cache down to a few recently used sidecars per shard and drops
+    /// the versioned hashes of blobs that are no longer on disk from the index.
+    ///
+    /// Both maps are rebuilt, so the memory they grew to is released. The dropped sidecars are
+    /// still read from disk on demand. This is meant for quiet periods: a sidecar inserted
+    /// concurrently may lose its versioned hashes and then only be served by transaction hash.
+    ///
+    /// See [`DiskFileBlobStoreConfig::with_shrink_cache_after_idle`] to run this automatically.
+    pub fn shrink_cache(&self) {
+        let _last_access = self.inner.last_access.lock();
+        self.inner.shrink_cache();
+    }
+
+    /// Moves the blob files of the given transactions into the `other` disk store.
+    ///
+    /// Unlike [`BlobStore::drain_into`], the blob files are renamed instead of being re-encoded,
+    /// and only copied if they can't be renamed, e.g. because the stores are on different file
+    /// systems. A renamed file keeps its permissions. Transactions without a sidecar in this
+    /// store, or that are pending deletion, are skipped.
+    pub fn drain_into_disk(&self, other: &Self, txs: Vec<B256>) -> Result<(), BlobStoreError> {
+        if Arc::ptr_eq(&self.inner, &other.inner) {
+            return Ok(())
+        }
+        for tx in txs {
+            self.inner.move_into(&other.inner, tx)?;
+        }
+        Ok(())
+    }
+
+    /// Returns a snapshot of the versioned hashes the store can currently map to a transaction,
+    /// most recently inserted first.
+    ///
+    /// This is a diagnostic for `engine_getBlobs` misses and is not part of [`BlobStore`]. The
+    /// index is bounded, so a versioned hash can be missing here even though its sidecar is
+    /// still stored, in which case it is not served by versioned hash.
+    pub fn known_versioned_hashes(&self) -> Vec<B256> {
+        self.inner.versioned_hashes_to_txhash.lock().iter().map(|(hash, _)| *hash).collect()
+    }
+
+    /// Rewrites every stored EIP-4844 sidecar in the EIP-7594 format.
+    ///
+    /// `convert` is expected to re-derive the cell proofs and return the
+    /// [`BlobTransactionSidecarVariant::Eip7594`] sidecar. Sidecars already in the EIP-7594 format
+    /// are skipped, converted sidecars replace their blob file atomically.
+    ///
+    /// This is a one-time upgrade operation and returns
+    /// [`DiskFileBlobStoreError::MigrationDisabled`] unless the store was opened with
+    /// [`DiskFileBlobStoreConfig::with_allow_migration`].
+    pub fn migrate_to_v2(
+        &self,
+        convert: impl Fn(&BlobTransactionSidecar) -> BlobTransactionSidecarVariant,
+    ) -> Result<MigrationReport, DiskFileBlobStoreError> {
+        self.inner.migrate_to_v2(convert)
+    }
+
+    /// Inserts sidecars whose blob file contents are already RLP encoded, skipping the encode pass
+    /// of [`BlobStore::insert_all`].
+    ///
+    /// The encoded bytes are written as is, while the decoded sidecars are cached and indexed by
+    /// their versioned hashes. The caller must ensure the bytes are the
+    /// [`BlobTransactionSidecarVariant::rlp_encode_fields`] encoding of the sidecar.
+    pub fn insert_all_encoded(
+        &self,
+        entries: Vec<(B256, Arc<BlobTransactionSidecarVariant>, Bytes)>,
+    ) -> Result<(), BlobStoreError> {
+        if entries.is_empty() {
+            return Ok(())
+        }
+        self.inner.record_access();
+        self.inner.insert_many_encoded(entries)
+    }
+
     #[cfg(test)]
     fn is_cached(&self, tx: &B256) -> bool {
-        self.inner.blob_cache.lock().get(tx).is_some()
+        self.inner.blob_cache.get(tx).is_some()
     }
 
     #[cfg(test)]
     fn clear_cache(&self) {
-        self.inner.blob_cache.lock().clear()
+        self.inner.blob_cache.clear()
     }
 
     /// Look up EIP-7594 blobs by their versioned hashes.
@@ -76,25 +240,27 @@ impl DiskFileBlobStore {
         // order
         let mut result = vec![None; versioned_hashes.len()];
         let mut missing_count = result.len();
-        // first scan all cached full sidecars
-        for (_tx_hash, blob_sidecar) in self.inner.blob_cache.lock().iter() {
-            if let Some(blob_sidecar) = blob_sidecar.as_eip7594() {
-                for (hash_idx, match_result) in
-                    blob_sidecar.match_versioned_hashes(versioned_hashes)
-                {
-                    let slot = &mut result[hash_idx];
-                    if slot.is_none() {
-                        missing_count -= 1;
+        // first scan all cached full sidecars, one shard at a time
+        for shard in self.inner.blob_cache.shards() {
+            for (_tx_hash, blob_sidecar) in shard.lock().iter() {
+                if let Some(blob_sidecar) = blob_sidecar.as_eip7594() {
+                    for (hash_idx, match_result) in
+                        blob_sidecar.match_versioned_hashes(versioned_hashes)
+                    {
+                        let slot = &mut result[hash_idx];
+                        if slot.is_none() {
+                            missing_count -= 1;
+                        }
+                        *slot = Some(match_result);
                     }
-                    *slot = Some(match_result);
                 }
-            }
 
-            // return early if all blobs are found.
-            if missing_count == 0 {
-                // since versioned_hashes may have duplicates, we double check here
-                if result.iter().all(|blob| blob.is_some()) {
-                    return Ok(result);
+                // return early if all blobs are found.
+                if missing_count == 0 {
+                    // since versioned_hashes may have duplicates, we double check here
+                    if result.iter().all(|blob| blob.is_some()) {
+                        return Ok(result);
+                    }
                 }
             }
         }
@@ -147,14 +313,7 @@ impl DiskFileBlobStore {
         let mut result = vec![None; versioned_hashes.len()];
         let mut missing_count = result.len();
 
-        let cached_blob_sidecars = self
-            .inner
-            .blob_cache
-            .lock()
-            .iter()
-            .map(|(_, blob_sidecar)| Arc::clone(blob_sidecar))
-            .collect::<Vec<_>>();
-        for blob_sidecar in cached_blob_sidecars {
+        for blob_sidecar in self.inner.blob_cache.sidecars() {
             if let Some(blob_sidecar) = blob_sidecar.as_eip7594() {
                 for (hash_idx, match_result) in blob_sidecar
                     .match_versioned_hashes_cells(versioned_hashes, cell_mask)
@@ -211,6 +370,7 @@ impl DiskFileBlobStore {
 
 impl BlobStore for DiskFileBlobStore {
     fn insert(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError> {
+        self.inner.record_access();
         self.inner.insert_one(tx, data)
     }
 
@@ -221,6 +381,7 @@ impl BlobStore for DiskFileBlobStore {
         if txs.is_empty() {
             return Ok(())
         }
+        self.inner.record_access();
         self.inner.insert_many(txs)
     }
 
@@ -241,35 +402,11 @@ impl BlobStore for DiskFileBlobStore {
     }
 
     fn cleanup(&self) -> BlobStoreCleanupStat {
-        let txs_to_delete = std::mem::take(&mut *self.inner.txs_to_delete.write());
-        let mut stat = BlobStoreCleanupStat::default();
-        let mut subsize = 0;
-        debug!(target:"txpool::blob", num_blobs=%txs_to_delete.len(), "Removing blobs from disk");
-        for tx in txs_to_delete {
-            let path = self.inner.blob_disk_file(tx);
-            let filesize = fs::metadata(&path).map_or(0, |meta| meta.len());
-            match fs::remove_file(&path) {
-                Ok(_) => {
-                    stat.delete_succeed += 1;
-                    subsize += filesize;
-                }
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                    // Already deleted by a concurrent cleanup task
-                    stat.delete_succeed += 1;
-                }
-                Err(e) => {
-                    stat.delete_failed += 1;
-                    let err = DiskFileBlobStoreError::DeleteFile(tx, path, e);
-                    debug!(target:"txpool::blob", %err);
-                }
-            };
-        }
-        self.inner.size_tracker.sub_size(subsize as usize);
-        self.inner.size_tracker.sub_len(stat.delete_succeed);
-        stat
+        self.inner.cleanup(false)
     }
 
     fn get(&self, tx: B256) -> Result<Option<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
+        self.inner.record_access();
         self.inner.get_one(tx)
     }
 
@@ -284,6 +421,7 @@ impl BlobStore for DiskFileBlobStore {
         if txs.is_empty() {
             return Ok(Vec::new())
         }
+        self.inner.record_access();
         self.inner.get_all(txs)
     }
 
@@ -294,9 +432,21 @@ impl BlobStore for DiskFileBlobStore {
         if txs.is_empty() {
             return Ok(Vec::new())
         }
+        self.inner.record_access();
         self.inner.get_exact(txs)
     }
 
+    fn get_for_block(
+        &self,
+        tx_hashes: &[B256],
+    ) -> Result<Vec<Option<Arc<BlobTransactionSidecarVariant>>>, BlobStoreError> {
+        if tx_hashes.is_empty() {
+            return Ok(Vec::new())
+        }
+        self.inner.record_access();
+        Ok(self.inner.get_for_block(tx_hashes))
+    }
+
     fn get_by_versioned_hashes_v1(
         &self,
         versioned_hashes: &[B256],
@@ -304,19 +454,21 @@ impl BlobStore for DiskFileBlobStore {
         // the response must always be the same len as the request, misses must be None
         let mut result = vec![None; versioned_hashes.len()];
 
-        // first scan all cached full sidecars
-        for (_tx_hash, blob_sidecar) in self.inner.blob_cache.lock().iter() {
-            if let Some(blob_sidecar) = blob_sidecar.as_eip4844() {
-                for (hash_idx, match_result) in
-                    blob_sidecar.match_versioned_hashes(versioned_hashes)
-                {
-                    result[hash_idx] = Some(match_result);
+        // first scan all cached full sidecars, one shard at a time
+        for shard in self.inner.blob_cache.shards() {
+            for (_tx_hash, blob_sidecar) in shard.lock().iter() {
+                if let Some(blob_sidecar) = blob_sidecar.as_eip4844() {
+                    for (hash_idx, match_result) in
+                        blob_sidecar.match_versioned_hashes(versioned_hashes)
+                    {
+                        result[hash_idx] = Some(match_result);
+                    }
                 }
-            }
 
-            // return early if all blobs are found.
-            if result.iter().all(|blob| blob.is_some()) {
-                return Ok(result);
+                // return early if all blobs are found.
+                if result.iter().all(|blob| blob.is_some()) {
+                    return Ok(result);
+                }
             }
         }
 
@@ -390,17 +542,19 @@ impl BlobStore for DiskFileBlobStore {
 
     fn has_versioned_hashes(&self, versioned_hashes: &[B256]) -> Result<Vec<bool>, BlobStoreError> {
         let mut result = vec![false; versioned_hashes.len()];
-        for (_tx_hash, blob_sidecar) in self.inner.blob_cache.lock().iter() {
-            for available_hash in blob_sidecar.versioned_hashes() {
-                for (idx, requested_hash) in versioned_hashes.iter().enumerate() {
-                    if !result[idx] && *requested_hash == available_hash {
-                        result[idx] = true;
+        for shard in self.inner.blob_cache.shards() {
+            for (_tx_hash, blob_sidecar) in shard.lock().iter() {
+                for available_hash in blob_sidecar.versioned_hashes() {
+                    for (idx, requested_hash) in versioned_hashes.iter().enumerate() {
+                        if !result[idx] && *requested_hash == available_hash {
+                            result[idx] = true;
+                        }
                     }
                 }
-            }
 
-            if result.iter().all(|available| *available) {
-                return Ok(result)
+                if result.iter().all(|available| *available) {
+                    return Ok(result)
+                }
             }
         }
 
@@ -425,6 +579,27 @@ impl BlobStore for DiskFileBlobStore {
         Ok(result)
     }
 
+    /// Looks up the transaction of the versioned hash in the index and checks whether its blob
+    /// file exists, falling back to scanning the blob cache.
+    ///
+    /// Note: the index is an LRU, so a blob whose index entry was evicted is only found while its
+    /// sidecar is cached and is otherwise reported as missing even if the file is on disk.
+    fn contains_versioned_hash(&self, versioned_hash: B256) -> Result<bool, BlobStoreError> {
+        let tx_hash = self.inner.versioned_hashes_to_txhash.lock().get(&versioned_hash).copied();
+        if let Some(tx_hash) = tx_hash &&
+            self.inner.contains(tx_hash)?
+        {
+            return Ok(true)
+        }
+
+        Ok(self.inner.blob_cache.shards().any(|shard| {
+            shard
+                .lock()
+                .iter()
+                .any(|(_, sidecar)| sidecar.versioned_hashes().any(|hash| hash == versioned_hash))
+        }))
+    }
+
     fn get_cells(
         &self,
         tx: B256,
@@ -448,6 +623,11 @@ impl BlobStore for DiskFileBlobStore {
         Some(self.inner.size_tracker.data_size())
     }
 
+    /// Returns the size of the cached sidecars, the blob files on disk are not included.
+    fn approx_memory_bytes(&self) -> usize {
+        self.inner.blob_cache.data_size()
+    }
+
     fn blobs_len(&self) -> usize {
         self.inner.size_tracker.blobs_len()
     }
@@ -455,7 +635,7 @@ impl BlobStore for DiskFileBlobStore {
 
 struct DiskFileBlobStoreInner {
     blob_dir: PathBuf,
-    blob_cache: Mutex<LruMap<TxHash, Arc<BlobTransactionSidecarVariant>, ByLength>>,
+    blob_cache: BlobCache,
     size_tracker: BlobStoreSize,
     file_lock: RwLock<()>,
     txs_to_delete: RwLock<B256Set>,
@@ -464,20 +644,53 @@ struct DiskFileBlobStoreInner {
     /// Note: It is possible that one blob can appear in multiple transactions but this only tracks
     /// the most recent one.
     versioned_hashes_to_txhash: Mutex<LruMap<B256, B256>>,
+    /// Unix permission bits applied to newly written blob files.
+    file_mode: Option<u32>,
+    /// Maximum size of an encoded sidecar, larger sidecars are rejected.
+    max_blob_file_bytes: Option<usize>,
+    /// Whether [`DiskFileBlobStore::migrate_to_v2`] may rewrite blob files.
+    allow_migration: bool,
+    /// How often a blob file read that failed with a transient error is retried.
+    read_retries: u32,
+    /// Dedicated pool for reading and writing blob files in parallel, if enabled.
+    #[cfg(feature = "io-threads")]
+    io_pool: Option<rayon::ThreadPool>,
+    /// Whether identical sidecars of different transactions share one hardlinked blob file.
+    dedup_hardlinks: bool,
+    /// After how long without inserts or gets the cleanup shrinks the cache, if enabled.
+    shrink_cache_after_idle: Option<Duration>,
+    /// When the store was last accessed, `None` once the cache was shrunk since.
+    ///
+    /// Only tracked if [`Self::shrink_cache_after_idle`] is set.
+    last_access: Mutex<Option<Instant>>,
+    #[cfg(feature = "metrics")]
+    read_metrics: DiskBlobStoreReadMetrics,
 }
 
 impl DiskFileBlobStoreInner {
-    /// Creates a new empty disk file blob store with the given maximum length of the blob cache.
-    fn new(blob_dir: PathBuf, max_length: u32) -> Self {
+    /// Creates a new empty disk file blob store with the given maximum length of the blob cache,
+    /// split into `cache_shards` shards.
+    fn new(blob_dir: PathBuf, max_length: u32, cache_shards: usize) -> Self {
         Self {
             blob_dir,
-            blob_cache: Mutex::new(LruMap::new(ByLength::new(max_length))),
+            blob_cache: BlobCache::new(max_length, cache_shards),
             size_tracker: Default::default(),
             file_lock: Default::default(),
             txs_to_delete: Default::default(),
             versioned_hashes_to_txhash: Mutex::new(LruMap::new(ByLength::new(
                 VERSIONED_HASH_TO_TX_HASH_CACHE_SIZE as u32,
             ))),
+            file_mode: None,
+            max_blob_file_bytes: None,
+            allow_migration: false,
+            read_retries: 0,
+            #[cfg(feature = "io-threads")]
+            io_pool: None,
+            dedup_hardlinks: false,
+            shrink_cache_after_idle: None,
+            last_access: Mutex::new(Some(Instant::now())),
+            #[cfg(feature = "metrics")]
+            read_metrics: Default::default(),
         }
     }
 
@@ -488,6 +701,301 @@ impl DiskFileBlobStoreInner {
             .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))
     }
 
+    /// Verifies all blob files, see [`DiskFileBlobStore::verify`].
+    fn verify(&self) -> Result<VerifyReport, DiskFileBlobStoreError> {
+        let entries = fs::read_dir(&self.blob_dir)
+            .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+
+        let mut report = VerifyReport::default();
+        for entry in entries {
+            let entry =
+                entry.map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+            let Some(tx) = entry.file_name().to_str().and_then(|name| name.parse::<B256>().ok())
+            else {
+                debug!(target:"txpool::blob", path = ?entry.path(), "Skipping unknown blob file");
+                continue
+            };
+
+            let data = {
+                let _lock = self.file_lock.read();
+                fs::read(entry.path())
+            };
+            let data = match data {
+                Ok(data) => data,
+                // deleted since the directory was listed
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, ?tx, "Failed to read blob file");
+                    report.unreadable.push(tx);
+                    continue
+                }
+            };
+
+            let valid = BlobTransactionSidecarVariant::rlp_decode_fields(&mut data.as_slice())
+                .is_ok_and(|sidecar| {
+                    let versioned_hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+                    sidecar.validate(&versioned_hashes, EnvKzgSettings::Default.get()).is_ok()
+                });
+            if valid {
+                report.ok += 1;
+            } else {
+                report.corrupt.push(tx);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Converts all EIP-4844 blob files, see [`DiskFileBlobStore::migrate_to_v2`].
+    fn migrate_to_v2(
+        &self,
+        convert: impl Fn(&BlobTransactionSidecar) -> BlobTransactionSidecarVariant,
+    ) -> Result<MigrationReport, DiskFileBlobStoreError> {
+        if !self.allow_migration {
+            return Err(DiskFileBlobStoreError::MigrationDisabled)
+        }
+
+        let entries = fs::read_dir(&self.blob_dir)
+            .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+
+        let mut report = MigrationReport::default();
+        for entry in entries {
+            let entry =
+                entry.map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+            let Some(tx) = entry.file_name().to_str().and_then(|name| name.parse::<B256>().ok())
+            else {
+                continue
+            };
+            let path = entry.path();
+
+            // hold the write lock for the whole rewrite so the file can't be deleted or replaced
+            // concurrently
+            let _lock = self.file_lock.write();
+            let data = match fs::read(&path) {
+                Ok(data) => data,
+                // deleted since the directory was listed
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, ?tx, "Failed to read blob file for migration");
+                    report.failed.push(tx);
+                    continue
+                }
+            };
+            let sidecar = match BlobTransactionSidecarVariant::rlp_decode_fields(
+                &mut data.as_slice(),
+            ) {
+                Ok(BlobTransactionSidecarVariant::Eip4844(sidecar)) => sidecar,
+                Ok(BlobTransactionSidecarVariant::Eip7594(_)) => {
+                    report.skipped += 1;
+                    continue
+                }
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, ?tx, "Failed to decode blob file for migration");
+                    report.failed.push(tx);
+                    continue
+                }
+            };
+
+            let migrated = convert(&sidecar);
+            if !migrated.is_eip7594() {
+                debug!(target:"txpool::blob", ?tx, "Conversion did not produce an EIP-7594 sidecar");
+                report.failed.push(tx);
+                continue
+            }
+
+            let mut buf = Vec::with_capacity(migrated.rlp_encoded_fields_length());
+            migrated.rlp_encode_fields(&mut buf);
+
+            // write to a temporary file first so a crash never leaves a partially written blob
+            let tmp_path = path.with_extension("migrating");
+            if let Err(err) =
+                self.write_blob_file(&tmp_path, &buf).and_then(|_| fs::rename(&tmp_path, &path))
+            {
+                debug!(target:"txpool::blob", %err, ?tx, "Failed to write migrated blob file");
+                let _ = fs::remove_file(&tmp_path);
+                report.failed.push(tx);
+                continue
+            }
+
+            self.size_tracker.sub_size(data.len());
+            self.size_tracker.add_size(buf.len());
+            if self.blob_cache.get(&tx).is_some() {
+                self.blob_cache.insert(tx, Arc::new(migrated));
+            }
+            report.migrated += 1;
+        }
+        Ok(report)
+    }
+
+    /// Deletes the blob files of all pending deletions.
+    ///
+    /// If `dry_run` is set, only reports what would be deleted and keeps the files and pending
+    /// deletions, see [`DiskFileBlobStore::cleanup_dry_run`].
+    fn cleanup(&self, dry_run: bool) -> BlobStoreCleanupStat {
+        let txs_to_delete = self.txs_to_delete.read().clone();
+        let mut stat = BlobStoreCleanupStat::default();
+        debug!(target:"txpool::blob", num_blobs=%txs_to_delete.len(), dry_run, "Removing blobs from disk");
+        for tx in txs_to_delete {
+            // Inserts remove the tx from the pending deletions while holding the file lock, so a
+            // blob that was re-inserted in the meantime is skipped here.
+            let _lock = self.file_lock.write();
+            let pending = if dry_run {
+                self.txs_to_delete.read().contains(&tx)
+            } else {
+                self.txs_to_delete.write().remove(&tx)
+            };
+            if !pending {
+                continue
+            }
+
+            // a blob file shared with other transactions keeps its bytes until the last link is
+            // removed
+            let path = self.blob_disk_file(tx);
+            let res = if dry_run {
+                fs::metadata(&path).map(|meta| unshared_len(&meta))
+            } else {
+                let filesize = fs::metadata(&path).map_or(0, |meta| unshared_len(&meta));
+                fs::remove_file(&path).map(|_| filesize)
+            };
+            match res {
+                Ok(filesize) => {
+                    stat.delete_succeed += 1;
+                    stat.reclaimed_bytes += filesize;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    // Already deleted by a concurrent cleanup task
+                    stat.delete_succeed += 1;
+                }
+                Err(e) => {
+                    stat.delete_failed += 1;
+                    let err = DiskFileBlobStoreError::DeleteFile(tx, path, e);
+                    debug!(target:"txpool::blob", %err);
+                }
+            };
+        }
+        if !dry_run {
+            self.size_tracker.sub_size(stat.reclaimed_bytes as usize);
+            self.size_tracker.sub_len(stat.delete_succeed);
+            self.shrink_cache_if_idle();
+        }
+        stat
+    }
+
+    /// Records an insert or get for [`DiskFileBlobStoreConfig::shrink_cache_after_idle`].
+    fn record_access(&self) {
+        if self.shrink_cache_after_idle.is_some() {
+            *self.last_access.lock() = Some(Instant::now());
+        }
+    }
+
+    /// Shrinks the cache once if the store wasn't accessed for the configured idle period.
+    ///
+    /// The access lock is held while shrinking, so an insert that starts meanwhile waits for it.
+    fn shrink_cache_if_idle(&self) {
+        let Some(idle) = self.shrink_cache_after_idle else { return };
+        let mut last_access = self.last_access.lock();
+        if last_access.is_some_and(|at| at.elapsed() >= idle) {
+            debug!(target:"txpool::blob", ?idle, "Shrinking idle blob cache");
+            self.shrink_cache();
+            *last_access = None;
+        }
+    }
+
+    /// Shrinks the blob cache and the versioned hash index, see
+    /// [`DiskFileBlobStore::shrink_cache`].
+    fn shrink_cache(&self) {
+        self.blob_cache.shrink(BLOB_CACHE_SHRINK_FLOOR);
+
+        // the blob files are checked without any lock held, so inserts and reads aren't blocked
+        // while every indexed file is stat'ed
+        let indexed =
+            self.versioned_hashes_to_txhash.lock().iter().map(|(_, tx)| *tx).collect::<B256Set>();
+        let missing = indexed
+            .into_iter()
+            .filter(|tx| !self.blob_disk_file(*tx).exists())
+            .collect::<B256Set>();
+
+        let txs_to_delete = self.txs_to_delete.read();
+        shrink_lru_map(&mut self.versioned_hashes_to_txhash.lock(), usize::MAX, |_, tx| {
+            !txs_to_delete.contains(tx) && !missing.contains(tx)
+        });
+    }
+
+    /// Compacts the blob directory, see [`DiskFileBlobStore::compact`].
+    fn compact(&self) -> CompactReport {
+        let cleanup = self.cleanup(false);
+        let mut report = CompactReport {
+            reclaimed_bytes: cleanup.reclaimed_bytes,
+            cleanup,
+            ..Default::default()
+        };
+
+        let entries = match fs::read_dir(&self.blob_dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                debug!(target:"txpool::blob", %err, blob_dir = ?self.blob_dir, "Failed to list blob directory");
+                return report
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !entry.file_type().is_ok_and(|ty| ty.is_file()) {
+                continue
+            }
+            let Some(tx) = entry.file_name().to_str().and_then(|name| name.parse::<B256>().ok())
+            else {
+                // leftover temporary file, e.g. from an interrupted migration. Rewrites only create
+                // temporary files while holding the write lock, so this can't race with one.
+                let _lock = self.file_lock.write();
+                let filesize = entry.metadata().map_or(0, |meta| meta.len());
+                if fs::remove_file(&path).is_ok() {
+                    report.stray_removed += 1;
+                    report.reclaimed_bytes += filesize;
+                }
+                continue
+            };
+
+            let _lock = self.file_lock.write();
+            let data = match fs::read(&path) {
+                Ok(data) => data,
+                // deleted since the directory was listed
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, ?tx, "Failed to read blob file for compaction");
+                    continue
+                }
+            };
+
+            if BlobTransactionSidecarVariant::rlp_decode_fields(&mut data.as_slice()).is_err() {
+                debug!(target:"txpool::blob", ?tx, "Removing corrupt blob file");
+                if fs::remove_file(&path).is_ok() {
+                    self.blob_cache.remove(&tx);
+                    self.size_tracker.sub_size(data.len());
+                    self.size_tracker.sub_len(1);
+                    report.stray_removed += 1;
+                    report.reclaimed_bytes += data.len() as u64;
+                }
+                continue
+            }
+
+            // rewriting a shared blob file would store its bytes once per transaction again
+            if entry.metadata().is_ok_and(|meta| unshared_len(&meta) < meta.len()) {
+                continue
+            }
+
+            // write to a temporary file first so a crash never leaves a partially written blob
+            let tmp_path = path.with_extension("compacting");
+            if let Err(err) =
+                self.write_blob_file(&tmp_path, &data).and_then(|_| fs::rename(&tmp_path, &path))
+            {
+                debug!(target:"txpool::blob", %err, ?tx, "Failed to rewrite blob file");
+                let _ = fs::remove_file(&tmp_path);
+                continue
+            }
+            report.files_rewritten += 1;
+        }
+        report
+    }
+
     /// Deletes the entire blob store.
     fn delete_all(&self) -> Result<(), DiskFileBlobStoreError> {
         match fs::remove_dir_all(&self.blob_dir) {
@@ -506,20 +1014,24 @@ impl DiskFileBlobStoreInner {
         tx: B256,
         data: BlobTransactionSidecarVariant,
     ) -> Result<(), BlobStoreError> {
+        self.ensure_blob_file_size(tx, data.rlp_encoded_fields_length())?;
         let mut buf = Vec::with_capacity(data.rlp_encoded_fields_length());
         data.rlp_encode_fields(&mut buf);
 
-        {
+        let duplicate_of = {
             // cache the versioned hashes to tx hash
             let mut map = self.versioned_hashes_to_txhash.lock();
+            let duplicate_of = self.duplicate_candidate(&mut map, tx, &data);
             data.versioned_hashes().for_each(|hash| {
                 map.insert(hash, tx);
             });
-        }
+            duplicate_of
+        };
 
-        self.blob_cache.lock().insert(tx, Arc::new(data));
+        // write the file before caching, so the cached sidecar can always be evicted safely
+        let size = self.write_one_encoded(tx, &buf, duplicate_of)?;
 
-        let size = self.write_one_encoded(tx, &buf)?;
+        self.blob_cache.insert(tx, Arc::new(data));
 
         self.size_tracker.add_size(size);
         self.size_tracker.inc_len(1);
@@ -531,72 +1043,237 @@ impl DiskFileBlobStoreInner {
         &self,
         txs: Vec<(B256, BlobTransactionSidecarVariant)>,
     ) -> Result<(), BlobStoreError> {
-        let raw = txs
-            .iter()
+        // reject the whole batch before anything is encoded
+        for (tx, data) in &txs {
+            self.ensure_blob_file_size(*tx, data.rlp_encoded_fields_length())?;
+        }
+
+        let entries = txs
+            .into_iter()
             .map(|(tx, data)| {
                 let mut buf = Vec::with_capacity(data.rlp_encoded_fields_length());
                 data.rlp_encode_fields(&mut buf);
-                (self.blob_disk_file(*tx), buf)
+                (tx, Arc::new(data), buf)
             })
-            .collect::<Vec<_>>();
+            .collect();
+        self.insert_many_encoded(entries)
+    }
 
-        {
-            // cache versioned hashes to tx hash
-            let mut map = self.versioned_hashes_to_txhash.lock();
-            for (tx, data) in &txs {
-                data.versioned_hashes().for_each(|hash| {
-                    map.insert(hash, *tx);
-                });
-            }
+    /// Ensures blobs are in the blob cache and their already encoded blob files are written to
+    /// the disk.
+    fn insert_many_encoded<T: AsRef<[u8]> + Sync>(
+        &self,
+        entries: Vec<(B256, Arc<BlobTransactionSidecarVariant>, T)>,
+    ) -> Result<(), BlobStoreError> {
+        // reject the whole batch before anything is written
+        for (tx, _, encoded) in &entries {
+            self.ensure_blob_file_size(*tx, encoded.as_ref().len())?;
         }
 
-        {
-            // cache blobs
-            let mut cache = self.blob_cache.lock();
-            for (tx, data) in txs {
-                cache.insert(tx, Arc::new(data));
-            }
-        }
+        let duplicates_of = {
+            // cache versioned hashes to tx hash
+            let mut map = self.versioned_hashes_to_txhash.lock();
+            entries
+                .iter()
+                .map(|(tx, data, _)| {
+                    let duplicate_of = self.duplicate_candidate(&mut map, *tx, data);
+                    data.versioned_hashes().for_each(|hash| {
+                        map.insert(hash, *tx);
+                    });
+                    duplicate_of
+                })
+                .collect::<Vec<_>>()
+        };
 
-        let mut add = 0;
-        let mut num = 0;
-        {
+        let results = {
             let _lock = self.file_lock.write();
-            for (path, data) in raw {
-                if path.exists() {
-                    debug!(target:"txpool::blob", ?path, "Blob already exists");
-                } else if let Err(err) = fs::write(&path, &data) {
-                    debug!(target:"txpool::blob", %err, ?path, "Failed to write blob file");
-                } else {
-                    add += data.len();
-                    num += 1;
+            let mut txs_to_delete = self.txs_to_delete.write();
+            for (tx, _, _) in &entries {
+                // the blob is wanted again, it must survive a pending cleanup
+                txs_to_delete.remove(tx);
+            }
+            let write = |((tx, _, data), duplicate_of): (&(B256, _, T), &Option<B256>)| {
+                let path = self.blob_disk_file(*tx);
+                if let Some(duplicate_of) = duplicate_of &&
+                    !path.exists() &&
+                    self.link_identical_blob_file(*duplicate_of, &path, data.as_ref())
+                {
+                    return Ok(Some(0))
+                }
+                self.write_new_blob_file(*tx, &path, data.as_ref())
+            };
+            self.map_io(entries.iter().zip(&duplicates_of).collect(), write)
+        };
+        let written = results.iter().filter_map(|res| res.as_ref().ok().copied().flatten());
+        self.size_tracker.add_size(written.clone().sum());
+        self.size_tracker.inc_len(written.count());
+
+        // only cache blobs that are on disk, so evicting them never loses data
+        let mut first_err = None;
+        for ((tx, data, _), res) in entries.into_iter().zip(results) {
+            match res {
+                Ok(_) => self.blob_cache.insert(tx, data),
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, "Failed to write blob file");
+                    first_err.get_or_insert(err);
                 }
             }
         }
-        self.size_tracker.add_size(add);
-        self.size_tracker.inc_len(num);
-
-        Ok(())
-    }
 
-    /// Returns true if the blob for the given transaction hash is in the blob cache or on disk.
-    fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
-        if self.blob_cache.lock().get(&tx).is_some() {
-            return Ok(true)
+        match first_err {
+            Some(err) => Err(err.into()),
+            None => Ok(()),
         }
-        // we only check if the file exists and assume it's valid
-        Ok(self.blob_disk_file(tx).is_file())
     }
 
-    /// Returns all the blob transactions which are in the cache or on the disk.
-    fn retain_existing(&self, txs: Vec<B256>) -> Result<Vec<B256>, BlobStoreError> {
-        let (in_cache, not_in_cache): (Vec<B256>, Vec<B256>) = {
-            let mut cache = self.blob_cache.lock();
-            txs.into_iter().partition(|tx| cache.get(tx).is_some())
-        };
+    /// Moves the blob file of the transaction into the `other` store, see
+    /// [`DiskFileBlobStore::drain_into_disk`].
+    fn move_into(&self, other: &Self, tx: B256) -> Result<(), BlobStoreError> {
+        self.move_into_with(other, tx, |src, dst| fs::rename(src, dst))
+    }
 
-        let mut existing = in_cache;
-        for tx in not_in_cache {
+    /// Like [`Self::move_into`], but moves the blob file with `rename`, falling back to copying it
+    /// if that fails.
+    fn move_into_with(
+        &self,
+        other: &Self,
+        tx: B256,
+        rename: impl FnOnce(&Path, &Path) -> io::Result<()>,
+    ) -> Result<(), BlobStoreError> {
+        // the decoded sidecar is needed to index its versioned hashes in the other store
+        let Some(sidecar) = self.get_one(tx)? else { return Ok(()) };
+
+        let src = self.blob_disk_file(tx);
+        let dst = other.blob_disk_file(tx);
+        let (size, already_stored) = {
+            // lock both stores in a consistent order, so concurrent moves in opposite directions
+            // can't deadlock
+            let (_first, _second) = if core::ptr::from_ref(self) < core::ptr::from_ref(other) {
+                (self.file_lock.write(), other.file_lock.write())
+            } else {
+                (other.file_lock.write(), self.file_lock.write())
+            };
+            if self.txs_to_delete.read().contains(&tx) {
+                // the blob file is removed by the next cleanup
+                return Ok(())
+            }
+            // the blob is wanted in the other store, it must survive a pending cleanup there
+            other.txs_to_delete.write().remove(&tx);
+
+            let size = fs::metadata(&src)
+                .map_err(|err| DiskFileBlobStoreError::ReadFile(tx, src.clone(), err))?
+                .len() as usize;
+            let already_stored = dst.exists();
+            if already_stored {
+                fs::remove_file(&src)
+                    .map_err(|err| DiskFileBlobStoreError::DeleteFile(tx, src.clone(), err))?;
+            } else if let Err(err) = rename(&src, &dst) {
+                debug!(target:"txpool::blob", %err, ?src, ?dst, "Failed to rename blob file, copying it");
+                // both file locks are already held, so the file is read without taking them again
+                let data = fs::read(&src)
+                    .map_err(|err| DiskFileBlobStoreError::ReadFile(tx, src.clone(), err))?;
+                other
+                    .write_blob_file(&dst, &data)
+                    .map_err(|err| DiskFileBlobStoreError::WriteFile(tx, dst.clone(), err))?;
+                fs::remove_file(&src)
+                    .map_err(|err| DiskFileBlobStoreError::DeleteFile(tx, src.clone(), err))?;
+            }
+            (size, already_stored)
+        };
+
+        {
+            let mut map = other.versioned_hashes_to_txhash.lock();
+            sidecar.versioned_hashes().for_each(|hash| {
+                map.insert(hash, tx);
+            });
+        }
+        self.size_tracker.sub_size(size);
+        self.size_tracker.sub_len(1);
+        self.blob_cache.remove(&tx);
+        if !already_stored {
+            other.size_tracker.add_size(size);
+            other.size_tracker.inc_len(1);
+        }
+        other.blob_cache.insert(tx, sidecar);
+        Ok(())
+    }
+
+    /// Returns the transaction that stored the same versioned hashes as `data` most recently, if
+    /// hardlink deduplication is enabled.
+    ///
+    /// This is only a candidate, [`Self::link_identical_blob_file`] compares the file contents.
+    fn duplicate_candidate(
+        &self,
+        versioned_hashes_to_txhash: &mut LruMap<B256, B256>,
+        tx: B256,
+        data: &BlobTransactionSidecarVariant,
+    ) -> Option<B256> {
+        if !self.dedup_hardlinks {
+            return None
+        }
+        let first = data.versioned_hashes().next()?;
+        versioned_hashes_to_txhash.get(&first).copied().filter(|candidate| *candidate != tx)
+    }
+
+    /// Hardlinks `path` to the blob file of `existing` if that file holds exactly `data`,
+    /// returning whether the link was created.
+    ///
+    /// The caller is expected to hold the file lock.
+    fn link_identical_blob_file(&self, existing: B256, path: &Path, data: &[u8]) -> bool {
+        let existing_path = self.blob_disk_file(existing);
+        let identical = fs::metadata(&existing_path)
+            .is_ok_and(|meta| meta.len() == data.len() as u64) &&
+            fs::read(&existing_path).is_ok_and(|existing| existing == data);
+        if !identical {
+            return false
+        }
+        match fs::hard_link(&existing_path, path) {
+            Ok(()) => {
+                trace!(target:"txpool::blob", ?existing, ?path, "Linked identical blob file");
+                true
+            }
+            Err(err) => {
+                debug!(target:"txpool::blob", %err, ?existing, ?path, "Failed to link blob file");
+                false
+            }
+        }
+    }
+
+    /// Writes the blob file unless it already exists, returning the number of bytes written or
+    /// `None` if the file already exists.
+    ///
+    /// The caller is expected to hold the file lock.
+    fn write_new_blob_file(
+        &self,
+        tx: B256,
+        path: &Path,
+        data: &[u8],
+    ) -> Result<Option<usize>, DiskFileBlobStoreError> {
+        if path.exists() {
+            debug!(target:"txpool::blob", ?path, "Blob already exists");
+            return Ok(None)
+        }
+        self.write_blob_file(path, data)
+            .map_err(|err| DiskFileBlobStoreError::WriteFile(tx, path.to_path_buf(), err))?;
+        Ok(Some(data.len()))
+    }
+
+    /// Returns true if the blob for the given transaction hash is in the blob cache or on disk.
+    fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
+        if self.blob_cache.get(&tx).is_some() {
+            return Ok(true)
+        }
+        // we only check if the file exists and assume it's valid
+        Ok(self.blob_disk_file(tx).is_file())
+    }
+
+    /// Returns all the blob transactions which are in the cache or on the disk.
+    fn retain_existing(&self, txs: Vec<B256>) -> Result<Vec<B256>, BlobStoreError> {
+        let (in_cache, not_in_cache): (Vec<B256>, Vec<B256>) =
+            txs.into_iter().partition(|tx| self.blob_cache.get(tx).is_some());
+
+        let mut existing = in_cache;
+        for tx in not_in_cache {
             if self.blob_disk_file(tx).is_file() {
                 existing.push(tx);
             }
@@ -610,13 +1287,17 @@ impl DiskFileBlobStoreInner {
         &self,
         tx: B256,
     ) -> Result<Option<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
-        if let Some(blob) = self.blob_cache.lock().get(&tx) {
-            return Ok(Some(blob.clone()))
+        if let Some(blob) = self.blob_cache.get(&tx) {
+            #[cfg(feature = "metrics")]
+            self.read_metrics.cache_hits.increment(1);
+            return Ok(Some(blob))
         }
+        #[cfg(feature = "metrics")]
+        self.read_metrics.cache_misses.increment(1);
 
         if let Some(blob) = self.read_one(tx)? {
             let blob_arc = Arc::new(blob);
-            self.blob_cache.lock().insert(tx, blob_arc.clone());
+            self.blob_cache.insert(tx, blob_arc.clone());
             return Ok(Some(blob_arc))
         }
 
@@ -633,18 +1314,19 @@ impl DiskFileBlobStoreInner {
     #[inline]
     fn read_one(&self, tx: B256) -> Result<Option<BlobTransactionSidecarVariant>, BlobStoreError> {
         let path = self.blob_disk_file(tx);
-        let data = {
-            let _lock = self.file_lock.read();
-            match fs::read(&path) {
-                Ok(data) => data,
-                Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
-                Err(e) => {
-                    return Err(BlobStoreError::Other(Box::new(DiskFileBlobStoreError::ReadFile(
-                        tx, path, e,
-                    ))))
-                }
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+        let data = match self.read_blob_file(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(BlobStoreError::Other(Box::new(DiskFileBlobStoreError::ReadFile(
+                    tx, path, e,
+                ))))
             }
         };
+        #[cfg(feature = "metrics")]
+        self.record_read(start, data.len());
         BlobTransactionSidecarVariant::rlp_decode_fields(&mut data.as_slice())
             .map(Some)
             .map_err(BlobStoreError::DecodeError)
@@ -669,32 +1351,92 @@ impl DiskFileBlobStoreInner {
     /// Only returns the blobs that were found in file.
     #[inline]
     fn read_many_raw(&self, txs: Vec<TxHash>) -> Vec<(TxHash, Vec<u8>)> {
-        let mut res = Vec::with_capacity(txs.len());
-        let _lock = self.file_lock.read();
-        for tx in txs {
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+        let read = |tx: TxHash| {
             let path = self.blob_disk_file(tx);
-            match fs::read(&path) {
-                Ok(data) => {
-                    res.push((tx, data));
-                }
+            match self.read_blob_file(&path) {
+                Ok(data) => Some((tx, data)),
                 Err(err) => {
                     debug!(target:"txpool::blob", %err, ?tx, "Failed to read blob file");
+                    None
                 }
+            }
+        };
+        let res: Vec<_> = self.map_io(txs, read).into_iter().flatten().collect();
+        #[cfg(feature = "metrics")]
+        self.record_read(start, res.iter().map(|(_, data)| data.len()).sum());
+        res
+    }
+
+    /// Applies the blob file operation `f` to all `items`, in parallel on the IO pool if there is
+    /// one and more than one item.
+    fn map_io<T: Send, R: Send>(&self, items: Vec<T>, f: impl Fn(T) -> R + Sync) -> Vec<R> {
+        #[cfg(feature = "io-threads")]
+        if let Some(pool) = &self.io_pool &&
+            items.len() > 1
+        {
+            use rayon::iter::{IntoParallelIterator, ParallelIterator};
+            return pool.install(|| items.into_par_iter().map(f).collect())
+        }
+        items.into_iter().map(f).collect()
+    }
+
+    /// Reads a blob file, retrying up to `read_retries` times with exponential backoff if the read
+    /// fails with a transient error.
+    ///
+    /// The file lock is only held for the duration of each read attempt and released during the
+    /// backoff, so retries don't block writers.
+    fn read_blob_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let mut backoff = READ_RETRY_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            let res = {
+                let _lock = self.file_lock.read();
+                fs::read(path)
             };
+            match res {
+                Err(err) if attempt < self.read_retries && is_transient_read_error(&err) => {
+                    debug!(target:"txpool::blob", %err, ?path, attempt, "Retrying blob file read");
+                    std::thread::sleep(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                res => return res,
+            }
         }
-        res
+    }
+
+    /// Records the duration and size of a disk read that started at `start`.
+    #[cfg(feature = "metrics")]
+    fn record_read(&self, start: Instant, bytes: usize) {
+        self.read_metrics.read_duration_seconds.record(start.elapsed());
+        self.read_metrics.read_bytes.record(bytes as f64);
     }
 
     /// Writes the blob data for the given transaction hash to the disk.
+    ///
+    /// If `duplicate_of` holds a transaction with an identical blob file, the file is hardlinked
+    /// instead and no bytes are added.
     #[inline]
-    fn write_one_encoded(&self, tx: B256, data: &[u8]) -> Result<usize, DiskFileBlobStoreError> {
+    fn write_one_encoded(
+        &self,
+        tx: B256,
+        data: &[u8],
+        duplicate_of: Option<B256>,
+    ) -> Result<usize, DiskFileBlobStoreError> {
         trace!(target:"txpool::blob", "[{:?}] writing blob file", tx);
         let mut add = 0;
         let path = self.blob_disk_file(tx);
         {
             let _lock = self.file_lock.write();
-            if !path.exists() {
-                fs::write(&path, data)
+            // the blob is wanted again, it must survive a pending cleanup
+            self.txs_to_delete.write().remove(&tx);
+            if !path.exists() &&
+                !duplicate_of
+                    .is_some_and(|existing| self.link_identical_blob_file(existing, &path, data))
+            {
+                self.write_blob_file(&path, data)
                     .map_err(|e| DiskFileBlobStoreError::WriteFile(tx, path, e))?;
                 add = data.len();
             }
@@ -702,6 +1444,27 @@ impl DiskFileBlobStoreInner {
         Ok(add)
     }
 
+    /// Returns an error if an encoded sidecar of the given size exceeds the configured maximum
+    /// blob file size.
+    fn ensure_blob_file_size(&self, tx: B256, size: usize) -> Result<(), DiskFileBlobStoreError> {
+        match self.max_blob_file_bytes {
+            Some(max) if size > max => Err(DiskFileBlobStoreError::BlobTooLarge(tx, size, max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Creates the blob file at the given path, applying the configured file mode on unix.
+    fn write_blob_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        if let Some(mode) = self.file_mode {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(mode);
+        }
+        io::Write::write_all(&mut options.open(path)?, data)
+    }
+
     /// Retrieves blobs for the given transaction hashes from the blob cache or disk.
     ///
     /// This will not return an error if there are missing blobs. Therefore, the result may be a
@@ -713,16 +1476,18 @@ impl DiskFileBlobStoreInner {
     ) -> Result<Vec<(B256, Arc<BlobTransactionSidecarVariant>)>, BlobStoreError> {
         let mut res = Vec::with_capacity(txs.len());
         let mut cache_miss = Vec::new();
-        {
-            let mut cache = self.blob_cache.lock();
-            for tx in txs {
-                if let Some(blob) = cache.get(&tx) {
-                    res.push((tx, blob.clone()));
-                } else {
-                    cache_miss.push(tx)
-                }
+        for tx in txs {
+            if let Some(blob) = self.blob_cache.get(&tx) {
+                res.push((tx, blob));
+            } else {
+                cache_miss.push(tx)
             }
         }
+        #[cfg(feature = "metrics")]
+        {
+            self.read_metrics.cache_hits.increment(res.len() as u64);
+            self.read_metrics.cache_misses.increment(cache_miss.len() as u64);
+        }
         if cache_miss.is_empty() {
             return Ok(res)
         }
@@ -739,14 +1504,56 @@ impl DiskFileBlobStoreInner {
             })
             .collect::<Vec<_>>();
 
-        let mut cache = self.blob_cache.lock();
         for (tx, data) in from_disk {
-            cache.insert(tx, data);
+            self.blob_cache.insert(tx, data);
         }
 
         Ok(res)
     }
 
+    /// Retrieves blobs for the given transaction hashes from the blob cache or disk, preserving the
+    /// request order.
+    ///
+    /// Cache misses are read from disk in a single batch. Missing sidecars are returned as `None`.
+    fn get_for_block(&self, txs: &[B256]) -> Vec<Option<Arc<BlobTransactionSidecarVariant>>> {
+        let mut res = Vec::with_capacity(txs.len());
+        let mut cache_miss = Vec::new();
+        for tx in txs {
+            let cached = self.blob_cache.get(tx);
+            if cached.is_none() {
+                cache_miss.push(*tx);
+            }
+            res.push(cached);
+        }
+        #[cfg(feature = "metrics")]
+        {
+            self.read_metrics.cache_hits.increment((txs.len() - cache_miss.len()) as u64);
+            self.read_metrics.cache_misses.increment(cache_miss.len() as u64);
+        }
+        if cache_miss.is_empty() {
+            return res
+        }
+
+        let from_disk = self
+            .read_many_decoded(cache_miss)
+            .into_iter()
+            .map(|(tx, data)| (tx, Arc::new(data)))
+            .collect::<B256Map<_>>();
+        if from_disk.is_empty() {
+            return res
+        }
+        for (tx, slot) in txs.iter().zip(res.iter_mut()) {
+            if slot.is_none() {
+                *slot = from_disk.get(tx).cloned();
+            }
+        }
+        for (tx, data) in from_disk {
+            self.blob_cache.insert(tx, data);
+        }
+
+        res
+    }
+
     /// Retrieves blobs for the given transaction hashes from the blob cache or disk.
     ///
     /// Returns an error if there are any missing blobs.
@@ -765,12 +1572,82 @@ impl fmt::Debug for DiskFileBlobStoreInner {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("DiskFileBlobStoreInner")
             .field("blob_dir", &self.blob_dir)
-            .field("cached_blobs", &self.blob_cache.try_lock().map(|lock| lock.len()))
+            .field("cached_blobs", &self.blob_cache.try_len())
             .field("txs_to_delete", &self.txs_to_delete.try_read())
             .finish()
     }
 }
 
+/// Returns the number of bytes removing this blob file frees on disk, which is `0` if the file is
+/// hardlinked to other blob files.
+fn unshared_len(meta: &fs::Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        if meta.nlink() > 1 {
+            return 0
+        }
+    }
+    meta.len()
+}
+
+/// Returns true if a failed read may succeed when retried, e.g. on networked filesystems.
+///
+/// A missing file is never transient.
+fn is_transient_read_error(err: &io::Error) -> bool {
+    // EIO is 5 on all unix platforms
+    const EIO: i32 = 5;
+    matches!(
+        err.kind(),
+        io::ErrorKind::Interrupted |
+            io::ErrorKind::WouldBlock |
+            io::ErrorKind::TimedOut |
+            io::ErrorKind::ResourceBusy
+    ) || (cfg!(unix) && err.raw_os_error() == Some(EIO))
+}
+
+/// Outcome of [`DiskFileBlobStore::verify`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Number of blob files that decoded and matched their commitments.
+    pub ok: usize,
+    /// Transactions whose blob file failed to decode or didn't match its commitments.
+    pub corrupt: Vec<TxHash>,
+    /// Transactions whose blob file couldn't be read.
+    pub unreadable: Vec<TxHash>,
+}
+
+impl VerifyReport {
+    /// Returns true if no corrupt or unreadable blob files were found.
+    pub const fn is_healthy(&self) -> bool {
+        self.corrupt.is_empty() && self.unreadable.is_empty()
+    }
+}
+
+/// Outcome of [`DiskFileBlobStore::migrate_to_v2`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Number of blob files rewritten in the EIP-7594 format.
+    pub migrated: usize,
+    /// Number of blob files that were already in the EIP-7594 format.
+    pub skipped: usize,
+    /// Transactions whose blob file couldn't be read, decoded, converted or rewritten.
+    pub failed: Vec<TxHash>,
+}
+
+/// Outcome of [`DiskFileBlobStore::compact`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactReport {
+    /// Outcome of the cleanup of pending deletions that ran first.
+    pub cleanup: BlobStoreCleanupStat,
+    /// Number of blob files rewritten in place.
+    pub files_rewritten: usize,
+    /// Number of stray temporary or corrupt files that were removed.
+    pub stray_removed: usize,
+    /// Total number of bytes freed on disk, including the cleanup.
+    pub reclaimed_bytes: u64,
+}
+
 /// Errors that can occur when interacting with a disk file blob store.
 #[derive(Debug, thiserror::Error)]
 pub enum DiskFileBlobStoreError {
@@ -790,6 +1667,16 @@ pub enum DiskFileBlobStoreError {
     #[error("[{0}] failed to delete blob file at {1}: {2}")]
     /// Indicates a failure while deleting a blob file.
     DeleteFile(TxHash, PathBuf, io::Error),
+    /// The encoded sidecar exceeds the maximum blob file size.
+    #[error("[{0}] blob file of {1} bytes exceeds the maximum of {2} bytes")]
+    BlobTooLarge(TxHash, usize, usize),
+    /// Migration was requested but not enabled in the [`DiskFileBlobStoreConfig`].
+    #[error("blob file migration is not enabled for this blob store")]
+    MigrationDisabled,
+    /// The dedicated IO thread pool could not be created.
+    #[cfg(feature = "io-threads")]
+    #[error("failed to build blob store IO thread pool: {0}")]
+    IoThreadPool(rayon::ThreadPoolBuildError),
 }
 
 impl From<DiskFileBlobStoreError> for BlobStoreError {
@@ -798,18 +1685,218 @@ impl From<DiskFileBlobStoreError> for BlobStoreError {
     }
 }
 
+/// A single shard of the [`BlobCache`].
+type BlobCacheShard = Mutex<LruMap<TxHash, Arc<BlobTransactionSidecarVariant>, ByLength>>;
+
+/// The in-memory cache of recently used sidecars, split into independently locked shards.
+///
+/// Transactions are assigned to shards by the high bits of their hash, so concurrent lookups of
+/// different transactions rarely contend on the same lock and scans over all cached sidecars only
+/// hold one shard lock at a time. Each shard is its own LRU with an equal share of the capacity.
+///
+/// Lookups by versioned hash scan the shards sequentially: the cache is small, see
+/// [`DEFAULT_MAX_CACHED_BLOBS`], matching a sidecar is a cheap hash comparison and the scan stops
+/// as soon as all blobs are found, so handing the shards to other threads would cost more than
+/// the scan itself.
+struct BlobCache {
+    shards: Box<[BlobCacheShard]>,
+}
+
+impl BlobCache {
+    /// Creates a cache for up to `max_length` sidecars split into `num_shards` shards.
+    ///
+    /// The number of shards is clamped so that every shard can hold at least one sidecar.
+    fn new(max_length: u32, num_shards: usize) -> Self {
+        let num_shards = num_shards.clamp(1, max_length.max(1) as usize);
+        let shard_length = max_length.div_ceil(num_shards as u32);
+        Self {
+            shards: (0..num_shards)
+                .map(|_| Mutex::new(LruMap::new(ByLength::new(shard_length))))
+                .collect(),
+        }
+    }
+
+    /// Returns the shard the transaction belongs to.
+    fn shard(&self, tx: &TxHash) -> &BlobCacheShard {
+        let high_bits = u64::from_be_bytes(tx[..8].try_into().expect("hash has 32 bytes"));
+        let idx = (high_bits as u128 * self.shards.len() as u128) >> 64;
+        &self.shards[idx as usize]
+    }
+
+    /// Returns all shards of the cache.
+    fn shards(&self) -> impl Iterator<Item = &BlobCacheShard> {
+        self.shards.iter()
+    }
+
+    /// Returns the cached sidecar and marks it as recently used.
+    fn get(&self, tx: &TxHash) -> Option<Arc<BlobTransactionSidecarVariant>> {
+        self.shard(tx).lock().get(tx).cloned()
+    }
+
+    /// Removes the sidecar from the cache.
+    fn remove(&self, tx: &TxHash) {
+        self.shard(tx).lock().remove(tx);
+    }
+
+    /// Caches the sidecar, see [`cache_blob`].
+    fn insert(&self, tx: TxHash, data: Arc<BlobTransactionSidecarVariant>) {
+        cache_blob(&mut self.shard(&tx).lock(), tx, data);
+    }
+
+    /// Returns all cached sidecars.
+    fn sidecars(&self) -> Vec<Arc<BlobTransactionSidecarVariant>> {
+        self.shards()
+            .flat_map(|shard| {
+                shard.lock().iter().map(|(_, sidecar)| Arc::clone(sidecar)).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Returns the number of cached sidecars, or `None` if a shard is currently locked.
+    fn try_len(&self) -> Option<usize> {
+        self.shards().map(|shard| shard.try_lock().map(|shard| shard.len())).sum()
+    }
+
+    /// Returns the total size of the cached sidecars.
+    fn data_size(&self) -> usize {
+        self.shards()
+            .map(|shard| shard.lock().iter().map(|(_, sidecar)| sidecar.size()).sum::<usize>())
+            .sum()
+    }
+
+    /// Shrinks every shard to its `len` most recently used sidecars, see [`shrink_lru_map`].
+    fn shrink(&self, len: usize) {
+        for shard in self.shards() {
+            shrink_lru_map(&mut shard.lock(), len, |_, _| true);
+        }
+    }
+
+    #[cfg(test)]
+    fn clear(&self) {
+        for shard in self.shards() {
+            shard.lock().clear();
+        }
+    }
+}
+
+/// Rebuilds the map with at most `len` of its most recently used entries that match `retain`,
+/// keeping their order.
+///
+/// Unlike removing entries in place, rebuilding also releases the memory the map grew to.
+fn shrink_lru_map<V>(
+    map: &mut LruMap<B256, V, ByLength>,
+    len: usize,
+    mut retain: impl FnMut(&B256, &V) -> bool,
+) {
+    let mut shrunk = LruMap::new(*map.limiter());
+    // drained most recently used first
+    let kept = map.drain().filter(|(key, value)| retain(key, value)).take(len).collect::<Vec<_>>();
+    for (key, value) in kept.into_iter().rev() {
+        shrunk.insert(key, value);
+    }
+    *map = shrunk;
+}
+
+/// Inserts the sidecar into the blob cache, evicting the least recently used sidecar if the cache
+/// is full.
+///
+/// Sidecars are only cached once their blob file was written, so an evicted sidecar can still be
+/// read from disk. Its versioned hashes are intentionally kept in the index for that reason.
+fn cache_blob(
+    cache: &mut LruMap<TxHash, Arc<BlobTransactionSidecarVariant>, ByLength>,
+    tx: TxHash,
+    data: Arc<BlobTransactionSidecarVariant>,
+) {
+    if cache.peek(&tx).is_none() &&
+        cache.limiter().is_over_the_limit(cache.len() + 1) &&
+        let Some((evicted, sidecar)) = cache.pop_oldest()
+    {
+        trace!(
+            target:"txpool::blob",
+            ?evicted,
+            versioned_hashes = sidecar.versioned_hashes().count(),
+            "Evicted blob from cache"
+        );
+    }
+    cache.insert(tx, data);
+}
+
 /// Configuration for a disk file blob store.
 #[derive(Debug, Clone)]
 pub struct DiskFileBlobStoreConfig {
     /// The maximum number of blobs to keep in the in memory blob cache.
     pub max_cached_entries: u32,
+    /// Into how many independently locked shards the in memory blob cache is split.
+    ///
+    /// More shards reduce lock contention between concurrent lookups, but the cache then evicts
+    /// the least recently used sidecar per shard instead of across the whole cache.
+    pub cache_shards: usize,
     /// How to open the blob store.
     pub open: OpenDiskFileBlobStore,
+    /// Unix permission bits for blob files, e.g. `0o600`.
+    ///
+    /// If `None`, files are created with the process umask. Ignored on non-unix platforms.
+    pub file_mode: Option<u32>,
+    /// Maximum size in bytes of an encoded sidecar, larger sidecars are rejected on insert.
+    ///
+    /// If `None`, sidecars of any size are accepted.
+    pub max_blob_file_bytes: Option<usize>,
+    /// Whether [`DiskFileBlobStore::migrate_to_v2`] is allowed to rewrite blob files.
+    ///
+    /// Disabled by default so the one-time migration can't run accidentally.
+    pub allow_migration: bool,
+    /// How often a blob file read that failed with a transient IO error is retried, with a short
+    /// exponential backoff.
+    ///
+    /// Missing files are never retried. Capped at [`MAX_READ_RETRIES`]. Default is `0`.
+    pub read_retries: u32,
+    /// Number of dedicated threads used to read and write the blob files of a batch in parallel,
+    /// e.g. for wide `getBlobs` queries.
+    ///
+    /// With `0` or `1`, blob files are read and written on the calling thread. Default is `0`.
+    #[cfg(feature = "io-threads")]
+    pub io_threads: usize,
+    /// Maximum number of versioned hashes kept in the index that maps them to their
+    /// transaction.
+    ///
+    /// Blobs are only served by versioned hash, e.g. to `engine_getBlobs`, while their versioned
+    /// hash is in this index, so it should cover all blobs that may still be requested. Every
+    /// entry takes roughly 64 bytes. Unlike the blob cache, evicting an entry makes a stored
+    /// sidecar unreachable by versioned hash and lowers the `getBlobs` hit rate.
+    ///
+    /// If `None`, [`VERSIONED_HASH_TO_TX_HASH_CACHE_SIZE`] entries are kept.
+    pub max_versioned_hash_entries: Option<u32>,
+    /// Whether a sidecar that is byte-identical to an already stored one, found by versioned
+    /// hash, is hardlinked to the existing blob file instead of being written again.
+    ///
+    /// Removing one of the transactions only removes its link, the shared bytes are freed with
+    /// the last one. Ignored on non-unix platforms. Disabled by default.
+    pub dedup_hardlinks: bool,
+    /// After how long without inserts or gets the in-memory blob cache is shrunk, see
+    /// [`DiskFileBlobStore::shrink_cache`].
+    ///
+    /// The check runs on [`BlobStore::cleanup`], so the cache is shrunk by the first cleanup after
+    /// the idle period and again after every further period of activity. If `None`, the cache
+    /// keeps its size. Disabled by default.
+    pub shrink_cache_after_idle: Option<Duration>,
 }
 
 impl Default for DiskFileBlobStoreConfig {
     fn default() -> Self {
-        Self { max_cached_entries: DEFAULT_MAX_CACHED_BLOBS, open: Default::default() }
+        Self {
+            max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
+            cache_shards: DEFAULT_BLOB_CACHE_SHARDS,
+            open: Default::default(),
+            file_mode: None,
+            max_blob_file_bytes: None,
+            allow_migration: false,
+            read_retries: 0,
+            #[cfg(feature = "io-threads")]
+            io_threads: 0,
+            max_versioned_hash_entries: None,
+            dedup_hardlinks: false,
+            shrink_cache_after_idle: None,
+        }
     }
 }
 
@@ -819,6 +1906,61 @@ impl DiskFileBlobStoreConfig {
         self.max_cached_entries = max_cached_entries;
         self
     }
+
+    /// Set into how many shards the in memory blob cache is split.
+    pub const fn with_cache_shards(mut self, cache_shards: usize) -> Self {
+        self.cache_shards = cache_shards;
+        self
+    }
+
+    /// Set the unix permission bits for newly written blob files.
+    pub const fn with_file_mode(mut self, file_mode: u32) -> Self {
+        self.file_mode = Some(file_mode);
+        self
+    }
+
+    /// Set the maximum size in bytes of an encoded sidecar.
+    pub const fn with_max_blob_file_bytes(mut self, max_blob_file_bytes: usize) -> Self {
+        self.max_blob_file_bytes = Some(max_blob_file_bytes);
+        self
+    }
+
+    /// Set how often a blob file read that failed with a transient error is retried.
+    pub const fn with_read_retries(mut self, read_retries: u32) -> Self {
+        self.read_retries = read_retries;
+        self
+    }
+
+    /// Set the number of dedicated threads used for parallel blob file IO.
+    #[cfg(feature = "io-threads")]
+    pub const fn with_io_threads(mut self, io_threads: usize) -> Self {
+        self.io_threads = io_threads;
+        self
+    }
+
+    /// Set the maximum number of entries in the versioned hash index.
+    pub const fn with_max_versioned_hash_entries(mut self, max_entries: u32) -> Self {
+        self.max_versioned_hash_entries = Some(max_entries);
+        self
+    }
+
+    /// Set whether identical sidecars share a hardlinked blob file.
+    pub const fn with_dedup_hardlinks(mut self, dedup_hardlinks: bool) -> Self {
+        self.dedup_hardlinks = dedup_hardlinks;
+        self
+    }
+
+    /// Set after how long without inserts or gets the blob cache is shrunk.
+    pub const fn with_shrink_cache_after_idle(mut self, idle: Duration) -> Self {
+        self.shrink_cache_after_idle = Some(idle);
+        self
+    }
+
+    /// Allow [`DiskFileBlobStore::migrate_to_v2`] to rewrite blob files.
+    pub const fn with_allow_migration(mut self, allow_migration: bool) -> Self {
+        self.allow_migration = allow_migration;
+        self
+    }
 }
 
 /// How to open a disk file blob store.
@@ -842,6 +1984,7 @@ mod tests {
     };
 
     use super::*;
+    use crate::blobstore::InMemoryBlobStore;
     use std::sync::atomic::Ordering;
 
     fn tmp_store() -> (DiskFileBlobStore, tempfile::TempDir) {
@@ -997,6 +2140,290 @@ mod tests {
         store.cleanup();
     }
 
+    #[test]
+    #[cfg(feature = "io-threads")]
+    fn disk_parallel_io() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_io_threads(4),
+        )
+        .unwrap();
+
+        let blobs = rng_blobs(8);
+        let txs = blobs.iter().map(|(tx, _)| *tx).collect::<Vec<_>>();
+        store.insert_all(blobs.clone()).unwrap();
+        assert_eq!(store.blobs_len(), blobs.len());
+
+        store.clear_cache();
+        let retrieved = store.get_for_block(&txs).unwrap();
+        for (retrieved, (_, blob)) in retrieved.into_iter().zip(&blobs) {
+            assert_eq!(retrieved.as_deref(), Some(blob));
+        }
+    }
+
+    #[test]
+    fn disk_insert_all_encoded() {
+        let (store, _dir) = tmp_store();
+
+        let (sidecar, versioned_hash, _) = eip7594_single_blob_sidecar();
+        let mut blobs = rng_blobs(2);
+        blobs.push((B256::random(), sidecar));
+        let entries = blobs
+            .iter()
+            .map(|(tx, blob)| {
+                let mut buf = Vec::new();
+                blob.rlp_encode_fields(&mut buf);
+                (*tx, Arc::new(blob.clone()), Bytes::from(buf))
+            })
+            .collect::<Vec<_>>();
+        store.insert_all_encoded(entries).unwrap();
+        assert_eq!(store.blobs_len(), blobs.len());
+
+        for (tx, _) in &blobs {
+            assert!(store.is_cached(tx));
+        }
+        assert!(store.contains_versioned_hash(versioned_hash).unwrap());
+
+        // the written bytes decode to the same sidecars
+        store.clear_cache();
+        for (tx, blob) in &blobs {
+            assert_eq!(store.get(*tx).unwrap().as_deref(), Some(blob));
+        }
+    }
+
+    #[test]
+    fn disk_known_versioned_hashes() {
+        let (store, _dir) = tmp_store();
+        assert!(store.known_versioned_hashes().is_empty());
+
+        let (sidecar, versioned_hash, _) = eip7594_single_blob_sidecar();
+        store.insert(B256::random(), sidecar).unwrap();
+        assert_eq!(store.known_versioned_hashes(), vec![versioned_hash]);
+    }
+
+    #[test]
+    fn disk_max_versioned_hash_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_max_versioned_hash_entries(1),
+        )
+        .unwrap();
+
+        let (sidecar, versioned_hash, _) = eip7594_single_blob_sidecar();
+        let tx = B256::random();
+        store.insert(tx, sidecar.clone()).unwrap();
+        assert!(store.contains_versioned_hash(versioned_hash).unwrap());
+
+        // a second blob evicts the first versioned hash, the sidecar itself is still stored
+        let mut other = sidecar;
+        if let BlobTransactionSidecarVariant::Eip7594(sidecar) = &mut other {
+            sidecar.commitments[0] = Bytes48::repeat_byte(0x01);
+        }
+        store.insert(B256::random(), other).unwrap();
+        assert_eq!(store.known_versioned_hashes().len(), 1);
+        assert!(!store.contains_versioned_hash(versioned_hash).unwrap());
+        assert!(store.contains(tx).unwrap());
+    }
+
+    #[test]
+    fn disk_shrink_cache() {
+        let (store, _dir) = tmp_store();
+
+        let blobs = rng_blobs(10);
+        store.insert_all(blobs.clone()).unwrap();
+        let (sidecar, versioned_hash, _) = eip7594_single_blob_sidecar();
+        let deleted = B256::random();
+        store.insert(deleted, sidecar).unwrap();
+        store.delete(deleted).unwrap();
+        store.cleanup();
+        assert_eq!(store.known_versioned_hashes(), vec![versioned_hash]);
+
+        let cached_bytes = store.approx_memory_bytes();
+        assert!(cached_bytes > 0);
+
+        store.shrink_cache();
+        assert_eq!(store.inner.blob_cache.try_len(), Some(BLOB_CACHE_SHRINK_FLOOR));
+        assert!(store.approx_memory_bytes() < cached_bytes);
+        // the deleted blob is gone from disk, so its versioned hash is dropped
+        assert!(store.known_versioned_hashes().is_empty());
+
+        // evicted sidecars are still read from disk
+        for (tx, blob) in blobs {
+            assert_eq!(*store.get(tx).unwrap().unwrap(), blob);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "metrics")]
+    fn disk_read_metrics() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        let (tx, blob) = rng_blobs(1).pop().unwrap();
+        metrics::with_local_recorder(&recorder, || {
+            let (store, _dir) = tmp_store();
+            store.insert(tx, blob.clone()).unwrap();
+
+            // served from the cache
+            assert_eq!(*store.get(tx).unwrap().unwrap(), blob);
+            // read from disk
+            store.inner.blob_cache.remove(&tx);
+            assert_eq!(*store.get(tx).unwrap().unwrap(), blob);
+        });
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let value = |name: &str| {
+            snapshot
+                .iter()
+                .find_map(|(key, _, _, value)| (key.key().name() == name).then_some(value))
+        };
+        assert_eq!(
+            value("transaction_pool.blobstore.disk.cache_hits"),
+            Some(&DebugValue::Counter(1))
+        );
+        assert_eq!(
+            value("transaction_pool.blobstore.disk.cache_misses"),
+            Some(&DebugValue::Counter(1))
+        );
+        let Some(DebugValue::Histogram(read_bytes)) =
+            value("transaction_pool.blobstore.disk.read_bytes")
+        else {
+            panic!("read bytes not recorded")
+        };
+        assert_eq!(read_bytes.len(), 1);
+        assert!(read_bytes[0].0 > 0.0);
+    }
+
+    #[test]
+    fn disk_shrink_cache_after_idle() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_shrink_cache_after_idle(Duration::ZERO),
+        )
+        .unwrap();
+
+        store.insert_all(rng_blobs(10)).unwrap();
+        assert_eq!(store.inner.blob_cache.try_len(), Some(10));
+
+        store.cleanup();
+        assert_eq!(store.inner.blob_cache.try_len(), Some(BLOB_CACHE_SHRINK_FLOOR));
+
+        // without an idle period the cache keeps its size
+        let (store, _dir) = tmp_store();
+        store.insert_all(rng_blobs(10)).unwrap();
+        store.cleanup();
+        assert_eq!(store.inner.blob_cache.try_len(), Some(10));
+    }
+
+    #[test]
+    fn disk_drain_into_disk_moves_blob_files() {
+        let (source, _source_dir) = tmp_store();
+        let (target, _target_dir) = tmp_store();
+
+        let (sidecar, versioned_hash, _) = eip7594_single_blob_sidecar();
+        let tx = B256::random();
+        let kept = rng_blobs(1);
+        source.insert(tx, sidecar.clone()).unwrap();
+        source.insert_all(kept.clone()).unwrap();
+        let size = source.data_size_hint().unwrap();
+        source.clear_cache();
+
+        source.drain_into_disk(&target, vec![tx, B256::random()]).unwrap();
+
+        assert!(!source.contains(tx).unwrap());
+        assert!(source.contains(kept[0].0).unwrap());
+        assert_eq!(source.blobs_len(), 1);
+        assert_eq!(target.blobs_len(), 1);
+        assert_eq!(source.data_size_hint().unwrap() + target.data_size_hint().unwrap(), size);
+        assert!(target.contains_versioned_hash(versioned_hash).unwrap());
+        target.clear_cache();
+        assert_eq!(target.get(tx).unwrap().as_deref(), Some(&sidecar));
+    }
+
+    #[test]
+    fn disk_move_into_copies_if_rename_fails() {
+        let (source, _source_dir) = tmp_store();
+        let (target, _target_dir) = tmp_store();
+
+        let (sidecar, versioned_hash, _) = eip7594_single_blob_sidecar();
+        let tx = B256::random();
+        source.insert(tx, sidecar.clone()).unwrap();
+        let size = source.data_size_hint().unwrap();
+
+        source
+            .inner
+            .move_into_with(&target.inner, tx, |_, _| Err(io::ErrorKind::CrossesDevices.into()))
+            .unwrap();
+
+        assert!(!source.inner.blob_disk_file(tx).exists());
+        assert_eq!(source.blobs_len(), 0);
+        assert_eq!(target.blobs_len(), 1);
+        assert_eq!(target.data_size_hint().unwrap(), size);
+        assert!(target.contains_versioned_hash(versioned_hash).unwrap());
+        target.clear_cache();
+        assert_eq!(target.get(tx).unwrap().as_deref(), Some(&sidecar));
+    }
+
+    #[test]
+    fn disk_drain_into_disk_skips_pending_deletions() {
+        let (source, _source_dir) = tmp_store();
+        let (target, _target_dir) = tmp_store();
+
+        let (sidecar, versioned_hash, _) = eip7594_single_blob_sidecar();
+        let tx = B256::random();
+        source.insert(tx, sidecar).unwrap();
+        source.delete(tx).unwrap();
+
+        source.drain_into_disk(&target, vec![tx]).unwrap();
+
+        assert_eq!(target.blobs_len(), 0);
+        assert!(target.inner.versioned_hashes_to_txhash.lock().get(&versioned_hash).is_none());
+    }
+
+    #[test]
+    fn drain_into_moves_sidecars_between_store_kinds() {
+        let (source, _dir) = tmp_store();
+        let target = InMemoryBlobStore::default();
+
+        let blobs = rng_blobs(2);
+        source.insert_all(blobs.clone()).unwrap();
+        source.drain_into(&target, vec![blobs[0].0]).unwrap();
+
+        assert_eq!(target.get(blobs[0].0).unwrap().as_deref(), Some(&blobs[0].1));
+        assert!(target.get(blobs[1].0).unwrap().is_none());
+        // deleted from the source, the blob file is removed by the next cleanup
+        assert_eq!(source.cleanup().delete_succeed, 1);
+        assert!(!source.contains(blobs[0].0).unwrap());
+        assert!(source.contains(blobs[1].0).unwrap());
+    }
+
+    #[test]
+    fn disk_get_for_block_preserves_order() {
+        let (store, _dir) = tmp_store();
+
+        let blobs = rng_blobs(3);
+        store.insert_all(blobs.clone()).unwrap();
+        // serve one sidecar from the cache and the rest from disk
+        store.clear_cache();
+        store.get(blobs[1].0).unwrap();
+
+        let missing = B256::random();
+        let request = [blobs[2].0, missing, blobs[0].0, blobs[1].0];
+        let retrieved = store.get_for_block(&request).unwrap();
+
+        assert_eq!(retrieved.len(), request.len());
+        assert_eq!(retrieved[0].as_deref(), Some(&blobs[2].1));
+        assert!(retrieved[1].is_none());
+        assert_eq!(retrieved[2].as_deref(), Some(&blobs[0].1));
+        assert_eq!(retrieved[3].as_deref(), Some(&blobs[1].1));
+        assert!(store.get_for_block(&[]).unwrap().is_empty());
+    }
+
     #[test]
     fn disk_get_exact_blobs_success() {
         let (store, _dir) = tmp_store();
@@ -1025,6 +2452,185 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn disk_blob_files_use_configured_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_file_mode(0o600),
+        )
+        .unwrap();
+
+        let blobs = rng_blobs(2);
+        let (tx, blob) = blobs[0].clone();
+        store.insert(tx, blob).unwrap();
+        store.insert_all(blobs[1..].to_vec()).unwrap();
+
+        for (tx, _) in &blobs {
+            let mode = fs::metadata(store.inner.blob_disk_file(*tx)).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn disk_rejects_oversized_blobs() {
+        let blobs = rng_blobs(2);
+        let max = blobs[0].1.rlp_encoded_fields_length() - 1;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_max_blob_file_bytes(max),
+        )
+        .unwrap();
+
+        let (tx, blob) = blobs[0].clone();
+        assert!(store.insert(tx, blob).is_err());
+        assert!(store.insert_all(blobs.clone()).is_err());
+
+        for (tx, _) in &blobs {
+            assert!(!store.inner.blob_disk_file(*tx).exists());
+            assert!(store.get(*tx).unwrap().is_none());
+        }
+        assert_eq!(store.blobs_len(), 0);
+    }
+
+    #[test]
+    fn disk_verify_reports_corrupt_files() {
+        let (store, _dir) = tmp_store();
+
+        let blobs = rng_blobs(3);
+        store.insert_all(blobs.clone()).unwrap();
+        assert_eq!(store.verify().unwrap(), VerifyReport { ok: 3, ..Default::default() });
+
+        let corrupt_tx = blobs[1].0;
+        fs::write(store.inner.blob_disk_file(corrupt_tx), [0xff, 0x00]).unwrap();
+
+        let report = store.verify().unwrap();
+        assert_eq!(report.ok, 2);
+        assert_eq!(report.corrupt, vec![corrupt_tx]);
+        assert!(report.unreadable.is_empty());
+        assert!(!report.is_healthy());
+    }
+
+    #[test]
+    fn disk_compact_removes_stray_files() {
+        let (store, dir) = tmp_store();
+
+        let blobs = rng_blobs(4);
+        store.insert_all(blobs.clone()).unwrap();
+        store.delete(blobs[0].0).unwrap();
+        let corrupt_tx = blobs[1].0;
+        fs::write(store.inner.blob_disk_file(corrupt_tx), [0xff, 0x00]).unwrap();
+        fs::write(dir.path().join(format!("{:x}.migrating", blobs[2].0)), [0x01]).unwrap();
+
+        let report = store.compact();
+        assert_eq!(report.cleanup.delete_succeed, 1);
+        assert_eq!(report.stray_removed, 2);
+        assert_eq!(report.files_rewritten, 2);
+        assert_eq!(report.reclaimed_bytes, report.cleanup.reclaimed_bytes + 3);
+        assert_eq!(store.blobs_len(), 2);
+
+        let mut files = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        let mut expected = blobs[2..].iter().map(|(tx, _)| format!("{tx:x}")).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(files, expected);
+
+        store.clear_cache();
+        for (tx, blob) in &blobs[2..] {
+            assert_eq!(store.get(*tx).unwrap().as_deref(), Some(blob));
+        }
+        assert!(!store.contains(corrupt_tx).unwrap());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn disk_dedup_hardlinks_shares_identical_sidecars() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_dedup_hardlinks(true),
+        )
+        .unwrap();
+
+        let (sidecar, _, _) = eip7594_single_blob_sidecar();
+        let first = TxHash::random();
+        let second = TxHash::random();
+        let third = TxHash::random();
+        store.insert(first, sidecar.clone()).unwrap();
+        store.insert_all(vec![(second, sidecar.clone()), (third, sidecar.clone())]).unwrap();
+
+        let size = fs::metadata(store.inner.blob_disk_file(first)).unwrap().len();
+        assert_eq!(fs::metadata(store.inner.blob_disk_file(first)).unwrap().nlink(), 3);
+        assert_eq!(store.data_size_hint(), Some(size as usize));
+        assert_eq!(store.blobs_len(), 3);
+
+        // removing a shared link frees nothing
+        store.delete_all(vec![first, second]).unwrap();
+        let stat = store.cleanup();
+        assert_eq!(stat.delete_succeed, 2);
+        assert_eq!(stat.reclaimed_bytes, 0);
+        assert_eq!(store.data_size_hint(), Some(size as usize));
+
+        store.clear_cache();
+        assert_eq!(store.get(third).unwrap().as_deref(), Some(&sidecar));
+
+        store.delete(third).unwrap();
+        assert_eq!(store.cleanup().reclaimed_bytes, size);
+        assert_eq!(store.data_size_hint(), Some(0));
+    }
+
+    #[test]
+    fn disk_migrate_to_v2() {
+        let convert = |sidecar: &BlobTransactionSidecar| {
+            BlobTransactionSidecarVariant::Eip7594(BlobTransactionSidecarEip7594::new(
+                sidecar.blobs.clone(),
+                sidecar.commitments.clone(),
+                vec![],
+            ))
+        };
+
+        let (store, _dir) = tmp_store();
+        assert!(matches!(
+            store.migrate_to_v2(convert),
+            Err(DiskFileBlobStoreError::MigrationDisabled)
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_allow_migration(true),
+        )
+        .unwrap();
+
+        let blobs = rng_blobs(2);
+        store.insert_all(blobs.clone()).unwrap();
+        let (v2_sidecar, _, _) = eip7594_single_blob_sidecar();
+        let v2_tx = TxHash::random();
+        store.insert(v2_tx, v2_sidecar).unwrap();
+        store.clear_cache();
+
+        let report = store.migrate_to_v2(convert).unwrap();
+        assert_eq!(report, MigrationReport { migrated: 2, skipped: 1, failed: vec![] });
+
+        for (tx, _) in &blobs {
+            assert!(store.get(*tx).unwrap().unwrap().is_eip7594());
+        }
+
+        // already migrated sidecars are skipped on a second run
+        let report = store.migrate_to_v2(convert).unwrap();
+        assert_eq!(report, MigrationReport { migrated: 0, skipped: 3, failed: vec![] });
+    }
+
     #[test]
     fn disk_data_size_hint() {
         let (store, _dir) = tmp_store();
@@ -1049,6 +2655,67 @@ mod tests {
         assert_eq!(stat.delete_failed, 0);
     }
 
+    #[test]
+    fn transient_read_errors() {
+        assert!(is_transient_read_error(&io::Error::from(io::ErrorKind::Interrupted)));
+        assert!(is_transient_read_error(&io::Error::from(io::ErrorKind::WouldBlock)));
+        assert!(!is_transient_read_error(&io::Error::from(io::ErrorKind::NotFound)));
+        assert!(!is_transient_read_error(&io::Error::from(io::ErrorKind::PermissionDenied)));
+    }
+
+    #[test]
+    fn disk_read_retries_skip_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_read_retries(3),
+        )
+        .unwrap();
+
+        let blobs = rng_blobs(1);
+        store.insert_all(blobs.clone()).unwrap();
+        store.clear_cache();
+        assert!(store.get(blobs[0].0).unwrap().is_some());
+        assert!(store.get(TxHash::random()).unwrap().is_none());
+    }
+
+    #[test]
+    fn disk_read_retries_are_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_read_retries(u32::MAX),
+        )
+        .unwrap();
+        assert_eq!(store.inner.read_retries, MAX_READ_RETRIES);
+    }
+
+    #[test]
+    fn disk_cleanup_dry_run() {
+        let (store, _dir) = tmp_store();
+
+        let blobs = rng_blobs(3);
+        let txs = blobs.iter().map(|(tx, _)| *tx).collect::<Vec<_>>();
+        store.insert_all(blobs).unwrap();
+        let size = store.data_size_hint().unwrap();
+
+        store.delete_all(txs[..2].to_vec()).unwrap();
+        let preview = store.cleanup_dry_run();
+        assert_eq!(preview.delete_succeed, 2);
+        assert_eq!(preview.delete_failed, 0);
+        assert!(preview.reclaimed_bytes > 0);
+
+        // nothing was deleted
+        for tx in &txs {
+            assert!(store.inner.blob_disk_file(*tx).exists());
+        }
+        assert_eq!(store.data_size_hint(), Some(size));
+
+        assert_eq!(store.cleanup(), preview);
+        assert!(!store.inner.blob_disk_file(txs[0]).exists());
+        assert_eq!(store.cleanup_dry_run(), BlobStoreCleanupStat::default());
+    }
+
     #[test]
     fn disk_get_blobs_v3_returns_partial_results() {
         let (store, _dir) = tmp_store();
@@ -1121,6 +2788,102 @@ mod tests {
         assert_eq!(store.has_versioned_hashes(&[versioned_hash]).unwrap(), vec![true]);
     }
 
+    #[test]
+    fn disk_evicted_blobs_remain_readable() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_max_cached_entries(1),
+        )
+        .unwrap();
+
+        let blobs = rng_blobs(2);
+        for (tx, blob) in blobs.clone() {
+            store.insert(tx, blob).unwrap();
+        }
+
+        assert!(!store.is_cached(&blobs[0].0));
+        assert!(store.is_cached(&blobs[1].0));
+        for (tx, blob) in &blobs {
+            assert_eq!(store.get(*tx).unwrap().map(Arc::unwrap_or_clone).as_ref(), Some(blob));
+        }
+    }
+
+    #[test]
+    fn disk_failed_write_is_not_cached() {
+        let (store, _dir) = tmp_store();
+
+        let (tx, blob) = rng_blobs(1).pop().unwrap();
+        fs::remove_dir_all(&store.inner.blob_dir).unwrap();
+
+        assert!(store.insert(tx, blob).is_err());
+        assert!(!store.is_cached(&tx));
+        assert!(store.get(tx).unwrap().is_none());
+        assert_eq!(store.blobs_len(), 0);
+    }
+
+    #[test]
+    fn disk_sharded_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_max_cached_entries(64).with_cache_shards(4),
+        )
+        .unwrap();
+        assert_eq!(store.inner.blob_cache.shards().count(), 4);
+
+        let blobs = rng_blobs(10);
+        store.insert_all(blobs.clone()).unwrap();
+        for (tx, _) in &blobs {
+            assert!(store.is_cached(tx));
+        }
+        assert_eq!(store.inner.blob_cache.try_len(), Some(blobs.len()));
+
+        let txs = blobs.iter().map(|(tx, _)| *tx).collect::<Vec<_>>();
+        assert_eq!(store.get_all(txs).unwrap().len(), blobs.len());
+
+        // shards are clamped so every shard can hold at least one sidecar
+        assert_eq!(BlobCache::new(2, 8).shards().count(), 2);
+    }
+
+    #[test]
+    fn disk_reinsert_after_delete_survives_cleanup() {
+        let (store, _dir) = tmp_store();
+
+        let (tx, blob) = rng_blobs(1).pop().unwrap();
+        store.insert(tx, blob.clone()).unwrap();
+        store.delete(tx).unwrap();
+        store.insert(tx, blob.clone()).unwrap();
+
+        let (other_tx, other_blob) = rng_blobs(1).pop().unwrap();
+        store.insert_all(vec![(other_tx, other_blob.clone())]).unwrap();
+        store.delete(other_tx).unwrap();
+        store.insert_all(vec![(other_tx, other_blob.clone())]).unwrap();
+
+        let stat = store.cleanup();
+        assert_eq!(stat.delete_succeed, 0);
+
+        store.clear_cache();
+        assert_eq!(store.get(tx).unwrap().map(Arc::unwrap_or_clone), Some(blob));
+        assert_eq!(store.get(other_tx).unwrap().map(Arc::unwrap_or_clone), Some(other_blob));
+    }
+
+    #[test]
+    fn disk_contains_versioned_hash() {
+        let (store, _dir) = tmp_store();
+
+        let (sidecar, versioned_hash, _) = eip7594_single_blob_sidecar();
+        assert!(!store.contains_versioned_hash(versioned_hash).unwrap());
+
+        store.insert(TxHash::random(), sidecar).unwrap();
+        assert!(store.contains_versioned_hash(versioned_hash).unwrap());
+        assert!(!store.contains_versioned_hash(B256::random()).unwrap());
+
+        // resolved through the index and the blob file
+        store.clear_cache();
+        assert!(store.contains_versioned_hash(versioned_hash).unwrap());
+    }
+
     #[test]
     fn disk_has_blobs_ignores_stale_index_entries() {
         let (store, _dir) = tmp_store();
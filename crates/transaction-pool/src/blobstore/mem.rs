@@ -266,6 +266,11 @@ impl BlobStore for InMemoryBlobStore {
         Some(self.inner.size_tracker.data_size())
     }
 
+    /// All blob data is kept in memory, so this is the same as the data size.
+    fn approx_memory_bytes(&self) -> usize {
+        self.inner.size_tracker.data_size()
+    }
+
     fn blobs_len(&self) -> usize {
         self.inner.size_tracker.blobs_len()
     }
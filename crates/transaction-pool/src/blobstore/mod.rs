@@ -5,6 +5,7 @@ use alloy_eips::{
     eip7594::{BlobTransactionSidecarVariant, Cell},
 };
 use alloy_primitives::{TxHash, B128, B256};
+pub use async_store::AsyncBlobStore;
 pub use converter::BlobSidecarConverter;
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
@@ -16,12 +17,15 @@ use std::{
         Arc,
     },
 };
+pub use tee::TeeBlobStore;
 pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 
+mod async_store;
 mod converter;
 pub mod disk;
 mod mem;
 mod noop;
+mod tee;
 mod tracker;
 
 /// A blob store that can be used to store blob data of EIP4844 transactions.
@@ -79,6 +83,38 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
         txs: Vec<B256>,
     ) -> Result<Vec<Arc<BlobTransactionSidecarVariant>>, BlobStoreError>;
 
+    /// Retrieves the blob sidecars of a block's blob transactions.
+    ///
+    /// The response is always the same length and order as `tx_hashes`, with `None` for every
+    /// transaction whose sidecar is not in the store.
+    fn get_for_block(
+        &self,
+        tx_hashes: &[B256],
+    ) -> Result<Vec<Option<Arc<BlobTransactionSidecarVariant>>>, BlobStoreError> {
+        tx_hashes.iter().map(|tx| self.get(*tx)).collect()
+    }
+
+    /// Moves the sidecars of the given transactions into `other` and deletes them from this
+    /// store.
+    ///
+    /// Sidecars are inserted into `other` before they are deleted here, so a failure can leave a
+    /// sidecar in both stores, but never in neither. Transactions without a sidecar in this store
+    /// are skipped.
+    ///
+    /// To move sidecars between two [`DiskFileBlobStore`]s without re-encoding them, use
+    /// [`DiskFileBlobStore::drain_into_disk`].
+    fn drain_into(&self, other: &dyn BlobStore, txs: Vec<B256>) -> Result<(), BlobStoreError> {
+        let sidecars = self.get_all(txs)?;
+        if sidecars.is_empty() {
+            return Ok(())
+        }
+        let moved = sidecars.iter().map(|(tx, _)| *tx).collect();
+        other.insert_all(
+            sidecars.into_iter().map(|(tx, sidecar)| (tx, Arc::unwrap_or_clone(sidecar))).collect(),
+        )?;
+        self.delete_all(moved)
+    }
+
     /// Return the [`BlobAndProofV1`]s for a list of blob versioned hashes.
     fn get_by_versioned_hashes_v1(
         &self,
@@ -125,6 +161,12 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// The response is always the same length and order as the request.
     fn has_versioned_hashes(&self, versioned_hashes: &[B256]) -> Result<Vec<bool>, BlobStoreError>;
 
+    /// Returns whether the blob with the given versioned hash is available, without loading the
+    /// sidecar it belongs to.
+    fn contains_versioned_hash(&self, versioned_hash: B256) -> Result<bool, BlobStoreError> {
+        Ok(self.has_versioned_hashes(&[versioned_hash])?.first().copied().unwrap_or_default())
+    }
+
     /// Returns all requested cells for all blobs belonging to the transaction.
     ///
     /// The `indices_bitarray` is applied independently to every blob in the tx.
@@ -151,8 +193,22 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
         indices_bitarray: B128,
     ) -> Result<Option<Vec<Cell>>, BlobStoreError>;
 
-    /// Data size of all transactions in the blob store.
-    fn data_size_hint(&self) -> Option<usize>;
+    /// Data size of all transactions in the blob store, in bytes.
+    ///
+    /// This is the size of all stored blob data, regardless of whether it's kept in memory or on
+    /// disk, see [`Self::approx_memory_bytes`] for the memory footprint. Returns `None` if the
+    /// store doesn't track its size.
+    fn data_size_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// Approximate number of bytes of blob data the store currently keeps in memory.
+    ///
+    /// For stores backed by disk this is only the footprint of their in-memory cache. Defaults to
+    /// `0` for stores that don't keep blob data in memory.
+    fn approx_memory_bytes(&self) -> usize {
+        0
+    }
 
     /// How many blobs are in the blob store.
     fn blobs_len(&self) -> usize;
@@ -229,11 +285,14 @@ impl PartialEq for BlobStoreSize {
 
 /// Statistics for the cleanup operation.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct BlobStoreCleanupStat {
     /// the number of successfully deleted blobs
     pub delete_succeed: usize,
     /// the number of failed deletions
     pub delete_failed: usize,
+    /// the number of bytes freed on disk
+    pub reclaimed_bytes: u64,
 }
 
 #[cfg(test)]
@@ -0,0 +1,329 @@
+//! A blob store that mirrors writes to a second store.
+
+use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError};
+use alloy_eips::{
+    eip4844::{BlobAndProofV1, BlobAndProofV2, BlobCellsAndProofsV1},
+    eip7594::{BlobTransactionSidecarVariant, Cell},
+};
+use alloy_primitives::{map::B256Set, TxHash, B128, B256};
+use std::sync::Arc;
+use tracing::debug;
+
+/// A [`BlobStore`] that writes to two stores and reads from the primary one first.
+///
+/// Inserts and deletes go to both stores. Reads are served by the primary store and fall back to
+/// the secondary store, sidecars found there are copied into the primary store. This allows
+/// migrating to a new store without downtime: use the new store as primary and the old one as
+/// secondary until the old store no longer serves any reads, then switch to the new store alone.
+///
+/// Lookups by versioned hash also fall back to the secondary store, but don't copy the sidecars
+/// since their transaction is unknown.
+#[derive(Debug, Clone)]
+pub struct TeeBlobStore<A, B> {
+    primary: A,
+    secondary: B,
+}
+
+impl<A, B> TeeBlobStore<A, B> {
+    /// Creates a new store that mirrors writes to both stores and prefers `primary` for reads.
+    pub const fn new(primary: A, secondary: B) -> Self {
+        Self { primary, secondary }
+    }
+
+    /// Returns the store that is read first.
+    pub const fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Returns the store that reads fall back to.
+    pub const fn secondary(&self) -> &B {
+        &self.secondary
+    }
+
+    /// Consumes the type and returns the primary and the secondary store.
+    pub fn into_inner(self) -> (A, B) {
+        (self.primary, self.secondary)
+    }
+}
+
+impl<A: BlobStore, B: BlobStore> TeeBlobStore<A, B> {
+    /// Copies sidecars that were only found in the secondary store into the primary store.
+    ///
+    /// This is best effort, a failed copy doesn't fail the read that found the sidecars.
+    fn backfill(&self, sidecars: &[(B256, Arc<BlobTransactionSidecarVariant>)]) {
+        if sidecars.is_empty() {
+            return
+        }
+        let sidecars =
+            sidecars.iter().map(|(tx, sidecar)| (*tx, sidecar.as_ref().clone())).collect();
+        if let Err(err) = self.primary.insert_all(sidecars) {
+            debug!(target:"txpool::blob", %err, "Failed to backfill blobs into primary store");
+        }
+    }
+}
+
+impl<A: BlobStore, B: BlobStore> BlobStore for TeeBlobStore<A, B> {
+    fn insert(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError> {
+        self.primary.insert(tx, data.clone())?;
+        self.secondary.insert(tx, data)
+    }
+
+    fn insert_all(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
+        if txs.is_empty() {
+            return Ok(())
+        }
+        self.primary.insert_all(txs.clone())?;
+        self.secondary.insert_all(txs)
+    }
+
+    fn delete(&self, tx: B256) -> Result<(), BlobStoreError> {
+        self.primary.delete(tx)?;
+        self.secondary.delete(tx)
+    }
+
+    fn delete_all(&self, txs: Vec<B256>) -> Result<(), BlobStoreError> {
+        if txs.is_empty() {
+            return Ok(())
+        }
+        self.primary.delete_all(txs.clone())?;
+        self.secondary.delete_all(txs)
+    }
+
+    fn cleanup(&self) -> BlobStoreCleanupStat {
+        let primary = self.primary.cleanup();
+        let secondary = self.secondary.cleanup();
+        BlobStoreCleanupStat {
+            delete_succeed: primary.delete_succeed + secondary.delete_succeed,
+            delete_failed: primary.delete_failed + secondary.delete_failed,
+            reclaimed_bytes: primary.reclaimed_bytes + secondary.reclaimed_bytes,
+        }
+    }
+
+    fn get(&self, tx: B256) -> Result<Option<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
+        if let Some(sidecar) = self.primary.get(tx)? {
+            return Ok(Some(sidecar))
+        }
+        let sidecar = self.secondary.get(tx)?;
+        if let Some(sidecar) = &sidecar {
+            self.backfill(&[(tx, Arc::clone(sidecar))]);
+        }
+        Ok(sidecar)
+    }
+
+    fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
+        Ok(self.primary.contains(tx)? || self.secondary.contains(tx)?)
+    }
+
+    fn get_all(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<(B256, Arc<BlobTransactionSidecarVariant>)>, BlobStoreError> {
+        if txs.is_empty() {
+            return Ok(Vec::new())
+        }
+        let mut sidecars = self.primary.get_all(txs.clone())?;
+        let found = sidecars.iter().map(|(tx, _)| *tx).collect::<B256Set>();
+        let missing = txs.into_iter().filter(|tx| !found.contains(tx)).collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(sidecars)
+        }
+        let fallback = self.secondary.get_all(missing)?;
+        self.backfill(&fallback);
+        sidecars.extend(fallback);
+        Ok(sidecars)
+    }
+
+    fn get_exact(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
+        txs.into_iter().map(|tx| self.get(tx)?.ok_or(BlobStoreError::MissingSidecar(tx))).collect()
+    }
+
+    fn get_for_block(
+        &self,
+        tx_hashes: &[B256],
+    ) -> Result<Vec<Option<Arc<BlobTransactionSidecarVariant>>>, BlobStoreError> {
+        let mut sidecars = self.primary.get_for_block(tx_hashes)?;
+        let missing = tx_hashes
+            .iter()
+            .zip(&sidecars)
+            .filter(|(_, sidecar)| sidecar.is_none())
+            .map(|(tx, _)| *tx)
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(sidecars)
+        }
+        let found = self.secondary.get_for_block(&missing)?;
+        let fallback = missing
+            .into_iter()
+            .zip(found)
+            .filter_map(|(tx, sidecar)| Some((tx, sidecar?)))
+            .collect::<Vec<_>>();
+        self.backfill(&fallback);
+        for (tx, slot) in tx_hashes.iter().zip(&mut sidecars) {
+            if slot.is_none() {
+                *slot = fallback
+                    .iter()
+                    .find(|(found, _)| found == tx)
+                    .map(|(_, sidecar)| Arc::clone(sidecar));
+            }
+        }
+        Ok(sidecars)
+    }
+
+    fn get_by_versioned_hashes_v1(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
+        let mut result = self.primary.get_by_versioned_hashes_v1(versioned_hashes)?;
+        fill_missing(&mut result, || self.secondary.get_by_versioned_hashes_v1(versioned_hashes))?;
+        Ok(result)
+    }
+
+    fn get_by_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
+        if let Some(result) = self.primary.get_by_versioned_hashes_v2(versioned_hashes)? {
+            return Ok(Some(result))
+        }
+        // the blobs may be split across both stores
+        Ok(self.get_by_versioned_hashes_v3(versioned_hashes)?.into_iter().collect())
+    }
+
+    fn get_by_versioned_hashes_v3(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
+        let mut result = self.primary.get_by_versioned_hashes_v3(versioned_hashes)?;
+        fill_missing(&mut result, || self.secondary.get_by_versioned_hashes_v3(versioned_hashes))?;
+        Ok(result)
+    }
+
+    fn get_by_versioned_hashes_v4(
+        &self,
+        versioned_hashes: &[B256],
+        indices_bitarray: B128,
+    ) -> Result<Vec<Option<BlobCellsAndProofsV1>>, BlobStoreError> {
+        let mut result =
+            self.primary.get_by_versioned_hashes_v4(versioned_hashes, indices_bitarray)?;
+        fill_missing(&mut result, || {
+            self.secondary.get_by_versioned_hashes_v4(versioned_hashes, indices_bitarray)
+        })?;
+        Ok(result)
+    }
+
+    fn has_versioned_hashes(&self, versioned_hashes: &[B256]) -> Result<Vec<bool>, BlobStoreError> {
+        let mut result = self.primary.has_versioned_hashes(versioned_hashes)?;
+        if result.iter().all(|available| *available) {
+            return Ok(result)
+        }
+        for (available, fallback) in
+            result.iter_mut().zip(self.secondary.has_versioned_hashes(versioned_hashes)?)
+        {
+            *available |= fallback;
+        }
+        Ok(result)
+    }
+
+    fn contains_versioned_hash(&self, versioned_hash: B256) -> Result<bool, BlobStoreError> {
+        Ok(self.primary.contains_versioned_hash(versioned_hash)? ||
+            self.secondary.contains_versioned_hash(versioned_hash)?)
+    }
+
+    fn get_cells(
+        &self,
+        tx_hash: TxHash,
+        indices_bitarray: B128,
+    ) -> Result<Option<Vec<Cell>>, BlobStoreError> {
+        if let Some(cells) = self.primary.get_cells(tx_hash, indices_bitarray)? {
+            return Ok(Some(cells))
+        }
+        self.secondary.get_cells(tx_hash, indices_bitarray)
+    }
+
+    /// Returns the larger size of the two stores, since their contents are mostly mirrored.
+    fn data_size_hint(&self) -> Option<usize> {
+        self.primary.data_size_hint().max(self.secondary.data_size_hint())
+    }
+
+    /// Returns the combined memory footprint of both stores, since each keeps its own copy.
+    fn approx_memory_bytes(&self) -> usize {
+        self.primary.approx_memory_bytes().saturating_add(self.secondary.approx_memory_bytes())
+    }
+
+    /// Returns the larger number of blobs of the two stores, since their contents are mostly
+    /// mirrored.
+    fn blobs_len(&self) -> usize {
+        self.primary.blobs_len().max(self.secondary.blobs_len())
+    }
+}
+
+/// Fills the `None` entries of `result` from the fallback lookup, which is only performed if an
+/// entry is missing.
+fn fill_missing<T>(
+    result: &mut [Option<T>],
+    fallback: impl FnOnce() -> Result<Vec<Option<T>>, BlobStoreError>,
+) -> Result<(), BlobStoreError> {
+    if result.iter().all(Option::is_some) {
+        return Ok(())
+    }
+    for (slot, fallback) in result.iter_mut().zip(fallback()?) {
+        if slot.is_none() {
+            *slot = fallback;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blobstore::InMemoryBlobStore;
+    use alloy_consensus::BlobTransactionSidecar;
+
+    fn sidecar() -> BlobTransactionSidecarVariant {
+        BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar::default())
+    }
+
+    #[test]
+    fn tee_writes_to_both_stores() {
+        let store = TeeBlobStore::new(InMemoryBlobStore::default(), InMemoryBlobStore::default());
+        let tx = B256::random();
+
+        store.insert(tx, sidecar()).unwrap();
+        assert!(store.primary().contains(tx).unwrap());
+        assert!(store.secondary().contains(tx).unwrap());
+        assert_eq!(store.blobs_len(), 1);
+
+        store.delete(tx).unwrap();
+        store.cleanup();
+        assert!(!store.primary().contains(tx).unwrap());
+        assert!(!store.secondary().contains(tx).unwrap());
+    }
+
+    #[test]
+    fn tee_backfills_reads_from_secondary() {
+        let store = TeeBlobStore::new(InMemoryBlobStore::default(), InMemoryBlobStore::default());
+        let (in_primary, in_secondary, missing) = (B256::random(), B256::random(), B256::random());
+        store.primary().insert(in_primary, sidecar()).unwrap();
+        store.secondary().insert(in_secondary, sidecar()).unwrap();
+
+        let found = store.get_for_block(&[in_primary, in_secondary, missing]).unwrap();
+        assert!(found[0].is_some());
+        assert!(found[1].is_some());
+        assert!(found[2].is_none());
+        assert!(store.primary().contains(in_secondary).unwrap());
+
+        let found = store.get_all(vec![in_primary, in_secondary, missing]).unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(matches!(
+            store.get_exact(vec![missing]),
+            Err(BlobStoreError::MissingSidecar(tx)) if tx == missing
+        ));
+    }
+}
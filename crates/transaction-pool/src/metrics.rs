@@ -78,6 +78,21 @@ pub struct BlobStoreMetrics {
     pub blobstore_entries: Gauge,
 }
 
+/// Read metrics of the [`DiskFileBlobStore`](crate::blobstore::DiskFileBlobStore)
+#[cfg(feature = "metrics")]
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.blobstore.disk")]
+pub struct DiskBlobStoreReadMetrics {
+    /// Number of blob lookups served from the in-memory cache
+    pub cache_hits: Counter,
+    /// Number of blob lookups that had to read from disk
+    pub cache_misses: Counter,
+    /// Time spent reading blob files from disk, per read call
+    pub read_duration_seconds: Histogram,
+    /// Number of bytes read from disk, per read call
+    pub read_bytes: Histogram,
+}
+
 /// Transaction pool maintenance metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
@@ -1,3 +1,4 @@
+use alloy_primitives::B256;
 use reth_execution_errors::{SparseTrieError, StateProofError};
 use reth_provider::ProviderError;
 use thiserror::Error;
@@ -14,6 +15,10 @@ pub enum StateRootTaskError {
     /// Sparse trie error.
     #[error(transparent)]
     SparseTrie(#[from] SparseTrieError),
+    /// The storage trie of the given hashed address was still blind when its root was computed,
+    /// meaning the proofs fetched for it were incomplete.
+    #[error("storage trie for hashed address {_0} is blind")]
+    BlindStorageTrie(B256),
     /// Sparse trie task stalled.
     #[error("sparse trie task stalled")]
     Stalled,
@@ -35,7 +35,7 @@ use crate::{
 };
 use alloy_primitives::{
     map::{B256Map, B256Set},
-    B256, U256,
+    Address, B256, U256,
 };
 use crossbeam_channel::{unbounded, Receiver as CrossbeamReceiver, Sender as CrossbeamSender};
 use reth_execution_errors::StateProofError;
@@ -45,9 +45,11 @@ use reth_storage_errors::db::DatabaseError;
 use reth_tasks::Runtime;
 use reth_trie::{
     hashed_cursor::{HashedCursorFactory, HashedStorageCursor, InstrumentedHashedCursor},
+    proof::Proof,
     proof_v2,
     trie_cursor::{InstrumentedTrieCursor, TrieCursorFactory, TrieStorageCursor},
-    DecodedMultiProofV2, HashedPostState, MultiProofTargetsV2, ProofTrieNodeV2, ProofV2Target,
+    AccountProof, DecodedMultiProofV2, HashedPostState, MultiProofTargetsV2, ProofTrieNodeV2,
+    ProofV2Target,
 };
 use std::{
     cell::RefCell,
@@ -357,7 +359,9 @@ impl ProofWorkerHandle {
                 let error =
                     ProviderError::other(std::io::Error::other("account workers unavailable"));
 
-                let AccountWorkerJob::AccountMultiproof { input } = err.0;
+                let AccountWorkerJob::AccountMultiproof { input } = err.0 else {
+                    unreachable!("sent an account multiproof job")
+                };
                 let ProofResultContext { sender: result_tx, state, start_time: start } =
                     input.into_proof_result_sender();
 
@@ -370,6 +374,30 @@ impl ProofWorkerHandle {
                 error
             })
     }
+
+    /// Dispatch the proof of a single account and the given storage slots, as returned by
+    /// `eth_getProof`, to the account worker pool.
+    ///
+    /// The result will be sent via the `result_sender` channel.
+    pub fn dispatch_account_proof(
+        &self,
+        address: Address,
+        slots: Vec<B256>,
+        result_sender: CrossbeamSender<ProviderResult<AccountProof>>,
+    ) -> Result<(), ProviderError> {
+        self.account_work_tx
+            .send(AccountWorkerJob::AccountProof { address, slots, result_sender })
+            .map_err(|err| {
+                let error =
+                    ProviderError::other(std::io::Error::other("account workers unavailable"));
+
+                if let AccountWorkerJob::AccountProof { result_sender, .. } = err.0 {
+                    let _ = result_sender.send(Err(error.clone()));
+                }
+
+                error
+            })
+    }
 }
 
 /// Data used for initializing cursor factories that is shared across all proof worker instances.
@@ -939,6 +967,13 @@ where
                     total_idle_time += value_encoder_stats.storage_wait_time;
                     value_encoder_stats_cache.extend(&value_encoder_stats);
                 }
+                AccountWorkerJob::AccountProof { address, slots, result_sender } => {
+                    let result = Proof::new(&provider, &provider)
+                        .account_proof(address, &slots)
+                        .map_err(Into::into);
+                    account_proofs_processed += 1;
+                    let _ = result_sender.send(result);
+                }
             }
 
             // Mark worker as available again.
@@ -1154,13 +1189,23 @@ enum AccountWorkerJob {
         /// Account multiproof input parameters
         input: Box<AccountMultiproofInput>,
     },
+    /// Single account proof request
+    AccountProof {
+        /// The account to prove
+        address: Address,
+        /// The storage slots to prove
+        slots: Vec<B256>,
+        /// Channel for sending the proof result
+        result_sender: CrossbeamSender<ProviderResult<AccountProof>>,
+    },
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use reth_chainspec::ChainSpec;
-    use reth_provider::test_utils::create_test_provider_factory_with_chain_spec;
+    use reth_primitives_traits::{Account, StorageEntry};
+    use reth_provider::{test_utils::create_test_provider_factory_with_chain_spec, HashingWriter};
     use std::sync::Arc;
 
     fn test_ctx<Factory>(factory: Factory) -> ProofTaskCtx<Factory> {
@@ -1192,4 +1237,88 @@ mod tests {
         // Workers shut down automatically when handle is dropped
         drop(proof_handle);
     }
+
+    fn account_proof_from_workers<Factory>(
+        factory: Factory,
+        address: Address,
+        slots: &[B256],
+    ) -> AccountProof
+    where
+        Factory: DatabaseProviderROFactory<Provider: TrieCursorFactory + HashedCursorFactory>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    {
+        let runtime = reth_tasks::Runtime::test();
+        let proof_handle = ProofWorkerHandle::new(&runtime, test_ctx(factory), false);
+
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        proof_handle.dispatch_account_proof(address, slots.to_vec(), tx).unwrap();
+        rx.recv().unwrap().unwrap()
+    }
+
+    #[test]
+    fn account_proof_of_missing_account() {
+        let chain_spec = Arc::new(ChainSpec::default());
+        let anchor_hash = chain_spec.genesis_hash();
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        let factory = reth_provider::providers::OverlayStateProviderFactory::new(
+            provider_factory,
+            reth_provider::providers::OverlayBuilder::<reth_ethereum_primitives::EthPrimitives>::new(
+                anchor_hash,
+                reth_trie_db::ChangesetCache::new(),
+            ),
+        );
+
+        let slot = B256::repeat_byte(0x01);
+        let proof = account_proof_from_workers(factory, Address::ZERO, &[slot]);
+        assert_eq!(proof.address, Address::ZERO);
+        assert!(proof.info.is_none());
+        assert_eq!(proof.storage_root, reth_trie::EMPTY_ROOT_HASH);
+        assert_eq!(proof.storage_proofs.len(), 1);
+        assert_eq!(proof.storage_proofs[0].key, slot);
+    }
+
+    #[test]
+    fn account_proof_with_storage_matches_serial_proof() {
+        let chain_spec = Arc::new(ChainSpec::default());
+        let anchor_hash = chain_spec.genesis_hash();
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec);
+
+        let address = Address::repeat_byte(0xaa);
+        let account = Account { nonce: 1, balance: U256::from(100), bytecode_hash: None };
+        let storage = [
+            StorageEntry { key: B256::repeat_byte(0x01), value: U256::from(1) },
+            StorageEntry { key: B256::repeat_byte(0x02), value: U256::from(2) },
+        ];
+        {
+            let provider_rw = provider_factory.provider_rw().unwrap();
+            provider_rw.insert_account_for_hashing([(address, Some(account))]).unwrap();
+            provider_rw.insert_storage_for_hashing([(address, storage)]).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let factory = reth_provider::providers::OverlayStateProviderFactory::new(
+            provider_factory,
+            reth_provider::providers::OverlayBuilder::<reth_ethereum_primitives::EthPrimitives>::new(
+                anchor_hash,
+                reth_trie_db::ChangesetCache::new(),
+            ),
+        );
+
+        // One existing and one missing slot.
+        let slots = [B256::repeat_byte(0x01), B256::repeat_byte(0x03)];
+        let expected = {
+            let provider = factory.database_provider_ro().unwrap();
+            Proof::new(&provider, &provider).account_proof(address, &slots).unwrap()
+        };
+
+        let proof = account_proof_from_workers(factory, address, &slots);
+        assert_eq!(proof, expected);
+        assert_eq!(proof.info, Some(account));
+        assert_ne!(proof.storage_root, reth_trie::EMPTY_ROOT_HASH);
+        assert_eq!(proof.storage_proofs[0].value, U256::from(1));
+        assert_eq!(proof.storage_proofs[1].value, U256::ZERO);
+    }
 }
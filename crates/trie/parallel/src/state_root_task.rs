@@ -8,13 +8,22 @@
 //! task runs lives in `reth-engine-tree` under `tree::state_root_strategy`.
 
 use crate::error::StateRootTaskError;
+use alloy_eips::eip2930::AccessList;
 use alloy_evm::block::OnStateHook;
-use alloy_primitives::{keccak256, map::B256Map, B256};
+use alloy_primitives::{map::B256Map, B256};
 use reth_trie::{
-    updates::TrieUpdates, HashedPostState, HashedStorage, MultiProofTargetsV2, ProofV2Target,
+    updates::TrieUpdates, HashedPostState, HashedStorage, KeccakKeyHasher, KeyHasher,
+    MultiProofTargetsV2, ProofV2Target, ProofV2TargetParent,
 };
 use revm::state::EvmState;
-use std::{fmt, sync::Arc};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tracing::trace;
 
 /// Messages used internally by the multi proof task.
@@ -43,12 +52,47 @@ pub struct StateRootComputeOutcome {
     pub trie_updates: Arc<TrieUpdates>,
     /// Hashed post state produced while computing the state root.
     pub hashed_state: Arc<HashedPostState>,
+    /// Wall-clock time from the start of the task until the state root was computed.
+    pub total_time: Duration,
+    /// Sum of the time proof workers spent on the multiproofs requested by the task, measured
+    /// from dispatch to completion of each proof.
+    ///
+    /// Proofs are computed concurrently, so this can exceed [`Self::total_time`].
+    pub proof_time: Duration,
+    /// Time the task spent revealing proofs, applying updates to the sparse trie, and computing
+    /// the final root.
+    pub sparse_trie_time: Duration,
+    /// Proof targets the task fetched or inherited, which can seed the next task that continues
+    /// from the same sparse trie.
+    pub fetched_proof_targets: Arc<FetchedProofTargets>,
     /// Debug recorders taken from the sparse tries, keyed by `None` for account trie
     /// and `Some(address)` for storage tries.
     #[cfg(feature = "trie-debug")]
     pub debug_recorders: Vec<(Option<B256>, reth_trie_sparse::debug_recorder::TrieDebugRecorder)>,
 }
 
+/// Proof targets that a state root task has already requested from the proof workers, together
+/// with the broadest parent context each was requested with.
+///
+/// The task skips these targets when it sees them again. A set carried over from a previous
+/// task is only valid if the new task starts from the sparse trie that the previous task
+/// revealed them into, unpruned and anchored at the previous task's state root. Otherwise the
+/// nodes the targets stand for are missing and the new task would never fetch them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchedProofTargets {
+    /// Account targets, keyed by hashed address.
+    pub accounts: B256Map<ProofV2TargetParent>,
+    /// Storage targets, keyed by hashed address and then hashed slot.
+    pub storages: B256Map<B256Map<ProofV2TargetParent>>,
+}
+
+impl FetchedProofTargets {
+    /// Returns `true` if no account or storage targets have been fetched.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.storages.values().all(|slots| slots.is_empty())
+    }
+}
+
 /// Handle to a background sparse trie state root computation.
 ///
 /// Used by both the engine (during `newPayload`) and the payload builder (during `FCU`-triggered
@@ -76,6 +120,8 @@ pub struct StateRootHandle {
         Option<std::sync::mpsc::Receiver<Result<StateRootComputeOutcome, StateRootTaskError>>>,
     /// Receiver for the hashed post state.
     hashed_state_rx: Option<std::sync::mpsc::Receiver<Arc<HashedPostState>>>,
+    /// Live proof load of the task.
+    load: StateRootTaskLoad,
 }
 
 impl StateRootHandle {
@@ -97,9 +143,44 @@ impl StateRootHandle {
             cancel_guard,
             state_root_rx: Some(state_root_rx),
             hashed_state_rx: Some(hashed_state_rx),
+            load: StateRootTaskLoad::default(),
         }
     }
 
+    /// Sets the load counters that the backing task updates while it runs.
+    pub fn with_load(mut self, load: StateRootTaskLoad) -> Self {
+        self.load = load;
+        self
+    }
+
+    /// Calls `observer` with every EVM state update before it is sent to the task for hashing.
+    ///
+    /// This gives tooling such as state diff viewers a read-only view of the execution state
+    /// changes. The observer runs on the thread that produces the updates, usually the execution
+    /// thread, so it must be cheap or offload its work. Pre-hashed updates are not observed.
+    ///
+    /// # Panics
+    ///
+    /// If the authoritative capability was already taken.
+    pub fn with_update_observer(
+        mut self,
+        observer: impl Fn(&EvmState) + Send + Sync + 'static,
+    ) -> Self {
+        let inner = self.take_hashed_update_stream().inner;
+        self.authoritative = Some(StateRootUpdateStream::new(Arc::new(ObservedStateRootSink {
+            inner,
+            observer: Box::new(observer),
+        })));
+        self
+    }
+
+    /// Returns the live proof load of the backing task.
+    ///
+    /// Callers can use this to back off speculative prefetching while the task is saturated.
+    pub const fn load(&self) -> &StateRootTaskLoad {
+        &self.load
+    }
+
     /// Returns the state root that the cached sparse trie is anchored at.
     pub const fn cached_trie_state_root(&self) -> B256 {
         self.cached_trie_state_root
@@ -152,6 +233,28 @@ impl StateRootHandle {
             .map_err(|_| StateRootTaskError::Other("sparse trie task dropped".to_string()))?
     }
 
+    /// Returns the state root computation result if it is ready, without blocking.
+    ///
+    /// Returns `None` while the task is still running. Once a result is returned, the receiver is
+    /// consumed like with [`Self::state_root`], so this can be polled from a select loop until it
+    /// yields.
+    ///
+    /// # Panics
+    ///
+    /// If the result was already taken.
+    pub fn try_wait(&mut self) -> Option<Result<StateRootComputeOutcome, StateRootTaskError>> {
+        let rx = self.state_root_rx.as_ref().expect("state_root already taken");
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return None,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                Err(StateRootTaskError::Other("sparse trie task dropped".to_string()))
+            }
+        };
+        self.state_root_rx = None;
+        Some(result)
+    }
+
     /// Takes the state root receiver for use with custom waiting logic (e.g., timeouts).
     ///
     /// # Panics
@@ -190,6 +293,53 @@ impl StateRootHandle {
     }
 }
 
+/// Live proof load of a running state-root task.
+///
+/// The task publishes its counters after every step, so readers observe a recent snapshot that
+/// may already be stale. Clones share the same counters.
+#[derive(Debug, Clone, Default)]
+pub struct StateRootTaskLoad(Arc<StateRootTaskLoadInner>);
+
+#[derive(Debug, Default)]
+struct StateRootTaskLoadInner {
+    in_flight: AtomicUsize,
+    pending: AtomicUsize,
+    max_concurrent: usize,
+}
+
+impl StateRootTaskLoad {
+    /// Creates load counters for a task that can run up to `max_concurrent` proofs at once.
+    pub fn new(max_concurrent: usize) -> Self {
+        Self(Arc::new(StateRootTaskLoadInner { max_concurrent, ..Default::default() }))
+    }
+
+    /// Returns the number of proof batches dispatched to workers and not yet received.
+    pub fn in_flight(&self) -> usize {
+        self.0.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of proof targets queued but not yet dispatched.
+    pub fn pending(&self) -> usize {
+        self.0.pending.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of proofs that can be computed concurrently.
+    pub fn max_concurrent(&self) -> usize {
+        self.0.max_concurrent
+    }
+
+    /// Returns `true` if at least as many proof batches are in flight as can run concurrently.
+    pub fn is_saturated(&self) -> bool {
+        self.max_concurrent() > 0 && self.in_flight() >= self.max_concurrent()
+    }
+
+    /// Publishes the current in-flight and pending counts.
+    pub fn update(&self, in_flight: usize, pending: usize) {
+        self.0.in_flight.store(in_flight, Ordering::Relaxed);
+        self.0.pending.store(pending, Ordering::Relaxed);
+    }
+}
+
 /// Guard that cancels a state-root task when dropped.
 ///
 /// The task watches the paired receiver in its event loop. No message is ever sent: the guard
@@ -308,6 +458,33 @@ pub struct StateAccessHint {
     pub storages: B256Map<Vec<B256>>,
 }
 
+impl StateAccessHint {
+    /// Builds a hint from the access lists of a block's transactions, keccak256-hashing the
+    /// addresses and storage keys.
+    ///
+    /// Entries repeated across lists are not deduplicated here: the task already skips targets
+    /// it has fetched before.
+    pub fn from_access_lists(lists: impl IntoIterator<Item = AccessList>) -> Self {
+        let mut hint = Self::default();
+        for item in lists.into_iter().flatten() {
+            let hashed_address = KeccakKeyHasher::hash_key(item.address);
+            hint.accounts.push(hashed_address);
+            if !item.storage_keys.is_empty() {
+                hint.storages
+                    .entry(hashed_address)
+                    .or_default()
+                    .extend(item.storage_keys.into_iter().map(KeccakKeyHasher::hash_key));
+            }
+        }
+        hint
+    }
+
+    /// Returns `true` if the hint has no account or storage keys.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.storages.is_empty()
+    }
+}
+
 impl From<MultiProofTargetsV2> for StateAccessHint {
     fn from(targets: MultiProofTargetsV2) -> Self {
         Self {
@@ -375,6 +552,17 @@ impl StateRootHintStream {
     pub fn on_access_hint(&self, hint: StateAccessHint) {
         self.inner.on_access_hint(hint);
     }
+
+    /// Prefetches proofs for every account and storage slot in the given access lists.
+    ///
+    /// Called before a block executes, this warms the sparse trie with the state the
+    /// transactions declared upfront, overlapping proof IO with execution.
+    pub fn prefetch_from_access_lists(&self, lists: impl IntoIterator<Item = AccessList>) {
+        let hint = StateAccessHint::from_access_lists(lists);
+        if !hint.is_empty() {
+            self.on_access_hint(hint);
+        }
+    }
 }
 
 /// Authoritative update capability of a state-root stream.
@@ -491,13 +679,49 @@ impl StateRootSink for SparseTrieStateRootSink {
     }
 }
 
+/// [`StateRootSink`] that passes every EVM state update to an observer before forwarding it.
+struct ObservedStateRootSink {
+    inner: Arc<dyn StateRootSink>,
+    observer: Box<dyn Fn(&EvmState) + Send + Sync>,
+}
+
+impl StateRootSink for ObservedStateRootSink {
+    fn on_access_hint(&self, hint: StateAccessHint) {
+        self.inner.on_access_hint(hint);
+    }
+
+    fn on_state_update(&self, state: EvmState) {
+        (self.observer)(&state);
+        self.inner.on_state_update(state);
+    }
+
+    fn on_hashed_state_update(&self, state: HashedPostState) {
+        self.inner.on_hashed_state_update(state);
+    }
+
+    fn on_updates_finished(&self) {
+        self.inner.on_updates_finished();
+    }
+}
+
 /// Converts [`EvmState`] to [`HashedPostState`] by keccak256-hashing addresses and storage slots.
 pub fn evm_state_to_hashed_post_state(update: EvmState) -> HashedPostState {
+    evm_state_to_hashed_post_state_with_hasher::<KeccakKeyHasher>(update)
+}
+
+/// Converts [`EvmState`] to [`HashedPostState`], hashing addresses and storage slots with the
+/// given [`KeyHasher`].
+///
+/// This is for chains whose state trie isn't keyed by keccak256 hashes, Ethereum uses
+/// [`evm_state_to_hashed_post_state`].
+pub fn evm_state_to_hashed_post_state_with_hasher<KH: KeyHasher>(
+    update: EvmState,
+) -> HashedPostState {
     let mut hashed_state = HashedPostState::with_capacity(update.len());
 
     for (address, account) in update {
         if account.is_touched() {
-            let hashed_address = keccak256(address);
+            let hashed_address = KH::hash_key(address);
             trace!(target: "trie::parallel::sparse", ?address, ?hashed_address, "Adding account to state update");
 
             let destroyed = account.is_selfdestructed();
@@ -506,19 +730,24 @@ pub fn evm_state_to_hashed_post_state(update: EvmState) -> HashedPostState {
                 hashed_state.accounts.insert(hashed_address, info);
             }
 
-            let mut changed_storage_iter = account
-                .storage
-                .into_iter()
-                .filter(|(_slot, value)| value.is_changed())
-                .map(|(slot, value)| (keccak256(B256::from(slot)), value.present_value))
-                .peekable();
-
             if destroyed {
                 hashed_state.storages.insert(hashed_address, HashedStorage::new(true));
-            } else if changed_storage_iter.peek().is_some() {
-                hashed_state
-                    .storages
-                    .insert(hashed_address, HashedStorage::from_iter(false, changed_storage_iter));
+                continue
+            }
+
+            // Count the changed slots first so the map is allocated once, the filtered iterator
+            // provides no useful size hint.
+            let changed_slots = account.storage.values().filter(|value| value.is_changed()).count();
+            if changed_slots > 0 {
+                let mut storage = HashedStorage::with_capacity(false, changed_slots);
+                storage.storage.extend(
+                    account
+                        .storage
+                        .into_iter()
+                        .filter(|(_slot, value)| value.is_changed())
+                        .map(|(slot, value)| (KH::hash_key(B256::from(slot)), value.present_value)),
+                );
+                hashed_state.storages.insert(hashed_address, storage);
             }
         }
     }
@@ -591,6 +820,27 @@ mod tests {
         assert_eq!(hint.storages[&storage_account], vec![storage_slot]);
     }
 
+    #[test]
+    fn access_lists_convert_to_hashed_hint() {
+        use alloy_eips::eip2930::AccessListItem;
+        use alloy_primitives::{keccak256, Address};
+
+        let address = Address::repeat_byte(0x01);
+        let storage_address = Address::repeat_byte(0x02);
+        let slot = B256::repeat_byte(0x03);
+        let lists = vec![
+            AccessList(vec![AccessListItem { address, storage_keys: vec![] }]),
+            AccessList(vec![AccessListItem { address: storage_address, storage_keys: vec![slot] }]),
+        ];
+
+        let hint = StateAccessHint::from_access_lists(lists);
+        assert_eq!(hint.accounts, vec![keccak256(address), keccak256(storage_address)]);
+        assert_eq!(hint.storages.len(), 1);
+        assert_eq!(hint.storages[&keccak256(storage_address)], vec![keccak256(slot)]);
+
+        assert!(StateAccessHint::from_access_lists([AccessList::default()]).is_empty());
+    }
+
     #[test]
     fn state_root_capabilities_forward_to_sink() {
         let sink = Arc::new(CountingSink::default());
@@ -654,6 +904,75 @@ mod tests {
         let _ = handle.take_hashed_update_stream();
     }
 
+    #[test]
+    fn update_observer_sees_state_updates_before_the_task() {
+        let (updates_tx, updates_rx) = crossbeam_channel::unbounded();
+        let (cancel_guard, _cancel_rx) = StateRootTaskCancelGuard::channel();
+        let (_state_root_tx, state_root_rx) = std::sync::mpsc::channel();
+        let (_hashed_state_tx, hashed_state_rx) = std::sync::mpsc::channel();
+        let observed = Arc::new(AtomicUsize::new(0));
+        let mut handle = StateRootHandle::new(
+            B256::ZERO,
+            updates_tx,
+            cancel_guard,
+            state_root_rx,
+            hashed_state_rx,
+        )
+        .with_update_observer({
+            let observed = observed.clone();
+            move |state| {
+                assert!(state.is_empty());
+                observed.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        {
+            let mut hook = handle.take_execution_hook();
+            hook.on_state(EvmState::default());
+            assert_eq!(observed.load(Ordering::Relaxed), 1);
+        }
+
+        assert!(matches!(updates_rx.try_recv(), Ok(StateRootMessage::StateUpdate(_))));
+        assert!(matches!(updates_rx.try_recv(), Ok(StateRootMessage::FinishedStateUpdates)));
+        assert_eq!(observed.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn try_wait_polls_without_blocking() {
+        let (updates_tx, _updates_rx) = crossbeam_channel::unbounded();
+        let (cancel_guard, _cancel_rx) = StateRootTaskCancelGuard::channel();
+        let (state_root_tx, state_root_rx) = std::sync::mpsc::channel();
+        let (_hashed_state_tx, hashed_state_rx) = std::sync::mpsc::channel();
+        let mut handle = StateRootHandle::new(
+            B256::ZERO,
+            updates_tx,
+            cancel_guard,
+            state_root_rx,
+            hashed_state_rx,
+        );
+
+        assert!(handle.try_wait().is_none());
+
+        state_root_tx.send(Err(StateRootTaskError::Stalled)).unwrap();
+        assert!(matches!(handle.try_wait(), Some(Err(StateRootTaskError::Stalled))));
+        assert!(handle.state_root_rx.is_none());
+
+        // a task that exits without a result is reported as an error
+        let (updates_tx, _updates_rx) = crossbeam_channel::unbounded();
+        let (cancel_guard, _cancel_rx) = StateRootTaskCancelGuard::channel();
+        let (state_root_tx, state_root_rx) = std::sync::mpsc::channel();
+        let (_hashed_state_tx, hashed_state_rx) = std::sync::mpsc::channel();
+        let mut handle = StateRootHandle::new(
+            B256::ZERO,
+            updates_tx,
+            cancel_guard,
+            state_root_rx,
+            hashed_state_rx,
+        );
+        drop(state_root_tx);
+        assert!(matches!(handle.try_wait(), Some(Err(StateRootTaskError::Other(_)))));
+    }
+
     /// Lifecycle of the opaque handle a strategy hands to the payload builder: the execution
     /// hook streams updates into the sink and signals completion on drop, the hashed-state
     /// receiver can be taken exactly once, and the outcome arrives through the state-root
@@ -687,6 +1006,10 @@ mod tests {
                 state_root: B256::repeat_byte(0x42),
                 trie_updates: Arc::new(TrieUpdates::default()),
                 hashed_state: Arc::new(HashedPostState::default()),
+                total_time: Duration::ZERO,
+                proof_time: Duration::ZERO,
+                sparse_trie_time: Duration::ZERO,
+                fetched_proof_targets: Default::default(),
                 #[cfg(feature = "trie-debug")]
                 debug_recorders: Vec::new(),
             }))
@@ -705,6 +1028,71 @@ mod tests {
         let _ = handle.take_state_root_rx();
     }
 
+    #[test]
+    fn state_root_handle_shares_task_load() {
+        let (updates_tx, _updates_rx) = crossbeam_channel::unbounded();
+        let (cancel_guard, _cancel_rx) = StateRootTaskCancelGuard::channel();
+        let (_state_root_tx, state_root_rx) = std::sync::mpsc::channel();
+        let (_hashed_state_tx, hashed_state_rx) = std::sync::mpsc::channel();
+        let task_load = StateRootTaskLoad::new(2);
+        let handle = StateRootHandle::new(
+            B256::ZERO,
+            updates_tx,
+            cancel_guard,
+            state_root_rx,
+            hashed_state_rx,
+        )
+        .with_load(task_load.clone());
+
+        let load = handle.load();
+        assert_eq!(load.max_concurrent(), 2);
+        assert_eq!((load.in_flight(), load.pending()), (0, 0));
+        assert!(!load.is_saturated());
+
+        task_load.update(2, 7);
+        assert_eq!((load.in_flight(), load.pending()), (2, 7));
+        assert!(load.is_saturated());
+    }
+
+    #[test]
+    fn evm_state_is_hashed_with_custom_hasher() {
+        use alloy_primitives::{keccak256, Address, U256};
+        use revm::state::{Account, AccountInfo, AccountStatus, EvmStorageSlot, TransactionId};
+
+        /// Uses the left padded key itself as its hash.
+        #[derive(Clone, Default)]
+        struct PlainKeyHasher;
+
+        impl KeyHasher for PlainKeyHasher {
+            fn hash_key<T: AsRef<[u8]>>(bytes: T) -> B256 {
+                B256::left_padding_from(bytes.as_ref())
+            }
+        }
+
+        let address = Address::repeat_byte(0x11);
+        let slot = U256::from(7);
+        let mut account = Account::default();
+        account.info = AccountInfo { balance: U256::from(1), ..Default::default() };
+        account.status = AccountStatus::Touched;
+        account.storage.insert(
+            slot,
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(9), TransactionId::ZERO),
+        );
+        let mut update = EvmState::default();
+        update.insert(address, account);
+
+        let hashed = evm_state_to_hashed_post_state_with_hasher::<PlainKeyHasher>(update.clone());
+        let plain_address = B256::left_padding_from(address.as_slice());
+        assert!(hashed.accounts.contains_key(&plain_address));
+        assert_eq!(hashed.storages[&plain_address].storage[&B256::from(slot)], U256::from(9));
+
+        // the default keeps hashing with keccak256
+        let hashed = evm_state_to_hashed_post_state(update);
+        assert!(hashed.storages[&keccak256(address)]
+            .storage
+            .contains_key(&keccak256(B256::from(slot))));
+    }
+
     #[test]
     fn payload_state_root_receiver_retains_cancellation() {
         let (updates_tx, _updates_rx) = crossbeam_channel::unbounded();
@@ -423,6 +423,34 @@ where
             .collect()
     }
 
+    /// Takes the updates of the given storage tries whose roots are already calculated.
+    ///
+    /// The updates of such a trie are final up to this point. If the trie changes later, the new
+    /// updates supersede the taken ones when applied on top of them with
+    /// [`StorageTrieUpdates::extend`]. Blind tries and tries with pending hash calculations are
+    /// skipped.
+    pub fn take_finalized_storage_trie_updates(
+        &mut self,
+        addresses: impl IntoIterator<Item = B256>,
+    ) -> B256Map<StorageTrieUpdates> {
+        addresses
+            .into_iter()
+            .filter_map(|address| {
+                let trie = self.storage.tries.get_mut(&address)?;
+                if !trie.is_root_cached() {
+                    return None
+                }
+                let updates = trie.as_revealed_mut()?.take_updates();
+                let updates = StorageTrieUpdates {
+                    is_deleted: updates.wiped,
+                    storage_nodes: updates.updated_nodes,
+                    removed_nodes: updates.removed_nodes,
+                };
+                (!updates.is_empty()).then_some((address, updates))
+            })
+            .collect()
+    }
+
     /// Returns [`TrieUpdates`] by taking the updates from the revealed sparse tries.
     ///
     /// Returns `None` if the accounts trie is not revealed.